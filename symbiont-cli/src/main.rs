@@ -3,9 +3,14 @@
 use clap::{Parser, Subcommand, ValueEnum};
 use std::io::Write;
 use symbiont_core::capability::common;
-use symbiont_sim::network::NetworkConfig;
-use symbiont_sim::runner::{quick_run, SimulationConfig, SimulationRunner};
-use symbiont_sim::scenarios::{AdversaryScenario, TrustEmergenceScenario, WorkflowScenario};
+use symbiont_core::trust::explain_trust;
+use symbiont_sim::events::AgentType;
+use symbiont_sim::network::{NetworkConfig, NetworkSnapshot};
+use symbiont_sim::runner::{quick_run, sweep_values, SimulationConfig, SimulationRunner};
+use symbiont_sim::scenarios::{
+    AdversaryScenario, ChurnScenario, ConsensusScenario, FileScenario, PartitionScenario,
+    TrustEmergenceScenario, WorkflowScenario,
+};
 use symbiont_sim::scenarios::adversary::AdversaryType;
 use symbiont_sim::scenarios::workflow::WorkflowType;
 use symbiont_sim::scenarios::Scenario;
@@ -23,8 +28,12 @@ enum Commands {
     /// Run a simulation scenario
     Run {
         /// Scenario to run
-        #[arg(short, long, value_enum)]
-        scenario: ScenarioArg,
+        #[arg(short, long, value_enum, required_unless_present = "scenario_file")]
+        scenario: Option<ScenarioArg>,
+
+        /// Load the scenario from a JSON spec instead
+        #[arg(long, conflicts_with = "scenario")]
+        scenario_file: Option<String>,
 
         /// Number of nodes
         #[arg(short, long, default_value = "20")]
@@ -46,6 +55,26 @@ enum Commands {
         #[arg(long)]
         export_trust: Option<String>,
 
+        /// Export per-node trust trajectories to CSV file
+        #[arg(long)]
+        export_node_trust: Option<String>,
+
+        /// Export all metrics to a JSON file
+        #[arg(long)]
+        export_json: Option<String>,
+
+        /// Write final metrics in Prometheus text format
+        #[arg(long)]
+        metrics_prometheus: Option<String>,
+
+        /// Export final network topology (GraphML if the path ends in .graphml, DOT otherwise)
+        #[arg(long)]
+        export_graph: Option<String>,
+
+        /// Save the final network state for `replay` (JSON if the path ends in .json)
+        #[arg(long)]
+        export_snapshot: Option<String>,
+
         /// For adversary scenarios: when to inject
         #[arg(long, default_value = "50")]
         inject_at: u64,
@@ -58,11 +87,77 @@ enum Commands {
         #[arg(long, default_value = "200")]
         defect_at: u64,
 
+        /// For the consensus scenario: decision criticality (agree-to-disagree needs < 0.8)
+        #[arg(long, default_value = "0.5")]
+        criticality: f64,
+
         /// Verbose output
         #[arg(short, long)]
         verbose: bool,
     },
 
+    /// Sweep a protocol parameter and print a CSV results table
+    Sweep {
+        /// Parameter to sweep (gamma, mu, alpha, delta)
+        #[arg(long)]
+        param: String,
+
+        /// First parameter value
+        #[arg(long)]
+        from: f64,
+
+        /// Last parameter value
+        #[arg(long)]
+        to: f64,
+
+        /// Number of evenly spaced values
+        #[arg(long, default_value = "5")]
+        steps: usize,
+
+        /// Scenario to run at each value
+        #[arg(short, long, value_enum, default_value = "trust-emergence")]
+        scenario: ScenarioArg,
+
+        /// Number of nodes
+        #[arg(short, long, default_value = "20")]
+        nodes: usize,
+
+        /// Number of ticks per run
+        #[arg(short, long, default_value = "500")]
+        ticks: u64,
+
+        /// First seed; runs use consecutive seeds from here
+        #[arg(long, default_value = "0")]
+        seed: u64,
+
+        /// Runs per parameter value
+        #[arg(long, default_value = "1")]
+        runs: u64,
+
+        /// Connection probability
+        #[arg(long, default_value = "0.3")]
+        connection_prob: f64,
+    },
+
+    /// Continue a run from a saved snapshot
+    Replay {
+        /// Snapshot written by `run --export-snapshot`
+        #[arg(long)]
+        snapshot: String,
+
+        /// Number of ticks to simulate past the snapshot
+        #[arg(short, long, default_value = "200")]
+        ticks: u64,
+
+        /// Random seed (defaults to the snapshot's)
+        #[arg(long)]
+        seed: Option<u64>,
+
+        /// Agent attached to every restored node
+        #[arg(long, value_enum, default_value = "honest")]
+        agent_profile: AgentProfile,
+    },
+
     /// Quick simulation with defaults
     Quick {
         /// Number of nodes
@@ -82,6 +177,27 @@ enum Commands {
     },
 }
 
+/// Behavior given to restored nodes, since agents aren't saved in snapshots
+#[derive(Clone, Copy, ValueEnum)]
+enum AgentProfile {
+    /// Honest agents of good quality
+    Honest,
+    /// Agents that never initiate interactions
+    Passive,
+    /// Agents that take more than they give
+    FreeRider,
+}
+
+impl AgentProfile {
+    fn agent_type(self) -> AgentType {
+        match self {
+            AgentProfile::Honest => AgentType::Honest { interaction_rate: 0.5, base_quality: 0.8 },
+            AgentProfile::Passive => AgentType::Passive,
+            AgentProfile::FreeRider => AgentType::FreeRider { interaction_rate: 0.5 },
+        }
+    }
+}
+
 #[derive(Clone, Copy, ValueEnum)]
 enum ScenarioArg {
     /// Trust emergence in honest network
@@ -92,6 +208,14 @@ enum ScenarioArg {
     FreeRider,
     /// Sybil cluster injection
     Sybil,
+    /// Whitewasher injection (identity churn)
+    Whitewasher,
+    /// Continuous membership churn
+    Churn,
+    /// Network split into two halves, then healed
+    Partition,
+    /// Polarized decision with agree-to-disagree fallback
+    Consensus,
     /// Chain workflow routing
     WorkflowChain,
     /// Fan-out/fan-in workflow
@@ -112,30 +236,108 @@ fn main() {
     match cli.command {
         Commands::Run {
             scenario,
+            scenario_file,
             nodes,
             ticks,
             seed,
             connection_prob,
             export_trust,
+            export_node_trust,
+            export_json,
+            metrics_prometheus,
+            export_graph,
+            export_snapshot,
             inject_at,
             adversary_count,
             defect_at,
+            criticality,
             verbose,
         } => {
             run_simulation(
                 scenario,
+                scenario_file,
                 nodes,
                 ticks,
                 seed,
                 connection_prob,
                 export_trust,
+                export_node_trust,
+                export_json,
+                metrics_prometheus,
+                export_graph,
+                export_snapshot,
                 inject_at,
                 adversary_count,
                 defect_at,
+                criticality,
                 verbose,
             );
         }
 
+        Commands::Replay {
+            snapshot,
+            ticks,
+            seed,
+            agent_profile,
+        } => {
+            let loaded = match load_snapshot(&snapshot) {
+                Ok(loaded) => loaded,
+                Err(e) => {
+                    eprintln!("Error loading {snapshot}: {e}");
+                    std::process::exit(2);
+                }
+            };
+            let start_tick = loaded.tick;
+            let network = NetworkConfig { seed, ..Default::default() };
+            let config = SimulationConfig::default().with_ticks(ticks).with_network(network);
+
+            println!("Replaying {snapshot} from tick {start_tick} for {ticks} ticks");
+            let mut runner =
+                SimulationRunner::from_snapshot(config, loaded, agent_profile.agent_type());
+            let result = runner.run();
+            print_result(&result, &runner);
+        }
+
+        Commands::Sweep {
+            param,
+            from,
+            to,
+            steps,
+            scenario,
+            nodes,
+            ticks,
+            seed,
+            runs,
+            connection_prob,
+        } => {
+            let config = SimulationConfig::default()
+                .with_ticks(ticks)
+                .with_network(network_config(nodes, connection_prob, None));
+            let options = ScenarioOptions::default();
+            let Some(last_seed) = seed.checked_add(runs.max(1) - 1) else {
+                eprintln!("Error: --seed {seed} with --runs {runs} runs past the largest seed");
+                std::process::exit(2);
+            };
+            let seeds: Vec<u64> = (seed..=last_seed).collect();
+
+            let sweep = SimulationRunner::run_sweep(
+                config,
+                || build_scenario(scenario, options),
+                &param,
+                &sweep_values(from, to, steps),
+                &seeds,
+            );
+            match sweep {
+                Ok(sweep) => {
+                    sweep.export_csv(&mut std::io::stdout()).expect("Failed to write CSV");
+                }
+                Err(e) => {
+                    eprintln!("Error: {e}");
+                    std::process::exit(2);
+                }
+            }
+        }
+
         Commands::Quick { nodes, ticks } => {
             println!("Running quick simulation: {nodes} nodes, {ticks} ticks");
             let result = quick_run(nodes, ticks);
@@ -151,18 +353,159 @@ fn main() {
 
 #[allow(clippy::too_many_arguments)]
 fn run_simulation(
-    scenario_arg: ScenarioArg,
+    scenario_arg: Option<ScenarioArg>,
+    scenario_file: Option<String>,
     nodes: usize,
     ticks: u64,
     seed: Option<u64>,
     connection_prob: f64,
     export_trust: Option<String>,
+    export_node_trust: Option<String>,
+    export_json: Option<String>,
+    metrics_prometheus: Option<String>,
+    export_graph: Option<String>,
+    export_snapshot: Option<String>,
     inject_at: u64,
     adversary_count: usize,
     defect_at: u64,
+    criticality: f64,
     verbose: bool,
 ) {
-    // Build network config
+    // Create scenario and network config
+    let options = ScenarioOptions {
+        inject_at,
+        adversary_count,
+        defect_at,
+        criticality,
+    };
+    let base_network = network_config(nodes, connection_prob, seed);
+    let (scenario, network): (Box<dyn Scenario>, NetworkConfig) = match scenario_file {
+        Some(path) => match FileScenario::from_file(&path) {
+            Ok(scenario) => {
+                let network = scenario.network_config(base_network);
+                (Box::new(scenario), network)
+            }
+            Err(e) => {
+                eprintln!("Error loading {path}: {e}");
+                std::process::exit(2);
+            }
+        },
+        None => {
+            let scenario_arg = scenario_arg.expect("clap requires --scenario or --scenario-file");
+            (build_scenario(scenario_arg, options), base_network)
+        }
+    };
+    let node_count = network.node_count;
+    let sim_config = SimulationConfig::default().with_ticks(ticks).with_network(network);
+
+    println!("Running scenario: {}", scenario.name());
+    println!("  Description: {}", scenario.description());
+    println!("  Nodes: {node_count}, Ticks: {ticks}");
+    println!();
+
+    // Run simulation with progress
+    let mut runner = SimulationRunner::new(sim_config);
+    runner.apply_scenario(scenario.as_ref());
+
+    let (result, runner) = if verbose {
+        // Add progress callback
+        let mut runner = runner.on_progress(Box::new(move |current, total| {
+            print!("\rProgress: {}/{} ({:.1}%)", current, total, (current as f64 / total as f64) * 100.0);
+            std::io::stdout().flush().unwrap();
+        }));
+        let result = runner.run();
+        println!(); // New line after progress
+        (result, runner)
+    } else {
+        let result = runner.run();
+        (result, runner)
+    };
+
+    print_result(&result, &runner);
+
+    // Export if requested
+    if let Some(path) = export_trust {
+        let mut file = std::fs::File::create(&path).expect("Failed to create export file");
+        runner.network().metrics.export_trust_csv(&mut file).expect("Failed to write CSV");
+        println!("\nExported trust history to: {path}");
+    }
+
+    if let Some(path) = export_node_trust {
+        let mut file = std::fs::File::create(&path).expect("Failed to create export file");
+        runner.network().metrics.export_node_trust_csv(&mut file).expect("Failed to write CSV");
+        println!("Exported node trust trajectories to: {path}");
+    }
+
+    if let Some(path) = export_json {
+        let mut file = std::fs::File::create(&path).expect("Failed to create export file");
+        runner.network().metrics.export_json(&mut file).expect("Failed to write JSON");
+        println!("Exported metrics to: {path}");
+    }
+
+    if let Some(path) = metrics_prometheus {
+        let mut file = std::fs::File::create(&path).expect("Failed to create export file");
+        runner
+            .network()
+            .metrics
+            .export_prometheus(&mut file)
+            .expect("Failed to write Prometheus metrics");
+        println!("Exported Prometheus metrics to: {path}");
+    }
+
+    if let Some(path) = export_graph {
+        let mut file = std::fs::File::create(&path).expect("Failed to create export file");
+        if path.ends_with(".graphml") {
+            runner.network().export_graphml(&mut file).expect("Failed to write GraphML");
+        } else {
+            runner.network().export_dot(&mut file).expect("Failed to write DOT");
+        }
+        println!("Exported network topology to: {path}");
+    }
+
+    if let Some(path) = export_snapshot {
+        let snapshot = runner.network().snapshot();
+        let bytes = if path.ends_with(".json") {
+            serde_json::to_vec(&snapshot).expect("Failed to encode snapshot")
+        } else {
+            snapshot.to_bytes().expect("Failed to encode snapshot")
+        };
+        std::fs::write(&path, bytes).expect("Failed to write snapshot");
+        println!("Exported network snapshot to: {path}");
+    }
+}
+
+/// Read a snapshot saved by `run --export-snapshot`
+fn load_snapshot(path: &str) -> Result<NetworkSnapshot, String> {
+    let bytes = std::fs::read(path).map_err(|e| e.to_string())?;
+    if path.ends_with(".json") {
+        NetworkSnapshot::from_json(&bytes).map_err(|e| e.to_string())
+    } else {
+        NetworkSnapshot::from_bytes(&bytes).map_err(|e| e.to_string())
+    }
+}
+
+/// Scenario settings shared by `run` and `sweep`
+#[derive(Clone, Copy)]
+struct ScenarioOptions {
+    inject_at: u64,
+    adversary_count: usize,
+    defect_at: u64,
+    criticality: f64,
+}
+
+impl Default for ScenarioOptions {
+    fn default() -> Self {
+        Self {
+            inject_at: 50,
+            adversary_count: 3,
+            defect_at: 200,
+            criticality: 0.5,
+        }
+    }
+}
+
+/// Network with every common capability on each node
+fn network_config(nodes: usize, connection_prob: f64, seed: Option<u64>) -> NetworkConfig {
     let mut network_config = NetworkConfig::default()
         .with_nodes(nodes)
         .with_capability(common::analysis())
@@ -175,79 +518,65 @@ fn run_simulation(
         network_config = network_config.with_seed(s);
     }
 
-    // Build simulation config
-    let sim_config = SimulationConfig::default()
-        .with_ticks(ticks)
-        .with_network(network_config);
+    network_config
+}
 
-    // Create scenario
-    let scenario: Box<dyn Scenario> = match scenario_arg {
+/// Construct the scenario selected on the command line
+fn build_scenario(scenario_arg: ScenarioArg, options: ScenarioOptions) -> Box<dyn Scenario> {
+    match scenario_arg {
         ScenarioArg::TrustEmergence => {
             Box::new(TrustEmergenceScenario::new())
         }
         ScenarioArg::Strategic => {
             Box::new(
                 AdversaryScenario::new(AdversaryType::Strategic)
-                    .inject_at(inject_at)
-                    .with_count(adversary_count)
-                    .defect_at(defect_at),
+                    .inject_at(options.inject_at)
+                    .with_count(options.adversary_count)
+                    .defect_at(options.defect_at),
             )
         }
         ScenarioArg::FreeRider => {
             Box::new(
                 AdversaryScenario::new(AdversaryType::FreeRider)
-                    .inject_at(inject_at)
-                    .with_count(adversary_count),
+                    .inject_at(options.inject_at)
+                    .with_count(options.adversary_count),
             )
         }
         ScenarioArg::Sybil => {
             Box::new(
                 AdversaryScenario::new(AdversaryType::Sybil)
-                    .inject_at(inject_at)
-                    .with_count(adversary_count),
+                    .inject_at(options.inject_at)
+                    .with_count(options.adversary_count),
             )
         }
+        ScenarioArg::Whitewasher => {
+            Box::new(
+                AdversaryScenario::new(AdversaryType::Whitewasher)
+                    .inject_at(options.inject_at)
+                    .with_count(options.adversary_count),
+            )
+        }
+        ScenarioArg::Churn => {
+            Box::new(ChurnScenario::new())
+        }
+        ScenarioArg::Partition => {
+            Box::new(PartitionScenario::new())
+        }
+        ScenarioArg::Consensus => {
+            Box::new(ConsensusScenario::new().with_criticality(options.criticality))
+        }
         ScenarioArg::WorkflowChain => {
             Box::new(WorkflowScenario::new(WorkflowType::Chain))
         }
         ScenarioArg::WorkflowFanOut => {
             Box::new(WorkflowScenario::new(WorkflowType::FanOutFanIn))
         }
-    };
-
-    println!("Running scenario: {}", scenario.name());
-    println!("  Description: {}", scenario.description());
-    println!("  Nodes: {nodes}, Ticks: {ticks}");
-    println!();
-
-    // Run simulation with progress
-    let mut runner = SimulationRunner::new(sim_config);
-    runner.apply_scenario(scenario.as_ref());
-
-    let (result, runner) = if verbose {
-        // Add progress callback
-        let mut runner = runner.on_progress(Box::new(move |current, total| {
-            print!("\rProgress: {}/{} ({:.1}%)", current, total, (current as f64 / total as f64) * 100.0);
-            std::io::stdout().flush().unwrap();
-        }));
-        let result = runner.run();
-        println!(); // New line after progress
-        (result, runner)
-    } else {
-        let result = runner.run();
-        (result, runner)
-    };
-
-    print_result(&result, &runner);
-
-    // Export if requested
-    if let Some(path) = export_trust {
-        let mut file = std::fs::File::create(&path).expect("Failed to create export file");
-        runner.network().metrics.export_trust_csv(&mut file).expect("Failed to write CSV");
-        println!("\nExported trust history to: {path}");
     }
 }
 
+/// Most flagged nodes whose trust breakdown is printed
+const MAX_EXPLAINED_NODES: usize = 10;
+
 fn print_result(result: &symbiont_sim::runner::SimulationResult, runner: &SimulationRunner) {
     println!("\n{}", result.summary);
     println!("Duration: {}ms", result.duration_ms);
@@ -259,6 +588,26 @@ fn print_result(result: &symbiont_sim::runner::SimulationResult, runner: &Simula
     println!("  Connections: {}", stats.connection_count);
     println!("  Mean Trust: {:.3}", stats.mean_trust);
     println!("  Mean Connections/Node: {:.1}", stats.mean_connections);
+    println!("  Alert Level: {:.3}", stats.alert_level);
+    println!(
+        "  Defense States: {} normal, {} primed, {} defending",
+        stats.defense.normal, stats.defense.primed, stats.defense.defending
+    );
+    println!("  Metrics Digest: {}", runner.network().metrics.digest().to_hex());
+
+    // Explain trust for flagged nodes, capped to keep output short
+    let mut flagged: Vec<_> =
+        runner.network().nodes().values().filter(|n| !n.flags.is_empty()).collect();
+    if !flagged.is_empty() {
+        flagged.sort_by_key(|n| n.id);
+        println!("\nFlagged Nodes: {}", flagged.len());
+        for node in flagged.iter().take(MAX_EXPLAINED_NODES) {
+            let mut flags: Vec<_> = node.flags.iter().map(|f| format!("{f:?}")).collect();
+            flags.sort();
+            println!("  {} [{}]", node.id, flags.join(", "));
+            println!("    {}", explain_trust(node));
+        }
+    }
 }
 
 fn show_info(scenario: Option<ScenarioArg>) {
@@ -277,6 +626,10 @@ fn show_info(scenario: Option<ScenarioArg>) {
                 ScenarioArg::Strategic,
                 ScenarioArg::FreeRider,
                 ScenarioArg::Sybil,
+                ScenarioArg::Whitewasher,
+                ScenarioArg::Churn,
+                ScenarioArg::Partition,
+                ScenarioArg::Consensus,
                 ScenarioArg::WorkflowChain,
                 ScenarioArg::WorkflowFanOut,
             ] {
@@ -319,6 +672,37 @@ fn scenario_details(scenario: ScenarioArg) -> (&'static str, &'static str, &'sta
              Tests diversity requirements and collusion detection.\n\
              Options: --inject-at, --adversary-count",
         ),
+        ScenarioArg::Whitewasher => (
+            "whitewasher",
+            "Whitewasher injection",
+            "Adversaries that provide poor quality and, once their trust\n\
+             falls below a fresh identity's, leave and rejoin as a new node.\n\
+             Tests resistance to reputation laundering via identity churn.\n\
+             Options: --inject-at, --adversary-count",
+        ),
+        ScenarioArg::Churn => (
+            "churn",
+            "Continuous membership churn",
+            "Honest nodes keep joining while members keep leaving,\n\
+             with departures biased toward low-trust members.\n\
+             Tests trust stability as the population turns over.",
+        ),
+        ScenarioArg::Partition => (
+            "partition",
+            "Network partition and healing",
+            "At tick 100 every link between the two halves is cut, and the\n\
+             halves (of differing quality) run independently until tick 300,\n\
+             when the severed links are restored.\n\
+             Measures trust divergence between halves and re-convergence.",
+        ),
+        ScenarioArg::Consensus => (
+            "consensus",
+            "Decision convergence with agree-to-disagree",
+            "Two camps start with opposing positions on a decision, and each\n\
+             tick positions drift toward trusted neighbors. If the decision is\n\
+             stuck and criticality (--criticality) is below 0.8, agree-to-disagree\n\
+             picks the trust-weighted majority and records the dissenters.",
+        ),
         ScenarioArg::WorkflowChain => (
             "workflow-chain",
             "Chain workflow routing test",
@@ -2,6 +2,7 @@
 //!
 //! Each node declares capabilities it can perform, with per-capability quality tracking.
 
+use crate::constants::{CAPABILITY_CONFIDENCE_VOLUME, CAPABILITY_IDLE_TICKS};
 use crate::types::{CapabilityId, Score, Timestamp};
 use serde::{Deserialize, Serialize};
 
@@ -76,6 +77,9 @@ pub struct CapabilityState {
     pub available: bool,
     /// Current load for this capability (0 = idle, 1 = maxed out)
     pub load: Score,
+    /// Maintenance ticks since this capability was last used
+    #[serde(default)]
+    pub idle_ticks: u32,
 }
 
 impl CapabilityState {
@@ -88,6 +92,7 @@ impl CapabilityState {
             last_used: Timestamp::now(),
             available: true,
             load: Score::ZERO,
+            idle_ticks: 0,
         }
     }
 
@@ -96,6 +101,22 @@ impl CapabilityState {
         self.available && self.load.value() < 0.95
     }
 
+    /// How far the quality estimate can be relied on, from how often it was used
+    ///
+    /// c = n / (n + CAPABILITY_CONFIDENCE_VOLUME), for n = volume
+    pub fn quality_confidence(&self) -> f64 {
+        let n = self.volume as f64;
+        n / (n + CAPABILITY_CONFIDENCE_VOLUME)
+    }
+
+    /// Quality shrunk toward neutral by confidence
+    ///
+    /// q_eff = 0.5 + c × (q - 0.5), so one lucky use can't rank a capability
+    /// alongside a proven one
+    pub fn effective_quality(&self) -> Score {
+        Score::new(0.5 + self.quality_confidence() * (self.quality.value() - 0.5))
+    }
+
     /// Update quality with new observation using EMA
     pub fn update_quality(&mut self, observed: Score, lambda: f64) {
         let new_quality = lambda * self.quality.value() + (1.0 - lambda) * observed.value();
@@ -103,10 +124,39 @@ impl CapabilityState {
     }
 
     /// Record usage of this capability
+    ///
+    /// Re-exercising restarts the idle clock and makes the capability
+    /// available again unless it is overloaded.
     pub fn record_usage(&mut self, quality: Score, lambda: f64) {
         self.update_quality(quality, lambda);
+        self.mark_used();
+    }
+
+    /// Record that this capability was exercised, without judging its quality
+    ///
+    /// For work whose outcome says nothing about this node's own quality,
+    /// such as serving a partner whose output quality is what got rated.
+    pub fn mark_used(&mut self) {
         self.volume += 1;
         self.last_used = Timestamp::now();
+        self.idle_ticks = 0;
+        self.available = self.load.value() < 0.9;
+    }
+
+    /// Advance the idle clock, fading quality toward neutral once stale
+    ///
+    /// After CAPABILITY_IDLE_TICKS unused ticks, each call applies
+    /// q_new = 0.5 + (q - 0.5) × rate
+    pub fn decay_quality(&mut self, rate: f64) {
+        self.idle_ticks = self.idle_ticks.saturating_add(1);
+        if self.idle_ticks > CAPABILITY_IDLE_TICKS {
+            self.quality = Score::new(0.5 + (self.quality.value() - 0.5) * rate);
+        }
+    }
+
+    /// Add load from incoming work (clamped to 1)
+    pub fn add_load(&mut self, amount: f64) {
+        self.load = Score::new(self.load.value() + amount);
     }
 
     /// Apply load decay (called periodically)
@@ -139,6 +189,17 @@ pub mod common {
         Capability::from_name("validation", CapabilityCategory::Validation)
             .with_description("Verification and validation")
     }
+
+    /// Look up a common capability by its name
+    pub fn by_name(name: &str) -> Option<Capability> {
+        match name {
+            "analysis" => Some(analysis()),
+            "generation" => Some(generation()),
+            "transformation" => Some(transformation()),
+            "validation" => Some(validation()),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -152,6 +213,12 @@ mod tests {
         assert_eq!(cap.category, CapabilityCategory::Analysis);
     }
 
+    #[test]
+    fn test_common_by_name() {
+        assert_eq!(common::by_name("validation").unwrap().id, common::validation().id);
+        assert!(common::by_name("juggling").is_none());
+    }
+
     #[test]
     fn test_capability_state() {
         let cap = common::analysis();
@@ -166,6 +233,56 @@ mod tests {
         assert!(state.quality.value() > 0.5);
     }
 
+    #[test]
+    fn test_quality_confidence_grows_with_volume() {
+        let mut state = CapabilityState::new(common::analysis());
+        state.quality = Score::new(0.9);
+        assert_eq!(state.quality_confidence(), 0.0);
+        assert_eq!(state.effective_quality(), Score::HALF);
+
+        state.volume = CAPABILITY_CONFIDENCE_VOLUME as u32;
+        assert!((state.quality_confidence() - 0.5).abs() < 1e-9);
+        assert!((state.effective_quality().value() - 0.7).abs() < 1e-9);
+
+        state.volume = 500;
+        assert!(state.quality_confidence() > 0.95);
+        assert!(state.effective_quality().value() > 0.88);
+
+        // Poor quality is pulled up toward neutral the same way
+        state.quality = Score::new(0.1);
+        state.volume = 1;
+        assert!(state.effective_quality().value() > 0.45);
+    }
+
+    #[test]
+    fn test_idle_capability_decays_to_neutral() {
+        let mut state = CapabilityState::new(common::analysis());
+        for _ in 0..50 {
+            state.record_usage(Score::new(0.95), 0.9);
+        }
+        let learned = state.quality.value();
+        assert!(learned > 0.9);
+
+        // Recently used capabilities keep their quality
+        for _ in 0..CAPABILITY_IDLE_TICKS {
+            state.decay_quality(0.99);
+        }
+        assert_eq!(state.quality.value(), learned);
+
+        for _ in 0..500 {
+            state.decay_quality(0.99);
+        }
+        let faded = state.quality.value();
+        assert!(faded > 0.5 && faded < 0.5 + (learned - 0.5) * 0.01, "{faded}");
+
+        // Using it again restarts the clock
+        state.available = false;
+        state.record_usage(Score::new(0.95), 0.9);
+        assert_eq!(state.idle_ticks, 0);
+        assert!(state.available);
+        assert!(state.quality.value() > faded);
+    }
+
     #[test]
     fn test_load_management() {
         let cap = common::generation();
@@ -0,0 +1,109 @@
+//! Time sources for Symbiont.
+//!
+//! Protocol timing (idle connections, signal expiry) is measured in
+//! milliseconds. Live nodes read the wall clock; simulations drive a
+//! virtual clock that advances a fixed amount per tick, so thousands of
+//! ticks span as much protocol time as they represent.
+
+use crate::types::Timestamp;
+
+/// A source of the current time
+pub trait Clock: Send + Sync {
+    /// Current time
+    fn now(&self) -> Timestamp;
+
+    /// Note that a simulation has reached `tick` (ignored by real clocks)
+    fn on_tick(&mut self, _tick: u64) {}
+}
+
+/// The system wall clock
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Timestamp {
+        Timestamp::now()
+    }
+}
+
+/// A clock driven by simulation ticks
+///
+/// now = start + tick × ms_per_tick
+#[derive(Debug, Clone)]
+pub struct VirtualClock {
+    /// Time at tick 0
+    start: Timestamp,
+    /// Milliseconds that pass per tick
+    ms_per_tick: u64,
+    /// Latest tick reached
+    tick: u64,
+}
+
+impl VirtualClock {
+    /// Create a clock starting now and advancing `ms_per_tick` per tick
+    ///
+    /// Starting at the wall clock keeps virtual time comparable with
+    /// timestamps taken before the clock existed.
+    pub fn new(ms_per_tick: u64) -> Self {
+        Self::starting_at(Timestamp::now(), ms_per_tick)
+    }
+
+    /// Create a clock starting at a fixed time
+    pub fn starting_at(start: Timestamp, ms_per_tick: u64) -> Self {
+        Self {
+            start,
+            ms_per_tick,
+            tick: 0,
+        }
+    }
+
+    /// Milliseconds that pass per tick
+    pub fn ms_per_tick(&self) -> u64 {
+        self.ms_per_tick
+    }
+
+    /// Move forward one tick
+    pub fn advance(&mut self) {
+        self.tick = self.tick.saturating_add(1);
+    }
+}
+
+impl Clock for VirtualClock {
+    fn now(&self) -> Timestamp {
+        self.start.add_millis(self.tick.saturating_mul(self.ms_per_tick))
+    }
+
+    fn on_tick(&mut self, tick: u64) {
+        self.tick = tick;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_virtual_clock_follows_ticks() {
+        let mut clock = VirtualClock::starting_at(Timestamp::new(5_000), 250);
+        assert_eq!(clock.now(), Timestamp::new(5_000));
+
+        clock.advance();
+        clock.advance();
+        assert_eq!(clock.now(), Timestamp::new(5_500));
+
+        clock.on_tick(40);
+        assert_eq!(clock.now() - Timestamp::new(5_000), 10_000);
+
+        // Far-future ticks saturate instead of wrapping
+        clock.on_tick(u64::MAX);
+        assert_eq!(clock.now(), Timestamp::new(u64::MAX));
+    }
+
+    #[test]
+    fn test_system_clock_reads_wall_time() {
+        let before = Timestamp::now();
+        let now = SystemClock.now();
+        assert!(now >= before);
+        assert!(now - before < 60_000);
+    }
+}
@@ -2,10 +2,11 @@
 //!
 //! Connections represent relationships between nodes, governed by the Physarum equation.
 
-use crate::constants::{ALPHA, DELTA, GAMMA, LAMBDA, MU, THETA};
+use crate::constants::{ALPHA, LAMBDA, THETA};
 use crate::math::{
-    exchange_ratio_log, quality_multiplier, reciprocity_sigmoid, tone_multiplier,
+    exchange_ratio_log, finite_mean, quality_multiplier, reciprocity_sigmoid, tone_multiplier,
 };
+use crate::params::{ProtocolParams, WeightIntegrator};
 use crate::types::{CapabilityId, NodeId, Score, SignedScore, Timestamp, Weight};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -23,6 +24,8 @@ pub struct Connection {
     pub q: Score,
     /// Per-capability quality scores
     pub capability_qualities: HashMap<CapabilityId, Score>,
+    /// Per-capability reciprocity scores
+    pub capability_reciprocity: HashMap<CapabilityId, f64>,
     /// Tone score τ ∈ [-1, 1]
     pub tau: SignedScore,
     /// Priming level π ∈ [0, 1]
@@ -42,6 +45,7 @@ impl Connection {
             r: 0.0,
             q: Score::HALF,
             capability_qualities: HashMap::new(),
+            capability_reciprocity: HashMap::new(),
             tau: SignedScore::ZERO,
             pi: Score::ZERO,
             last_active: Timestamp::now(),
@@ -57,6 +61,7 @@ impl Connection {
             r: 0.0,
             q: Score::HALF,
             capability_qualities: HashMap::new(),
+            capability_reciprocity: HashMap::new(),
             tau: SignedScore::ZERO,
             pi: Score::ZERO,
             last_active: Timestamp::now(),
@@ -91,6 +96,26 @@ impl Connection {
         self.capability_qualities.get(&capability).copied().unwrap_or(self.q)
     }
 
+    /// Update per-capability reciprocity (same rule as global reciprocity)
+    pub fn update_capability_reciprocity(
+        &mut self,
+        capability: CapabilityId,
+        exchange_in: f64,
+        exchange_out: f64,
+        quality: Score,
+    ) {
+        let log_rho = exchange_ratio_log(exchange_in, exchange_out);
+        let quality_adj = THETA * (quality.value() - 0.5);
+        let current = self.capability_reciprocity.get(&capability).copied().unwrap_or(0.0);
+        let new_r = LAMBDA * current + (1.0 - LAMBDA) * (log_rho + quality_adj);
+        self.capability_reciprocity.insert(capability, new_r);
+    }
+
+    /// Get reciprocity for a specific capability (falls back to global reciprocity)
+    pub fn capability_reciprocity(&self, capability: CapabilityId) -> f64 {
+        self.capability_reciprocity.get(&capability).copied().unwrap_or(self.r)
+    }
+
     /// Update tone score using EMA
     pub fn update_tone(&mut self, observed_tone: SignedScore) {
         let new_tau = LAMBDA * self.tau.value() + (1.0 - LAMBDA) * observed_tone.value();
@@ -101,26 +126,106 @@ impl Connection {
     ///
     /// Φ = γ × |Q|^μ × σ(r) × ψ(q) × φ(τ)
     pub fn compute_reinforcement(&self, volume: f64) -> f64 {
-        let flow = volume.abs().powf(MU);
+        self.compute_reinforcement_with(volume, &ProtocolParams::default())
+    }
+
+    /// Compute reinforcement term Φ with γ and μ taken from `params`
+    pub fn compute_reinforcement_with(&self, volume: f64, params: &ProtocolParams) -> f64 {
+        let flow = volume.abs().powf(params.mu);
         let sigma_r = reciprocity_sigmoid(self.r);
         let psi_q = quality_multiplier(self.q);
         let phi_tau = tone_multiplier(self.tau);
 
-        GAMMA * flow * sigma_r.value() * psi_q * phi_tau
+        params.gamma * flow * sigma_r.value() * psi_q * phi_tau
     }
 
     /// Update connection weight based on interaction
     ///
     /// w_new = clamp(w + Δt × (Φ - α×w - D), W_MIN, W_MAX)
     pub fn update_weight(&mut self, volume: f64, threat_level: f64, dt: f64) {
-        let phi = self.compute_reinforcement(volume);
-        let decay = ALPHA * self.w.value();
-        let defense = DELTA * threat_level;
+        self.update_weight_euler_with(volume, threat_level, dt, &ProtocolParams::default());
+    }
+
+    /// Euler weight update with coefficients taken from `params`
+    fn update_weight_euler_with(
+        &mut self,
+        volume: f64,
+        threat_level: f64,
+        dt: f64,
+        params: &ProtocolParams,
+    ) {
+        let phi = self.compute_reinforcement_with(volume, params);
+        let decay = params.alpha * self.w.value();
+        let defense = params.delta * threat_level;
 
         let delta_w = dt * (phi - decay - defense);
         self.w = self.w + delta_w;
     }
 
+    /// Update connection weight with 4th-order Runge-Kutta
+    ///
+    /// Integrates dw/dt = Φ - α×w - D over `dt` in `substeps` steps,
+    /// clamping to [W_MIN, W_MAX] after each step.
+    pub fn update_weight_rk4(&mut self, volume: f64, threat_level: f64, dt: f64, substeps: u32) {
+        let params = ProtocolParams::default();
+        self.update_weight_rk4_with(volume, threat_level, dt, substeps, &params);
+    }
+
+    /// RK4 weight update with coefficients taken from `params`
+    fn update_weight_rk4_with(
+        &mut self,
+        volume: f64,
+        threat_level: f64,
+        dt: f64,
+        substeps: u32,
+        params: &ProtocolParams,
+    ) {
+        let phi = self.compute_reinforcement_with(volume, params);
+        let defense = params.delta * threat_level;
+        let dw = |w: f64| phi - params.alpha * w - defense;
+
+        let steps = substeps.max(1);
+        let h = dt / steps as f64;
+        for _ in 0..steps {
+            let w = self.w.value();
+            let k1 = dw(w);
+            let k2 = dw(w + h / 2.0 * k1);
+            let k3 = dw(w + h / 2.0 * k2);
+            let k4 = dw(w + h * k3);
+            self.w = self.w + h / 6.0 * (k1 + 2.0 * k2 + 2.0 * k3 + k4);
+        }
+    }
+
+    /// Update connection weight with the given integrator
+    pub fn integrate_weight(
+        &mut self,
+        volume: f64,
+        threat_level: f64,
+        dt: f64,
+        integrator: WeightIntegrator,
+    ) {
+        let params = ProtocolParams::default().with_weight_integrator(integrator);
+        self.integrate_weight_with(volume, threat_level, dt, &params);
+    }
+
+    /// Update connection weight with the integrator and coefficients in `params`
+    pub fn integrate_weight_with(
+        &mut self,
+        volume: f64,
+        threat_level: f64,
+        dt: f64,
+        params: &ProtocolParams,
+    ) {
+        match params.weight_integrator {
+            WeightIntegrator::Euler => {
+                self.update_weight_euler_with(volume, threat_level, dt, params)
+            }
+            WeightIntegrator::Rk4 { substeps } => {
+                self.update_weight_rk4_with(volume, threat_level, dt, substeps, params)
+            }
+        }
+    }
+
     /// Full update from an interaction outcome
     pub fn process_interaction(
         &mut self,
@@ -130,6 +235,29 @@ impl Connection {
         quality: Score,
         tone: SignedScore,
         threat_level: f64,
+    ) {
+        self.process_interaction_with(
+            volume,
+            exchange_in,
+            exchange_out,
+            quality,
+            tone,
+            threat_level,
+            &ProtocolParams::default(),
+        );
+    }
+
+    /// Full update from an interaction outcome under specific protocol settings
+    #[allow(clippy::too_many_arguments)]
+    pub fn process_interaction_with(
+        &mut self,
+        volume: f64,
+        exchange_in: f64,
+        exchange_out: f64,
+        quality: Score,
+        tone: SignedScore,
+        threat_level: f64,
+        params: &ProtocolParams,
     ) {
         // Update reciprocity
         self.update_reciprocity(exchange_in, exchange_out, quality);
@@ -141,7 +269,7 @@ impl Connection {
         self.update_tone(tone);
 
         // Update weight (dt = 1.0 for discrete updates)
-        self.update_weight(volume, threat_level, 1.0);
+        self.integrate_weight_with(volume, threat_level, 1.0, params);
 
         // Update metadata
         self.last_active = Timestamp::now();
@@ -150,8 +278,7 @@ impl Connection {
 
     /// Apply passive decay (for idle connections)
     pub fn apply_decay(&mut self, dt: f64) {
-        let decay = ALPHA * self.w.value() * dt;
-        self.w = Weight::new(self.w.value() - decay);
+        self.w = self.w - ALPHA * self.w.value() * dt;
     }
 
     /// Check if connection is considered idle
@@ -159,6 +286,11 @@ impl Connection {
         self.last_active.is_older_than(threshold_ms)
     }
 
+    /// Check if connection is considered idle as of `now`
+    pub fn is_idle_at(&self, threshold_ms: u64, now: Timestamp) -> bool {
+        self.last_active.is_older_than_at(threshold_ms, now)
+    }
+
     /// Increase priming level
     pub fn increase_priming(&mut self, boost: f64) {
         self.pi = Score::new((self.pi.value() + boost).min(1.0));
@@ -194,10 +326,11 @@ impl ConnectionStats {
             return Self::default();
         }
 
+        // Means skip non-finite values so one bad connection can't poison them
         let count = conns.len();
-        let mean_weight = conns.iter().map(|c| c.w.value()).sum::<f64>() / count as f64;
-        let mean_reciprocity = conns.iter().map(|c| c.r).sum::<f64>() / count as f64;
-        let mean_quality = conns.iter().map(|c| c.q.value()).sum::<f64>() / count as f64;
+        let mean_weight = finite_mean(conns.iter().map(|c| c.w.value())).unwrap_or(0.0);
+        let mean_reciprocity = finite_mean(conns.iter().map(|c| c.r)).unwrap_or(0.0);
+        let mean_quality = finite_mean(conns.iter().map(|c| c.q.value())).unwrap_or(0.0);
         let total_interactions = conns.iter().map(|c| c.count).sum();
 
         Self {
@@ -213,7 +346,7 @@ impl ConnectionStats {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::constants::W_INIT;
+    use crate::constants::{MU, W_INIT};
 
     #[test]
     fn test_new_connection() {
@@ -240,6 +373,27 @@ mod tests {
         assert!(conn.r > 0.0);
     }
 
+    #[test]
+    fn test_capability_reciprocity_diverges() {
+        let mut conn = Connection::new(NodeId::from_index(1));
+        let analysis = CapabilityId::new(1);
+        let generation = CapabilityId::new(2);
+
+        // Generous on analysis, free-riding on generation
+        for _ in 0..10 {
+            conn.update_capability_reciprocity(analysis, 2.0, 1.0, Score::new(0.8));
+            conn.update_reciprocity(2.0, 1.0, Score::new(0.8));
+            conn.update_capability_reciprocity(generation, 1.0, 3.0, Score::new(0.3));
+            conn.update_reciprocity(1.0, 3.0, Score::new(0.3));
+        }
+
+        assert!(conn.capability_reciprocity(analysis) > 0.0);
+        assert!(conn.capability_reciprocity(generation) < 0.0);
+        // Unseen capabilities fall back to the blended global value
+        assert_eq!(conn.capability_reciprocity(CapabilityId::new(3)), conn.r);
+        assert!(conn.r > conn.capability_reciprocity(generation));
+    }
+
     #[test]
     fn test_quality_ema() {
         let id = NodeId::from_index(1);
@@ -299,6 +453,33 @@ mod tests {
         assert!(conn2.w.value() < W_INIT);
     }
 
+    #[test]
+    fn test_rk4_matches_fine_euler_fixed_point() {
+        let mut euler = Connection::new(NodeId::from_index(1));
+        // Positive reciprocity and quality so Φ > 0
+        euler.r = 1.0;
+        euler.q = Score::new(0.8);
+        let mut rk4 = euler.clone();
+
+        // Choose a volume so the fixed point w* = Φ/α sits mid-range
+        let target = 0.5;
+        let unit_phi = euler.compute_reinforcement(1.0);
+        let volume = (target * ALPHA / unit_phi).powf(1.0 / MU);
+        let fixed_point = euler.compute_reinforcement(volume) / ALPHA;
+        assert!((fixed_point - target).abs() < 1e-9);
+
+        for _ in 0..20_000 {
+            euler.update_weight(volume, 0.0, 0.1);
+        }
+        for _ in 0..2_000 {
+            rk4.integrate_weight(volume, 0.0, 1.0, WeightIntegrator::Rk4 { substeps: 4 });
+        }
+
+        assert!((euler.w.value() - fixed_point).abs() < 1e-6);
+        assert!((rk4.w.value() - fixed_point).abs() < 1e-6);
+        assert!((euler.w.value() - rk4.w.value()).abs() < 1e-6);
+    }
+
     #[test]
     fn test_process_interaction() {
         let id = NodeId::from_index(1);
@@ -321,7 +502,7 @@ mod tests {
 
     #[test]
     fn test_connection_stats() {
-        let conns = vec![
+        let conns = [
             Connection::with_weight(NodeId::from_index(1), Weight::new(0.5)),
             Connection::with_weight(NodeId::from_index(2), Weight::new(0.7)),
         ];
@@ -329,5 +510,12 @@ mod tests {
         let stats = ConnectionStats::from_connections(conns.iter());
         assert_eq!(stats.count, 2);
         assert!((stats.mean_weight - 0.6).abs() < 0.01);
+
+        // A NaN reciprocity is skipped rather than poisoning the mean
+        let mut conns = conns;
+        conns[0].r = f64::NAN;
+        conns[1].r = 1.0;
+        let stats = ConnectionStats::from_connections(conns.iter());
+        assert_eq!(stats.mean_reciprocity, 1.0);
     }
 }
@@ -61,12 +61,43 @@ pub const SWIFT_TRUST_BASE: f64 = 0.4;
 /// Penalty multiplier applied to voucher's trust when vouched node fails
 pub const VOUCH_PENALTY: f64 = 0.5;
 
+/// Share of a voucher's trust (above Swift Trust) transferred to a vouched node
+pub const VOUCH_TRUST_SHARE: f64 = 0.5;
+
 /// Number of interactions required during probation period
 pub const PROBATION_COUNT: u32 = 50;
 
 /// Minimum quality threshold to pass probation
 pub const PROBATION_THRESHOLD: f64 = 0.6;
 
+/// Number of failed probation windows before a node is expelled
+pub const MAX_PROBATION_FAILURES: u32 = 3;
+
+/// Recent computed trust values that must all clear ESTABLISHED_MIN_TRUST
+pub const ESTABLISHED_TRUST_WINDOW: usize = 20;
+
+/// Trust a member must sustain to become established
+pub const ESTABLISHED_MIN_TRUST: f64 = 0.5;
+
+/// Interactions across all connections a member needs to become established
+pub const ESTABLISHED_MIN_INTERACTIONS: u64 = 200;
+
+/// Active partners an established node needs to become a hub
+pub const HUB_MIN_PARTNERS: usize = 20;
+
+/// Trust an established node needs to become a hub
+pub const HUB_MIN_TRUST: f64 = 0.6;
+
+/// Interactions, initiated or received, before a probationary node's swift
+/// trust stops decaying
+pub const SWIFT_TRUST_MIN_INTERACTIONS: u32 = 10;
+
+/// Per-tick decay of an unproven node's trust cap (recovers at the same rate once proven)
+pub const SWIFT_TRUST_DECAY: f64 = 0.99;
+
+/// Lowest the swift trust multiplier decays to
+pub const SWIFT_TRUST_FLOOR: f64 = 0.25;
+
 // =============================================================================
 // QUALITY WEIGHTS
 // =============================================================================
@@ -89,6 +120,9 @@ pub const REUSE_BOOST: f64 = 1.2;
 /// Multiplier when user indicates they would NOT reuse
 pub const REUSE_PENALTY: f64 = 0.8;
 
+/// Share of smooth-mapped quality decided by would_reuse
+pub const REUSE_WEIGHT: f64 = 0.15;
+
 // =============================================================================
 // DEFENSE SIGNALING
 // =============================================================================
@@ -108,12 +142,21 @@ pub const MAX_HOPS: u8 = 5;
 /// How much defense signals increase priming
 pub const PRIMING_SENSITIVITY: f64 = 0.1;
 
+/// How long (ms) a received defense signal suppresses duplicates of itself
+pub const SIGNAL_VALIDITY_MS: u64 = 60_000;
+
 /// Per-tick decay of priming level
 pub const PRIMING_DECAY: f64 = 0.99;
 
+/// Routing penalty on a fully primed connection (π = 1) not yet believed a threat
+pub const CONNECTION_PRIMING_PENALTY: f64 = 0.2;
+
 /// Threat belief level that triggers defensive action
 pub const ACTION_THRESHOLD: f64 = 0.7;
 
+/// Per-tick decay of threat beliefs absent new evidence
+pub const THREAT_BELIEF_DECAY: f64 = 0.995;
+
 // =============================================================================
 // CONFIDENCE
 // =============================================================================
@@ -121,6 +164,12 @@ pub const ACTION_THRESHOLD: f64 = 0.7;
 /// Memory factor for self-confidence EMA
 pub const CONFIDENCE_MEMORY: f64 = 0.95;
 
+/// Per-tick decay of recently received affirmations
+pub const AFFIRMATION_DECAY: f64 = 0.95;
+
+/// Affirmer trust at which recent affirmations carry half of social proof
+pub const AFFIRMATION_HALF_WEIGHT: f64 = 1.0;
+
 // =============================================================================
 // DETECTION
 // =============================================================================
@@ -131,9 +180,58 @@ pub const COLLUSION_THRESHOLD: f64 = 0.85;
 /// Minimum diversity score to avoid trust cap
 pub const DIVERSITY_THRESHOLD: f64 = 0.3;
 
+/// Fewest interactions diversity is measured against, so a short history
+/// can't read as fully diverse
+pub const DIVERSITY_MIN_SAMPLE: usize = 10;
+
 /// Quality drop threshold to flag strategic adversary
 pub const ADVERSARY_DROP: f64 = 0.3;
 
+/// Minimum connection weight counted as an edge in collusion detection
+pub const COLLUSION_EDGE_WEIGHT: f64 = 0.05;
+
+/// Mean mutual rating above which a dense cluster is suspected of collusion
+pub const COLLUSION_MUTUAL_RATING: f64 = 0.9;
+
+/// External links per member, relative to 0.5, below which a cluster is closed
+pub const COLLUSION_EXTERNAL_RATIO: f64 = 1.0;
+
+/// Consecutive own interactions averaged per oscillation window
+pub const OSCILLATION_WINDOW: usize = 8;
+
+/// Std dev of window mean quality above which behavior is flagged as oscillating
+pub const OSCILLATION_THRESHOLD: f64 = 0.15;
+
+/// Quality variance over recent history above which a node is flagged as anomalous
+pub const QUALITY_ANOMALY_VARIANCE: f64 = 0.05;
+
+/// Minimum interactions in history before quality variance is judged
+pub const QUALITY_ANOMALY_MIN_HISTORY: usize = 20;
+
+/// Trust cap multiplier applied while a node is flagged for quality anomaly
+pub const QUALITY_ANOMALY_CAP: f64 = 0.9;
+
+/// Most partners a node can have and still count as isolated
+pub const ISOLATION_MAX_PARTNERS: usize = 2;
+
+/// Total interaction volume below which a node counts as isolated
+pub const ISOLATION_MAX_VOLUME: f64 = 10.0;
+
+/// Trust cap while a node is isolated (replaces the low-diversity cap)
+pub const ISOLATION_TRUST_CAP: f64 = 0.5;
+
+/// Mean log give/take ratio below which a node is flagged for reciprocity abuse
+pub const RECIPROCITY_ABUSE_THRESHOLD: f64 = -0.3;
+
+/// Minimum total interaction volume before reciprocity abuse is judged
+pub const RECIPROCITY_ABUSE_MIN_VOLUME: f64 = 30.0;
+
+/// Minimum interactions on a connection before its reciprocity is judged
+pub const RECIPROCITY_ABUSE_MIN_INTERACTIONS: u32 = 10;
+
+/// Fraction of established connections with negative reciprocity that flags abuse
+pub const RECIPROCITY_ABUSE_FRACTION: f64 = 0.5;
+
 // =============================================================================
 // TRUST COMPUTATION WEIGHTS
 // =============================================================================
@@ -150,6 +248,31 @@ pub const TRUST_WEIGHT_SOCIAL: f64 = 0.2;
 /// Weight for diversity in trust computation
 pub const TRUST_WEIGHT_DIVERSITY: f64 = 0.2;
 
+/// Share of global trust drawn from local trust each propagation step
+pub const GLOBAL_TRUST_DAMPING: f64 = 0.15;
+
+// =============================================================================
+// TRUST STABILITY
+// =============================================================================
+
+/// Number of recent computed trust values a node retains
+pub const TRUST_HISTORY_SIZE: usize = 50;
+
+/// Default number of interactions a node's history retains
+pub const INTERACTION_HISTORY_SIZE: usize = 100;
+
+/// Window of recent trust values checked for volatility
+pub const TRUST_VOLATILITY_WINDOW: usize = 20;
+
+/// Trust variance above which a node is flagged as volatile
+pub const TRUST_VOLATILITY_THRESHOLD: f64 = 0.001;
+
+/// Multiple of the network's median trust velocity that counts as rapid gain
+pub const RAPID_GAIN_FACTOR: f64 = 3.0;
+
+/// Trust gain per tick below which a climb is never rapid
+pub const RAPID_GAIN_MIN_VELOCITY: f64 = 0.002;
+
 // =============================================================================
 // TONE COMPUTATION WEIGHTS
 // =============================================================================
@@ -163,6 +286,49 @@ pub const TONE_WEIGHT_FRIENDLINESS: f64 = 0.3;
 /// Weight for collaboration signals in tone
 pub const TONE_WEIGHT_COLLABORATION: f64 = 0.3;
 
+// =============================================================================
+// LOAD
+// =============================================================================
+
+/// Load added to a node (and the capability used) per incoming interaction
+pub const LOAD_PER_INTERACTION: f64 = 0.05;
+
+/// Multiplicative load decay applied each tick
+pub const LOAD_DECAY: f64 = 0.8;
+
+/// Load added to a node (and each capability drawn on) per task started
+pub const LOAD_PER_TASK: f64 = 0.2;
+
+/// Tasks a node can hold in its backlog
+pub const TASK_QUEUE_CAPACITY: usize = 8;
+
+/// Most queued tasks a node starts per tick
+pub const TASKS_PER_TICK: usize = 2;
+
+/// Routing penalty per queued task: b = 1 / (1 + penalty × depth)
+pub const QUEUE_DEPTH_PENALTY: f64 = 0.25;
+
+/// Estimated latency of an idle, inexperienced node (ms)
+pub const LATENCY_BASE_MS: f64 = 100.0;
+
+/// Latency multiplier added at full load (a maxed-out node is 10× slower)
+pub const LATENCY_LOAD_PENALTY: f64 = 9.0;
+
+/// Speedup per unit of log capability volume
+pub const LATENCY_EXPERIENCE: f64 = 0.1;
+
+// =============================================================================
+// ROUTING
+// =============================================================================
+
+/// Routing bonus for a candidate whose capabilities all sit in the task's
+/// preferred category
+pub const SPECIALIZATION_BONUS: f64 = 0.5;
+
+/// Uses after which routing counts half of a capability's quality lead over
+/// neutral
+pub const CAPABILITY_CONFIDENCE_VOLUME: f64 = 20.0;
+
 // =============================================================================
 // TIMING
 // =============================================================================
@@ -170,6 +336,12 @@ pub const TONE_WEIGHT_COLLABORATION: f64 = 0.3;
 /// Threshold (in ticks/ms) for considering a connection idle
 pub const IDLE_THRESHOLD: u64 = 100_000;
 
+/// Ticks a capability can go unused before its quality starts to fade
+pub const CAPABILITY_IDLE_TICKS: u32 = 100;
+
+/// Per-tick retention of an idle capability's distance from neutral quality
+pub const CAPABILITY_QUALITY_DECAY: f64 = 0.99;
+
 /// Interval for diversity checks (in ticks)
 pub const DIVERSITY_INTERVAL: u64 = 100;
 
@@ -179,6 +351,28 @@ pub const STATUS_INTERVAL: u64 = 50;
 /// Interval for adversary scanning (in ticks)
 pub const ADVERSARY_INTERVAL: u64 = 100;
 
+/// Shortest adaptive adversary scan interval, used after joins or trust spikes
+pub const ADVERSARY_INTERVAL_MIN: u64 = 25;
+
+/// Longest adaptive adversary scan interval, used while trust is stable
+pub const ADVERSARY_INTERVAL_MAX: u64 = 300;
+
+/// Ticks a queued defense signal stays actionable; by then a regular scan
+/// has re-examined its target
+pub const SIGNAL_MAX_AGE_TICKS: u64 = ADVERSARY_INTERVAL;
+
+/// Trust snapshots per window compared when adapting the scan interval
+pub const SCAN_VARIANCE_WINDOW: usize = 20;
+
+/// Recent-to-baseline trust variance ratio treated as a spike
+pub const SCAN_VARIANCE_SPIKE: f64 = 1.5;
+
+/// Recent-to-baseline trust variance ratio at or below which trust is stable
+pub const SCAN_VARIANCE_STABLE: f64 = 1.05;
+
+/// Floor added to both variances so near-uniform trust never reads as a spike
+pub const SCAN_VARIANCE_FLOOR: f64 = 1e-4;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -192,7 +386,12 @@ mod tests {
 
         // Probability/score bounds
         assert!(SWIFT_TRUST_BASE >= 0.0 && SWIFT_TRUST_BASE <= 1.0);
+        assert!(SWIFT_TRUST_FLOOR > 0.0 && SWIFT_TRUST_FLOOR < 1.0);
         assert!(PROBATION_THRESHOLD >= 0.0 && PROBATION_THRESHOLD <= 1.0);
+        assert!(VOUCH_PENALTY > 0.0 && VOUCH_PENALTY < 1.0);
+        assert!(VOUCH_TRUST_SHARE >= 0.0 && VOUCH_TRUST_SHARE <= 1.0);
+        assert!(COLLUSION_MUTUAL_RATING > 0.0 && COLLUSION_MUTUAL_RATING < 1.0);
+        assert!(COLLUSION_EXTERNAL_RATIO > 0.0);
 
         // Weight sums (should approximately sum to 1)
         let quality_sum = OMEGA_HELP + OMEGA_ACC + OMEGA_REL + OMEGA_TIME;
@@ -209,8 +408,41 @@ mod tests {
         // Decay factors should be in (0, 1)
         assert!(LAMBDA > 0.0 && LAMBDA < 1.0);
         assert!(PRIMING_DECAY > 0.0 && PRIMING_DECAY < 1.0);
+        assert!(CONNECTION_PRIMING_PENALTY > 0.0 && CONNECTION_PRIMING_PENALTY < 1.0);
+        assert!(THREAT_BELIEF_DECAY > 0.0 && THREAT_BELIEF_DECAY < 1.0);
+        assert!(CAPABILITY_QUALITY_DECAY > 0.0 && CAPABILITY_QUALITY_DECAY < 1.0);
         assert!(DECAY_PER_HOP > 0.0 && DECAY_PER_HOP < 1.0);
         assert!(CONFIDENCE_MEMORY > 0.0 && CONFIDENCE_MEMORY < 1.0);
+        assert!(REUSE_WEIGHT > 0.0 && REUSE_WEIGHT < 1.0);
+        assert!(AFFIRMATION_DECAY > 0.0 && AFFIRMATION_DECAY < 1.0);
+        assert!(AFFIRMATION_HALF_WEIGHT > 0.0);
+
+        // Status ladder
+        assert!(ESTABLISHED_TRUST_WINDOW <= TRUST_HISTORY_SIZE);
+        assert!(TRUST_VOLATILITY_WINDOW <= TRUST_HISTORY_SIZE);
+        assert!(RAPID_GAIN_FACTOR > 1.0);
+        assert!(RAPID_GAIN_MIN_VELOCITY > 0.0);
+        assert!(ESTABLISHED_MIN_TRUST >= 0.0 && HUB_MIN_TRUST <= 1.0);
+        assert!(HUB_MIN_TRUST >= ESTABLISHED_MIN_TRUST);
+
+        // Adaptive scan interval brackets the fixed one
+        assert!(ADVERSARY_INTERVAL_MIN <= ADVERSARY_INTERVAL);
+        assert!(ADVERSARY_INTERVAL <= ADVERSARY_INTERVAL_MAX);
+        assert!(SIGNAL_MAX_AGE_TICKS >= ADVERSARY_INTERVAL_MIN);
+        assert!(SCAN_VARIANCE_STABLE > 1.0 && SCAN_VARIANCE_STABLE < SCAN_VARIANCE_SPIKE);
+
+        // Specialization can at most double a routing score
+        assert!(SPECIALIZATION_BONUS > 0.0 && SPECIALIZATION_BONUS <= 1.0);
+        assert!(CAPABILITY_CONFIDENCE_VOLUME > 0.0);
+
+        // A queued task must be able to start on an idle node
+        assert!(LOAD_PER_TASK > 0.0 && LOAD_PER_TASK < 0.95);
+        assert!(TASK_QUEUE_CAPACITY > 0 && TASKS_PER_TICK > 0);
+        assert!(QUEUE_DEPTH_PENALTY > 0.0);
+
+        // History must cover the anomaly check's minimum sample
+        assert!(INTERACTION_HISTORY_SIZE >= QUALITY_ANOMALY_MIN_HISTORY);
+        assert!(DIVERSITY_MIN_SAMPLE > 0 && DIVERSITY_MIN_SAMPLE <= INTERACTION_HISTORY_SIZE);
 
         // Epsilon should be small but positive
         assert!(EPSILON > 0.0 && EPSILON < 0.01);
@@ -58,7 +58,7 @@ pub struct Position {
 }
 
 /// Tracker for convergence on a specific task
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConvergenceTracker {
     /// Task being tracked
     pub task_id: TaskId,
@@ -103,8 +103,15 @@ impl ConvergenceTracker {
         let values: Vec<f64> = self.positions.values().map(|p| p.value).collect();
         let var = variance(&values);
 
-        // Conv = 1 - (Var / Var_max)
-        let score = 1.0 - (var / self.max_variance).min(1.0);
+        // Conv = 1 - (Var / Var_max); with no room to vary, any spread is
+        // as far apart as positions get
+        let score = if self.max_variance > 0.0 {
+            1.0 - (var / self.max_variance).min(1.0)
+        } else if var > 0.0 {
+            0.0
+        } else {
+            1.0
+        };
         self.current_score = Score::new(score);
 
         self.score_history.push((Timestamp::now(), self.current_score));
@@ -121,8 +128,11 @@ impl ConvergenceTracker {
     }
 
     /// Get trend over recent history
+    ///
+    /// Stable until there is more history than the window, and for windows
+    /// too short to split into halves.
     pub fn trend(&self, window: usize) -> ConvergenceTrend {
-        if self.score_history.len() < window + 1 {
+        if window < 2 || self.score_history.len() <= window {
             return ConvergenceTrend::Stable;
         }
 
@@ -167,10 +177,26 @@ impl ConvergenceTracker {
         let sum: f64 = self.positions.values().map(|p| p.value).sum();
         sum / self.positions.len() as f64
     }
+
+    /// Get the mean position value weighted by each position's confidence
+    ///
+    /// v̄_c = Σ c_i × v_i / Σ c_i; falls back to the plain mean if all confidences are zero
+    pub fn confidence_weighted_mean(&self) -> f64 {
+        let weight_sum: f64 = self.positions.values().map(|p| p.confidence.value()).sum();
+        if weight_sum <= 0.0 {
+            return self.mean_position();
+        }
+        let weighted_sum: f64 = self
+            .positions
+            .values()
+            .map(|p| p.confidence.value() * p.value)
+            .sum();
+        weighted_sum / weight_sum
+    }
 }
 
 /// Result of agree-to-disagree protocol
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AgreeToDisagreeResult {
     /// The chosen path forward
     pub chosen_position: f64,
@@ -194,10 +220,36 @@ pub fn should_invoke_atd(tracker: &ConvergenceTracker, task_criticality: Score)
         && task_criticality.value() < 0.8
 }
 
-/// Execute agree-to-disagree protocol
+/// Execute agree-to-disagree protocol, weighting each node by its trust
 pub fn execute_atd(
     tracker: &ConvergenceTracker,
     node_trusts: &HashMap<NodeId, Score>,
+) -> AgreeToDisagreeResult {
+    atd_weighted_by(tracker, |p| trust_or_neutral(node_trusts, &p.node_id))
+}
+
+/// Execute agree-to-disagree protocol, weighting each node by trust × confidence
+///
+/// A trusted node that is unsure of its position counts for less than in
+/// [`execute_atd`].
+pub fn execute_atd_with_confidence(
+    tracker: &ConvergenceTracker,
+    node_trusts: &HashMap<NodeId, Score>,
+) -> AgreeToDisagreeResult {
+    atd_weighted_by(tracker, |p| {
+        trust_or_neutral(node_trusts, &p.node_id) * p.confidence.value()
+    })
+}
+
+/// Trust of a node, or 0.5 if unknown
+fn trust_or_neutral(node_trusts: &HashMap<NodeId, Score>, id: &NodeId) -> f64 {
+    node_trusts.get(id).map(|s| s.value()).unwrap_or(0.5)
+}
+
+/// Agree-to-disagree with a per-position weight
+fn atd_weighted_by(
+    tracker: &ConvergenceTracker,
+    weight: impl Fn(&Position) -> f64,
 ) -> AgreeToDisagreeResult {
     let positions = tracker.positions();
 
@@ -213,55 +265,46 @@ pub fn execute_atd(
     // Cluster positions (simple: above/below mean)
     let mean = tracker.mean_position();
 
-    let mut above: Vec<(NodeId, f64)> = Vec::new();
-    let mut below: Vec<(NodeId, f64)> = Vec::new();
+    let mut above: Vec<(NodeId, f64, f64)> = Vec::new();
+    let mut below: Vec<(NodeId, f64, f64)> = Vec::new();
 
     for (id, pos) in positions {
         if pos.value >= mean {
-            above.push((*id, pos.value));
+            above.push((*id, pos.value, weight(pos)));
         } else {
-            below.push((*id, pos.value));
+            below.push((*id, pos.value, weight(pos)));
         }
     }
 
-    // Weight clusters by trust
-    let above_weight: f64 = above
-        .iter()
-        .map(|(id, _)| node_trusts.get(id).map(|s| s.value()).unwrap_or(0.5))
-        .sum();
-    let below_weight: f64 = below
-        .iter()
-        .map(|(id, _)| node_trusts.get(id).map(|s| s.value()).unwrap_or(0.5))
-        .sum();
+    // Weight clusters
+    let above_weight: f64 = above.iter().map(|(_, _, w)| w).sum();
+    let below_weight: f64 = below.iter().map(|(_, _, w)| w).sum();
 
     // Choose winning cluster
-    let (winners, losers) = if above_weight >= below_weight {
-        (above, below)
+    let (winners, losers, winner_weight) = if above_weight >= below_weight {
+        (above, below, above_weight)
     } else {
-        (below, above)
+        (below, above, below_weight)
     };
 
     // Compute chosen position as weighted mean of winners
-    let chosen = if winners.is_empty() {
+    let chosen = if winners.is_empty() || winner_weight <= 0.0 {
         mean
     } else {
-        let weighted_sum: f64 = winners
-            .iter()
-            .map(|(id, v)| v * node_trusts.get(id).map(|s| s.value()).unwrap_or(0.5))
-            .sum();
-        let weight_sum: f64 = winners
-            .iter()
-            .map(|(id, _)| node_trusts.get(id).map(|s| s.value()).unwrap_or(0.5))
-            .sum();
-        weighted_sum / weight_sum
+        let weighted_sum: f64 = winners.iter().map(|(_, v, w)| v * w).sum();
+        weighted_sum / winner_weight
     };
 
-    let agreeing: Vec<NodeId> = winners.into_iter().map(|(id, _)| id).collect();
-    let dissenting: Vec<NodeId> = losers.into_iter().map(|(id, _)| id).collect();
+    let agreeing: Vec<NodeId> = winners.into_iter().map(|(id, _, _)| id).collect();
+    let dissenting: Vec<NodeId> = losers.into_iter().map(|(id, _, _)| id).collect();
 
     // Confidence based on margin
     let total_weight = above_weight + below_weight;
-    let margin = (above_weight - below_weight).abs() / total_weight;
+    let margin = if total_weight > 0.0 {
+        (above_weight - below_weight).abs() / total_weight
+    } else {
+        0.0
+    };
     let confidence = Score::new(0.5 + margin * 0.5);
 
     AgreeToDisagreeResult {
@@ -314,6 +357,40 @@ mod tests {
         assert_eq!(tracker.state(), ConvergenceState::Converged);
     }
 
+    #[test]
+    fn test_convergence_tracker_degenerate_inputs() {
+        // One position is trivially converged and has no trend
+        let mut tracker = ConvergenceTracker::new(TaskId::random(), 100.0);
+        tracker.record_position(create_position(1, 30.0));
+        assert_eq!(tracker.score(), Score::ONE);
+        assert_eq!(tracker.state(), ConvergenceState::Converged);
+        for window in [0, 1, 2, 5, 100] {
+            assert_eq!(tracker.trend(window), ConvergenceTrend::Stable);
+        }
+        assert_eq!(tracker.mean_position(), 30.0);
+
+        let nodes: HashMap<NodeId, Score> = HashMap::new();
+        let result = execute_atd(&tracker, &nodes);
+        assert_eq!(result.agreeing_nodes, vec![NodeId::from_index(1)]);
+        assert!(result.dissenting_nodes.is_empty());
+
+        // Windows of zero or one can't be split into halves
+        let mut tracker = ConvergenceTracker::new(TaskId::random(), 100.0);
+        for i in 0..5 {
+            tracker.record_position(create_position(i, i as f64 * 10.0));
+        }
+        assert_eq!(tracker.trend(0), ConvergenceTrend::Stable);
+        assert_eq!(tracker.trend(1), ConvergenceTrend::Stable);
+
+        // A zero variance bound scores agreement as converged, spread as polarized
+        let mut tracker = ConvergenceTracker::new(TaskId::random(), 0.0);
+        tracker.record_position(create_position(1, 5.0));
+        tracker.record_position(create_position(2, 5.0));
+        assert_eq!(tracker.score(), Score::ONE);
+        tracker.record_position(create_position(3, 6.0));
+        assert_eq!(tracker.score(), Score::ZERO);
+    }
+
     #[test]
     fn test_convergence_polarized() {
         let mut tracker = ConvergenceTracker::new(TaskId::random(), 100.0);
@@ -355,6 +432,38 @@ mod tests {
         assert!(result.chosen_position < 50.0);
     }
 
+    #[test]
+    fn test_confidence_weighted_atd() {
+        let mut tracker = ConvergenceTracker::new(TaskId::random(), 100.0);
+
+        // Trusted but unsure low cluster, less trusted but confident high cluster
+        let stances = [(1, 10.0, 0.2), (2, 12.0, 0.2), (3, 88.0, 0.9), (4, 90.0, 0.9)];
+        for (idx, value, confidence) in stances {
+            tracker.record_position(Position {
+                confidence: Score::new(confidence),
+                ..create_position(idx, value)
+            });
+        }
+
+        let trusts: HashMap<_, _> = [(1, 0.9), (2, 0.9), (3, 0.5), (4, 0.5)]
+            .into_iter()
+            .map(|(idx, t)| (NodeId::from_index(idx), Score::new(t)))
+            .collect();
+
+        // Trust alone picks the low cluster
+        assert!(execute_atd(&tracker, &trusts).chosen_position < 50.0);
+
+        // Discounting by confidence flips it to the high cluster
+        let result = execute_atd_with_confidence(&tracker, &trusts);
+        assert!(result.chosen_position > 50.0);
+        assert!(result.agreeing_nodes.contains(&NodeId::from_index(3)));
+        assert!(result.dissenting_nodes.contains(&NodeId::from_index(1)));
+
+        // (10×0.2 + 12×0.2 + 88×0.9 + 90×0.9) / 2.2 = 74.82
+        assert!((tracker.confidence_weighted_mean() - 74.818_181_818).abs() < 1e-6);
+        assert!((tracker.mean_position() - 50.0).abs() < 1e-9);
+    }
+
     #[test]
     fn test_should_invoke_atd() {
         let mut tracker = ConvergenceTracker::new(TaskId::random(), 100.0);
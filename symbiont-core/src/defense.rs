@@ -5,11 +5,13 @@
 
 use crate::constants::{
     ACTION_THRESHOLD, DECAY_PER_HOP, MAX_HOPS, MIN_SIGNAL, PRIMING_SENSITIVITY,
-    PROPAGATE_THRESHOLD,
+    PROPAGATE_THRESHOLD, SIGNAL_VALIDITY_MS,
 };
 use crate::node::{DefenseState, Node, ThreatType};
-use crate::types::{Hash, NodeId, Score, Signature, Timestamp};
+use crate::types::{Hash, NodeId, Score, SignedScore, Signature, Timestamp};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Write;
 
 /// Type of defense signal
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -72,21 +74,37 @@ impl DefenseSignal {
 
     /// Create a forwarded version of this signal
     pub fn forward(&self, new_sender: NodeId, connection_weight: f64) -> Option<Self> {
+        self.try_forward(new_sender, connection_weight).ok()
+    }
+
+    /// Confidence after one hop over a link of the given weight
+    ///
+    /// c' = c × DECAY_PER_HOP × w
+    pub fn attenuated_confidence(&self, connection_weight: f64) -> f64 {
+        self.confidence.value() * DECAY_PER_HOP * connection_weight
+    }
+
+    /// Create a forwarded version of this signal, or say why it stops here
+    pub fn try_forward(
+        &self,
+        new_sender: NodeId,
+        connection_weight: f64,
+    ) -> Result<Self, SignalDrop> {
         // Check if we should propagate
         if self.confidence.value() < PROPAGATE_THRESHOLD {
-            return None;
+            return Err(SignalDrop::BelowPropagateThreshold);
         }
         if self.hops >= MAX_HOPS {
-            return None;
+            return Err(SignalDrop::MaxHops);
         }
 
         // Attenuate confidence
-        let new_confidence = self.confidence.value() * DECAY_PER_HOP * connection_weight;
+        let new_confidence = self.attenuated_confidence(connection_weight);
         if new_confidence < MIN_SIGNAL {
-            return None;
+            return Err(SignalDrop::BelowMinSignal);
         }
 
-        Some(Self {
+        Ok(Self {
             signal_type: self.signal_type,
             sender: new_sender,
             origin: self.origin,
@@ -104,6 +122,115 @@ impl DefenseSignal {
     pub fn is_valid(&self, max_age_ms: u64) -> bool {
         !self.timestamp.is_older_than(max_age_ms)
     }
+
+    /// Check if signal is still valid as of `now`
+    pub fn is_valid_at(&self, max_age_ms: u64, now: Timestamp) -> bool {
+        !self.timestamp.is_older_than_at(max_age_ms, now)
+    }
+}
+
+/// Why a defense signal went no further along a hop
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SignalDrop {
+    /// Confidence too low to propagate at all
+    BelowPropagateThreshold,
+    /// Already travelled MAX_HOPS
+    MaxHops,
+    /// Attenuation over the link left less than MIN_SIGNAL
+    BelowMinSignal,
+    /// The receiver had already applied this evidence
+    Duplicate,
+}
+
+impl SignalDrop {
+    /// Short snake_case name
+    pub fn name(&self) -> &'static str {
+        match self {
+            SignalDrop::BelowPropagateThreshold => "below_propagate_threshold",
+            SignalDrop::MaxHops => "max_hops",
+            SignalDrop::BelowMinSignal => "below_min_signal",
+            SignalDrop::Duplicate => "duplicate",
+        }
+    }
+}
+
+/// One attempted hop of a defense signal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignalHop {
+    /// Node passing the signal on
+    pub from: NodeId,
+    /// Neighbor it was passed to
+    pub to: NodeId,
+    /// Hop count the signal carries over this link (0 straight from the origin)
+    pub hops: u8,
+    /// Confidence after attenuation over this link
+    pub confidence: f64,
+    /// Why the signal stopped here, if it did
+    pub dropped: Option<SignalDrop>,
+}
+
+/// Propagation tree of one defense signal, for debugging reach
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignalTrace {
+    /// Node that detected the threat
+    pub origin: NodeId,
+    /// The suspected threat
+    pub threat: NodeId,
+    /// Hash of the evidence, which identifies the signal
+    pub evidence: Hash,
+    /// Hops in the order they were attempted
+    pub hops: Vec<SignalHop>,
+}
+
+impl SignalTrace {
+    fn new(signal: &DefenseSignal) -> Self {
+        Self {
+            origin: signal.origin,
+            threat: signal.threat,
+            evidence: signal.evidence,
+            hops: Vec::new(),
+        }
+    }
+
+    /// Hops the signal crossed and was accepted on
+    pub fn delivered(&self) -> impl Iterator<Item = &SignalHop> {
+        self.hops.iter().filter(|h| h.dropped.is_none())
+    }
+
+    /// Hops where the signal stopped
+    pub fn dropped(&self) -> impl Iterator<Item = &SignalHop> {
+        self.hops.iter().filter(|h| h.dropped.is_some())
+    }
+
+    /// Export the propagation tree as a Graphviz DOT graph
+    ///
+    /// Edges are labeled with attenuated confidence; dropped hops are
+    /// dashed red and name the reason.
+    pub fn export_dot<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writeln!(writer, "digraph signal {{")?;
+        writeln!(writer, "  label=\"threat {} from {}\";", self.threat, self.origin)?;
+        writeln!(writer, "  \"{}\" [shape=doublecircle];", self.origin)?;
+
+        for hop in &self.hops {
+            match hop.dropped {
+                None => writeln!(
+                    writer,
+                    "  \"{}\" -> \"{}\" [label=\"{:.3}\"];",
+                    hop.from, hop.to, hop.confidence
+                )?,
+                Some(reason) => writeln!(
+                    writer,
+                    "  \"{}\" -> \"{}\" [label=\"{:.3} {}\", style=dashed, color=red];",
+                    hop.from,
+                    hop.to,
+                    hop.confidence,
+                    reason.name()
+                )?,
+            }
+        }
+
+        writeln!(writer, "}}")
+    }
 }
 
 /// An affirmation of good behavior from one node to another
@@ -150,12 +277,20 @@ impl Affirmation {
     }
 }
 
+/// Key identifying a signal a node has already applied:
+/// (receiver, origin, threat, evidence)
+type SeenKey = (NodeId, NodeId, NodeId, Hash);
+
 /// Handler for defense signals
 pub struct DefenseHandler {
     /// Signals to propagate
     pending_signals: Vec<DefenseSignal>,
     /// Affirmations to send
     pending_affirmations: Vec<Affirmation>,
+    /// Signals already applied, kept until they expire
+    seen_signals: HashMap<SeenKey, DefenseSignal>,
+    /// Propagation traces by (origin, threat, evidence), if tracing
+    traces: Option<HashMap<(NodeId, NodeId, Hash), SignalTrace>>,
 }
 
 impl DefenseHandler {
@@ -164,6 +299,63 @@ impl DefenseHandler {
         Self {
             pending_signals: Vec::new(),
             pending_affirmations: Vec::new(),
+            seen_signals: HashMap::new(),
+            traces: None,
+        }
+    }
+
+    /// Record a [`SignalTrace`] for every signal handled
+    pub fn with_tracing(mut self) -> Self {
+        self.traces = Some(HashMap::new());
+        self
+    }
+
+    /// Propagation trace of a signal, if tracing is on and it was handled
+    pub fn trace(&self, signal: &DefenseSignal) -> Option<&SignalTrace> {
+        self.traces
+            .as_ref()?
+            .get(&(signal.origin, signal.threat, signal.evidence))
+    }
+
+    /// All recorded propagation traces
+    pub fn traces(&self) -> impl Iterator<Item = &SignalTrace> {
+        self.traces.iter().flat_map(|t| t.values())
+    }
+
+    /// Trace of a signal, created on first use (None unless tracing)
+    fn trace_mut(&mut self, signal: &DefenseSignal) -> Option<&mut SignalTrace> {
+        let key = (signal.origin, signal.threat, signal.evidence);
+        Some(
+            self.traces
+                .as_mut()?
+                .entry(key)
+                .or_insert_with(|| SignalTrace::new(signal)),
+        )
+    }
+
+    /// Note a signal's arrival at a node
+    ///
+    /// Forwarded hops were recorded when sent; a signal straight from its
+    /// origin gets its first hop here. A duplicate marks its hop dropped.
+    fn trace_arrival(&mut self, node: NodeId, signal: &DefenseSignal, duplicate: bool) {
+        let Some(trace) = self.trace_mut(signal) else {
+            return;
+        };
+        let dropped = duplicate.then_some(SignalDrop::Duplicate);
+        let sent = trace
+            .hops
+            .iter_mut()
+            .rev()
+            .find(|h| h.from == signal.sender && h.to == node && h.dropped.is_none());
+        match sent {
+            Some(hop) => hop.dropped = dropped,
+            None => trace.hops.push(SignalHop {
+                from: signal.sender,
+                to: node,
+                hops: signal.hops,
+                confidence: signal.confidence.value(),
+                dropped,
+            }),
         }
     }
 
@@ -179,6 +371,17 @@ impl DefenseHandler {
             return SignalResult::Ignored;
         }
 
+        // Don't count the same evidence twice when it echoes back through the graph
+        let now = node.now();
+        self.seen_signals.retain(|_, seen| seen.is_valid_at(SIGNAL_VALIDITY_MS, now));
+        let key = (node.id, signal.origin, signal.threat, signal.evidence);
+        let duplicate = self.seen_signals.contains_key(&key);
+        self.trace_arrival(node.id, signal, duplicate);
+        if duplicate {
+            return SignalResult::Ignored;
+        }
+        self.seen_signals.insert(key, signal.clone());
+
         // Get sender's trust level (if known)
         let sender_trust = node
             .get_connection(&signal.sender)
@@ -189,9 +392,12 @@ impl DefenseHandler {
         let weight = sender_trust * signal.confidence.value();
         node.update_threat_belief(signal.threat, signal.threat_type, weight, Some(signal.evidence));
 
-        // Increase priming
+        // Increase priming, both overall and on our link to the threat
         let boost = signal.confidence.value() * PRIMING_SENSITIVITY;
         node.increase_priming(boost);
+        if let Some(conn) = node.get_connection_mut(&signal.threat) {
+            conn.increase_priming(boost);
+        }
 
         // Check if we should take action
         let threat_level = node.get_threat_level(&signal.threat);
@@ -201,9 +407,7 @@ impl DefenseHandler {
         }
 
         // Maybe propagate
-        if signal.confidence.value() > PROPAGATE_THRESHOLD && signal.hops < MAX_HOPS {
-            self.queue_propagation(node, signal);
-        }
+        self.queue_propagation(node, signal);
 
         SignalResult::Processed {
             new_threat_level: Score::new(threat_level),
@@ -215,17 +419,34 @@ impl DefenseHandler {
     fn take_defensive_action(&mut self, node: &mut Node, threat: NodeId) {
         node.defense_state = DefenseState::Defending;
 
-        // Reduce connection weight to threat
+        // Reduce connection weight to threat and stay fully alert on it
         if let Some(conn) = node.get_connection_mut(&threat) {
             conn.w = crate::types::Weight::MIN;
+            conn.increase_priming(1.0);
         }
 
         // Could also: block interactions, notify other systems, etc.
     }
 
-    /// Queue signal propagation to neighbors
+    /// Queue signal propagation to neighbors, tracing each hop
+    ///
+    /// A signal at MAX_HOPS or without more than PROPAGATE_THRESHOLD
+    /// confidence goes nowhere.
     fn queue_propagation(&mut self, node: &Node, signal: &DefenseSignal) {
-        for (partner_id, conn) in &node.connections {
+        let stop = if signal.hops >= MAX_HOPS {
+            Some(SignalDrop::MaxHops)
+        } else if signal.confidence.value() <= PROPAGATE_THRESHOLD {
+            Some(SignalDrop::BelowPropagateThreshold)
+        } else {
+            None
+        };
+        if stop.is_some() && self.traces.is_none() {
+            return;
+        }
+
+        let mut partners: Vec<_> = node.connections.iter().collect();
+        partners.sort_by_key(|(id, _)| **id);
+        for (partner_id, conn) in partners {
             // Don't send back to sender or origin
             if *partner_id == signal.sender || *partner_id == signal.origin {
                 continue;
@@ -237,7 +458,20 @@ impl DefenseHandler {
             }
 
             // Forward with attenuation based on connection strength
-            if let Some(forwarded) = signal.forward(node.id, conn.w.value()) {
+            let forwarded = match stop {
+                Some(reason) => Err(reason),
+                None => signal.try_forward(node.id, conn.w.value()),
+            };
+            if let Some(trace) = self.trace_mut(signal) {
+                trace.hops.push(SignalHop {
+                    from: node.id,
+                    to: *partner_id,
+                    hops: signal.hops + 1,
+                    confidence: signal.attenuated_confidence(conn.w.value()),
+                    dropped: forwarded.as_ref().err().copied(),
+                });
+            }
+            if let Ok(forwarded) = forwarded {
                 self.pending_signals.push(forwarded);
             }
         }
@@ -249,7 +483,15 @@ impl DefenseHandler {
     }
 
     /// Check if we should send an affirmation based on interaction quality
-    pub fn maybe_affirm(&mut self, node: &Node, partner: NodeId, quality: Score, tone: Score) {
+    ///
+    /// Tone keeps its sign, so a hostile exchange never reads as neutral.
+    pub fn maybe_affirm(
+        &mut self,
+        node: &Node,
+        partner: NodeId,
+        quality: Score,
+        tone: SignedScore,
+    ) {
         if quality.value() > 0.8 && tone.value() > 0.5 {
             let strength = Score::new((quality.value() + tone.value()) / 2.0);
             let affirmation = Affirmation::new(
@@ -306,6 +548,8 @@ pub fn emit_defense_signal(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::constants::{PRIMING_DECAY, W_INIT};
+    use crate::types::Weight;
 
     fn create_test_signal() -> DefenseSignal {
         DefenseSignal::new(
@@ -385,6 +629,182 @@ mod tests {
         assert!(node.priming.value() > 0.0);
     }
 
+    #[test]
+    fn test_affirmation_needs_positive_tone() {
+        let node = Node::new(NodeId::from_index(1));
+        let partner = NodeId::from_index(2);
+        let mut handler = DefenseHandler::new();
+
+        handler.maybe_affirm(&node, partner, Score::new(0.9), SignedScore::new(-0.9));
+        handler.maybe_affirm(&node, partner, Score::new(0.9), SignedScore::new(0.2));
+        assert!(handler.take_pending_affirmations().is_empty());
+
+        handler.maybe_affirm(&node, partner, Score::new(0.9), SignedScore::new(0.7));
+        let sent = handler.take_pending_affirmations();
+        assert_eq!(sent.len(), 1);
+        assert!((sent[0].strength.value() - 0.8).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_signal_trace_along_chain() {
+        // Chain n0 - n1 - n2 - n3 - n4, with a weak last link
+        let ids: Vec<_> = (0..5).map(NodeId::from_index).collect();
+        let mut nodes: Vec<Node> = ids.iter().map(|&id| Node::new(id)).collect();
+        for i in 0..4 {
+            let weight = if i == 3 { 0.15 } else { 1.0 };
+            nodes[i].get_or_create_connection(ids[i + 1]).w = Weight::new(weight);
+            nodes[i + 1].get_or_create_connection(ids[i]).w = Weight::new(weight);
+        }
+
+        let mut handler = DefenseHandler::new().with_tracing();
+        let signal = DefenseSignal::new(
+            ids[0],
+            NodeId::from_index(99),
+            ThreatType::Strategic,
+            Score::new(0.95),
+            Hash::compute(b"chain"),
+        );
+
+        // Hand each forwarded copy to the next node down the chain
+        let mut incoming = vec![signal.clone()];
+        for node in &mut nodes[1..] {
+            let Some(arriving) = incoming.pop() else {
+                break;
+            };
+            handler.handle_signal(node, &arriving);
+            incoming = handler.take_pending_signals();
+        }
+        assert!(incoming.is_empty());
+
+        let trace = handler.trace(&signal).unwrap();
+        let delivered: Vec<_> = trace.delivered().collect();
+        assert_eq!(
+            delivered.iter().map(|h| (h.from, h.to)).collect::<Vec<_>>(),
+            vec![(ids[0], ids[1]), (ids[1], ids[2]), (ids[2], ids[3])]
+        );
+        assert!(delivered.windows(2).all(|w| w[1].confidence < w[0].confidence));
+
+        // The weak link attenuates it below MIN_SIGNAL on the fourth hop
+        let dropped: Vec<_> = trace.dropped().collect();
+        assert_eq!(dropped.len(), 1);
+        assert_eq!((dropped[0].from, dropped[0].to), (ids[3], ids[4]));
+        assert_eq!(dropped[0].hops, 3);
+        assert_eq!(dropped[0].dropped, Some(SignalDrop::BelowMinSignal));
+        assert!(dropped[0].confidence < MIN_SIGNAL);
+
+        let mut buf = Vec::new();
+        trace.export_dot(&mut buf).unwrap();
+        let dot = String::from_utf8(buf).unwrap();
+        assert!(dot.starts_with("digraph signal {"));
+        assert_eq!(dot.matches("->").count(), 4);
+        assert_eq!(dot.matches("below_min_signal").count(), 1);
+
+        // Untraced handlers keep nothing
+        assert!(DefenseHandler::new().trace(&signal).is_none());
+    }
+
+    #[test]
+    fn test_duplicate_signal_ignored() {
+        let mut node = Node::new(NodeId::from_index(7));
+        let threat = NodeId::from_index(99);
+        let mut handler = DefenseHandler::new();
+        let signal = create_test_signal();
+
+        assert!(matches!(
+            handler.handle_signal(&mut node, &signal),
+            SignalResult::Processed { .. }
+        ));
+        let belief = node.get_threat_level(&threat);
+        let priming = node.priming;
+
+        // The same evidence arriving again via another neighbor is an echo
+        let echo = signal.forward(NodeId::from_index(3), 1.0).unwrap();
+        assert!(matches!(handler.handle_signal(&mut node, &echo), SignalResult::Ignored));
+        assert_eq!(node.get_threat_level(&threat), belief);
+        assert_eq!(node.priming, priming);
+
+        // New evidence about the same threat still counts
+        let mut fresh = create_test_signal();
+        fresh.evidence = Hash::compute(b"other evidence");
+        assert!(matches!(
+            handler.handle_signal(&mut node, &fresh),
+            SignalResult::Processed { .. }
+        ));
+        assert!(node.get_threat_level(&threat) > belief);
+
+        // Once the original expires, it is no longer suppressed
+        let mut stale = create_test_signal();
+        stale.timestamp = Timestamp::new(0);
+        let mut other = Node::new(NodeId::from_index(5));
+        handler.handle_signal(&mut other, &stale);
+        assert!(matches!(
+            handler.handle_signal(&mut other, &stale),
+            SignalResult::Processed { .. }
+        ));
+    }
+
+    #[test]
+    fn test_signal_primes_threat_connection() {
+        let mut node = Node::new(NodeId::from_index(5));
+        let suspect = NodeId::from_index(99);
+        let bystander = NodeId::from_index(3);
+        node.get_or_create_connection(suspect);
+        node.get_or_create_connection(bystander);
+
+        let mut handler = DefenseHandler::new();
+        handler.handle_signal(&mut node, &create_test_signal());
+        let primed = node.get_connection(&suspect).unwrap().pi.value();
+        assert!((primed - 0.8 * PRIMING_SENSITIVITY).abs() < 1e-9);
+        assert_eq!(node.get_connection(&bystander).unwrap().pi, Score::ZERO);
+
+        for _ in 0..100 {
+            node.decay_connection_priming();
+        }
+        let decayed = node.get_connection(&suspect).unwrap().pi.value();
+        assert!((decayed - primed * PRIMING_DECAY.powi(100)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_reformed_node_recovers() {
+        let mut node = Node::new(NodeId::from_index(1));
+        let sender = NodeId::from_index(2);
+        let suspect = NodeId::from_index(99);
+        node.get_or_create_connection(sender).w = Weight::MAX;
+        node.get_or_create_connection(suspect);
+
+        // Independent reports push the belief past the action threshold
+        let mut handler = DefenseHandler::new();
+        for i in 0..5u8 {
+            let mut signal = create_test_signal();
+            signal.sender = sender;
+            signal.evidence = Hash::compute(&[i]);
+            handler.handle_signal(&mut node, &signal);
+        }
+        let flagged = node.get_threat_level(&suspect);
+        assert!(flagged > ACTION_THRESHOLD);
+        assert_eq!(node.defense_state, DefenseState::Defending);
+        assert_eq!(node.get_connection(&suspect).unwrap().w, Weight::MIN);
+        assert_eq!(node.get_connection(&suspect).unwrap().pi, Score::ONE);
+
+        // The suspect then serves well and generously, with no further reports
+        for _ in 0..600 {
+            node.decay_priming();
+            node.handle_outgoing_interaction(
+                suspect,
+                1.0,
+                1.5,
+                1.0,
+                Score::new(0.9),
+                SignedScore::ZERO,
+                None,
+            );
+        }
+
+        assert!(node.get_threat_level(&suspect) < flagged / 2.0);
+        assert_eq!(node.defense_state, DefenseState::Normal);
+        assert!(node.get_connection(&suspect).unwrap().w.value() > W_INIT);
+    }
+
     #[test]
     fn test_affirmation() {
         let affirmation = Affirmation::new(
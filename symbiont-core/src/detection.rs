@@ -1,11 +1,22 @@
 //! Adversary detection for Symbiont.
 //!
-//! Detects strategic adversaries, Sybil attacks, and collusion rings.
+//! Detects strategic adversaries, Sybil attacks, and collusion rings, and
+//! tells isolated nodes apart from Sybils.
 
-use crate::constants::{ADVERSARY_DROP, COLLUSION_THRESHOLD, DIVERSITY_THRESHOLD};
+use crate::constants::{
+    ADVERSARY_DROP, COLLUSION_EDGE_WEIGHT, COLLUSION_EXTERNAL_RATIO, COLLUSION_MUTUAL_RATING,
+    COLLUSION_THRESHOLD, DIVERSITY_THRESHOLD, ISOLATION_MAX_PARTNERS,
+    OSCILLATION_THRESHOLD, OSCILLATION_WINDOW, RAPID_GAIN_FACTOR, RAPID_GAIN_MIN_VELOCITY,
+    RECIPROCITY_ABUSE_FRACTION,
+    RECIPROCITY_ABUSE_MIN_INTERACTIONS, RECIPROCITY_ABUSE_MIN_VOLUME, RECIPROCITY_ABUSE_THRESHOLD,
+    TRUST_VOLATILITY_WINDOW,
+};
+use crate::connection::Connection;
 use crate::interaction::InteractionHistory;
+use crate::math::{exchange_ratio_log, percentile};
 use crate::node::{Node, ThreatType};
 use crate::types::{NodeId, Score};
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
 /// Result of adversary detection
@@ -42,9 +53,9 @@ impl DetectionResult {
         }
     }
 
-    /// Check if a threat was detected
+    /// Check if a threat was detected (informational results never are)
     pub fn is_threat(&self) -> bool {
-        self.threat_type.is_some() && self.confidence.value() > 0.5
+        self.threat_type.is_some_and(|t| !t.is_informational()) && self.confidence.value() > 0.5
     }
 }
 
@@ -103,11 +114,11 @@ pub fn detect_strategic_adversary(node: &Node, history: &InteractionHistory) ->
     DetectionResult::clean(node.id)
 }
 
-/// Detect low diversity (potential Sybil or isolation)
+/// Detect low diversity in a node that is not merely isolated (potential Sybil)
 pub fn detect_low_diversity(node: &Node) -> DetectionResult {
     let diversity = node.diversity_score();
 
-    if diversity.value() < DIVERSITY_THRESHOLD {
+    if diversity.value() < DIVERSITY_THRESHOLD && !node.is_isolated() {
         // Confidence increases as diversity decreases
         let confidence = 0.5 + 0.3 * (1.0 - diversity.value() / DIVERSITY_THRESHOLD);
         DetectionResult::threat(
@@ -121,6 +132,26 @@ pub fn detect_low_diversity(node: &Node) -> DetectionResult {
     }
 }
 
+/// Detect isolation: few partners, little volume, no two-way exchanges
+///
+/// The result is informational. It never counts as a threat, so it emits no
+/// defense signal; the trust cap is applied by `Node::check_diversity`.
+/// Confidence falls with each partner: 1 - partners / (ISOLATION_MAX_PARTNERS + 1).
+pub fn detect_isolation(node: &Node) -> DetectionResult {
+    if !node.is_isolated() {
+        return DetectionResult::clean(node.id);
+    }
+
+    let partners = node.active_partner_count();
+    let confidence = 1.0 - partners as f64 / (ISOLATION_MAX_PARTNERS + 1) as f64;
+    DetectionResult::threat(
+        node.id,
+        ThreatType::Isolation,
+        Score::new(confidence),
+        format!("Isolated with {partners} partner(s)"),
+    )
+}
+
 /// A suspected collusion cluster
 #[derive(Debug, Clone)]
 pub struct CollusionCluster {
@@ -137,141 +168,397 @@ pub struct CollusionCluster {
 }
 
 impl CollusionCluster {
-    /// Check if this looks like collusion
+    /// Check if this looks like collusion under the default thresholds
     pub fn is_suspicious(&self) -> bool {
-        self.internal_density > COLLUSION_THRESHOLD
-            && self.external_ratio < 1.0
-            && self.mutual_rating > 0.9
+        self.is_suspicious_with(&CollusionConfig::default())
+    }
+
+    /// Check if this looks like collusion under `config`
+    pub fn is_suspicious_with(&self, config: &CollusionConfig) -> bool {
+        self.internal_density > config.density_threshold
+            && self.external_ratio < config.max_external_ratio
+            && self.mutual_rating > config.mutual_rating_threshold
+    }
+}
+
+/// Thresholds a cluster must pass to be judged collusive
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CollusionConfig {
+    /// Internal density must exceed this
+    pub density_threshold: f64,
+    /// External ratio must stay below this
+    pub max_external_ratio: f64,
+    /// Mean mutual rating must exceed this
+    pub mutual_rating_threshold: f64,
+}
+
+impl Default for CollusionConfig {
+    fn default() -> Self {
+        Self {
+            density_threshold: COLLUSION_THRESHOLD,
+            max_external_ratio: COLLUSION_EXTERNAL_RATIO,
+            mutual_rating_threshold: COLLUSION_MUTUAL_RATING,
+        }
+    }
+}
+
+impl CollusionConfig {
+    /// Set the internal density threshold
+    pub fn with_density_threshold(mut self, threshold: f64) -> Self {
+        self.density_threshold = threshold;
+        self
+    }
+
+    /// Set the external ratio ceiling
+    pub fn with_max_external_ratio(mut self, ratio: f64) -> Self {
+        self.max_external_ratio = ratio;
+        self
+    }
+
+    /// Set the mutual rating threshold
+    pub fn with_mutual_rating_threshold(mut self, threshold: f64) -> Self {
+        self.mutual_rating_threshold = threshold;
+        self
+    }
+
+    /// Confidence that a cluster with these metrics is colluding
+    ///
+    /// confidence = 0.5 + 0.5 × (m_density + m_external + m_rating) / 3
+    ///
+    /// WHERE each margin m is how far the metric is past its threshold, as a
+    /// share of the way to its extreme, clamped to [0, 1]:
+    ///     m_density  = (density - θ_d) / (1 - θ_d)
+    ///     m_external = (θ_e - external) / θ_e
+    ///     m_rating   = (rating - θ_r) / (1 - θ_r)
+    ///
+    /// A cluster just past every threshold scores about 0.5; a closed ring
+    /// with density and rating near 1 scores near 1.
+    pub fn confidence(&self, density: f64, external_ratio: f64, mutual_rating: f64) -> Score {
+        let margins = [
+            margin(density, self.density_threshold, 1.0),
+            margin(external_ratio, self.max_external_ratio, 0.0),
+            margin(mutual_rating, self.mutual_rating_threshold, 1.0),
+        ];
+        Score::new(0.5 + 0.5 * margins.iter().sum::<f64>() / margins.len() as f64)
+    }
+}
+
+/// Share of the way `value` has moved from `threshold` toward `extreme`,
+/// clamped to [0, 1]
+fn margin(value: f64, threshold: f64, extreme: f64) -> f64 {
+    let span = extreme - threshold;
+    if span == 0.0 {
+        return if value == extreme { 1.0 } else { 0.0 };
+    }
+    ((value - threshold) / span).clamp(0.0, 1.0)
+}
+
+/// Tunable settings for graph-based detection
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DetectionConfig {
+    /// Connections weaker than this are not treated as edges
+    pub edge_weight_threshold: f64,
+    /// Thresholds for judging a cluster collusive
+    pub collusion: CollusionConfig,
+}
+
+impl Default for DetectionConfig {
+    fn default() -> Self {
+        Self {
+            edge_weight_threshold: COLLUSION_EDGE_WEIGHT,
+            collusion: CollusionConfig::default(),
+        }
+    }
+}
+
+impl DetectionConfig {
+    /// Set the minimum edge weight
+    pub fn with_edge_weight_threshold(mut self, threshold: f64) -> Self {
+        self.edge_weight_threshold = threshold;
+        self
+    }
+
+    /// Set the collusion thresholds
+    pub fn with_collusion(mut self, collusion: CollusionConfig) -> Self {
+        self.collusion = collusion;
+        self
     }
 }
 
+/// Detect collusion rings in the network with default settings
+pub fn detect_collusion(nodes: &HashMap<NodeId, Node>) -> Vec<CollusionCluster> {
+    detect_collusion_with(nodes, &DetectionConfig::default())
+}
+
 /// Detect collusion rings in the network
 ///
 /// Looks for clusters of nodes that:
 /// - Have high internal connection density
 /// - Low external connections relative to size
 /// - Suspiciously high mutual ratings
-pub fn detect_collusion(nodes: &HashMap<NodeId, Node>) -> Vec<CollusionCluster> {
+///
+/// Only connections with w >= `edge_weight_threshold` count as edges, so
+/// decayed links neither join clusters nor inflate external connections.
+///
+/// A stealth ring that serves outsiders well links as widely as any honest
+/// node, so the weight communities miss it. A second pass repeats the search
+/// over near-perfect mutual ratings only (q >= the mutual rating threshold
+/// both ways), where such a ring stands alone and is judged on internal
+/// density and mutual rating, never on the quality outsiders receive.
+pub fn detect_collusion_with(
+    nodes: &HashMap<NodeId, Node>,
+    config: &DetectionConfig,
+) -> Vec<CollusionCluster> {
+    let threshold = config.edge_weight_threshold;
+    let collusion = &config.collusion;
+
     // Build interaction graph
     let mut graph: HashMap<NodeId, HashSet<NodeId>> = HashMap::new();
 
     for (id, node) in nodes {
-        let neighbors: HashSet<NodeId> = node.connections.keys().cloned().collect();
+        let neighbors: HashSet<NodeId> = node
+            .connections
+            .iter()
+            .filter(|(_, conn)| conn.w.value() >= threshold)
+            .map(|(partner, _)| *partner)
+            .collect();
         graph.insert(*id, neighbors);
     }
 
-    // Find communities using simple connected component analysis
-    // (In production, use more sophisticated community detection)
-    let communities = find_connected_components(&graph);
-
-    let mut suspicious = Vec::new();
+    // Split the weighted graph into modularity communities, so a tight
+    // high-weight cluster separates from a loosely connected majority
+    let mut suspicious: Vec<CollusionCluster> = find_communities(&weighted_graph(nodes, threshold))
+        .into_iter()
+        .filter(|community| community.len() >= 3)
+        .map(|community| evaluate_cluster(community, &graph, nodes, collusion))
+        .filter(|cluster| cluster.is_suspicious_with(collusion))
+        .collect();
 
-    for community in communities {
-        if community.len() < 3 {
+    // Repeat over mutual praise alone to catch rings hidden by good external work
+    let praise = praise_graph(nodes, threshold, collusion.mutual_rating_threshold);
+    let praise_neighbors: HashMap<NodeId, HashSet<NodeId>> = praise
+        .iter()
+        .map(|(id, partners)| (*id, partners.keys().cloned().collect()))
+        .collect();
+    for community in find_communities(&praise) {
+        if community.len() < 3 || suspicious.iter().any(|c| !c.members.is_disjoint(&community)) {
             continue;
         }
+        let cluster = evaluate_cluster(community, &praise_neighbors, nodes, collusion);
+        if cluster.is_suspicious_with(collusion) {
+            suspicious.push(cluster);
+        }
+    }
 
-        // Calculate internal density
-        let max_edges = community.len() * (community.len() - 1) / 2;
-        let mut actual_edges = 0;
+    suspicious
+}
 
-        for node_id in &community {
-            if let Some(neighbors) = graph.get(node_id) {
-                actual_edges += neighbors.iter().filter(|n| community.contains(n)).count();
-            }
+/// Measure a candidate cluster (of at least 3 nodes) by density, external
+/// links and mutual rating
+///
+/// Confidence is zero unless the cluster passes every threshold in `config`.
+fn evaluate_cluster(
+    community: HashSet<NodeId>,
+    graph: &HashMap<NodeId, HashSet<NodeId>>,
+    nodes: &HashMap<NodeId, Node>,
+    config: &CollusionConfig,
+) -> CollusionCluster {
+    // Calculate internal density
+    let max_edges = community.len() * (community.len() - 1) / 2;
+    let mut actual_edges = 0;
+
+    for node_id in &community {
+        if let Some(neighbors) = graph.get(node_id) {
+            actual_edges += neighbors.iter().filter(|n| community.contains(n)).count();
         }
-        actual_edges /= 2; // Each edge counted twice
+    }
+    actual_edges /= 2; // Each edge counted twice
 
-        let internal_density = if max_edges > 0 {
-            actual_edges as f64 / max_edges as f64
-        } else {
-            0.0
-        };
+    let internal_density = if max_edges > 0 {
+        actual_edges as f64 / max_edges as f64
+    } else {
+        0.0
+    };
 
-        // Calculate external connections
-        let mut external_edges = 0;
-        for node_id in &community {
-            if let Some(neighbors) = graph.get(node_id) {
-                external_edges += neighbors.iter().filter(|n| !community.contains(n)).count();
-            }
+    // Calculate external connections
+    let mut external_edges = 0;
+    for node_id in &community {
+        if let Some(neighbors) = graph.get(node_id) {
+            external_edges += neighbors.iter().filter(|n| !community.contains(n)).count();
         }
-        let expected_external = community.len() as f64 * 0.5;
-        let external_ratio = if expected_external > 0.0 {
-            external_edges as f64 / expected_external
-        } else {
-            0.0
-        };
+    }
+    let expected_external = community.len() as f64 * 0.5;
+    let external_ratio = if expected_external > 0.0 {
+        external_edges as f64 / expected_external
+    } else {
+        0.0
+    };
 
-        // Calculate mutual ratings
-        let mut rating_sum = 0.0;
-        let mut rating_count = 0;
+    // Calculate mutual ratings
+    let mut rating_sum = 0.0;
+    let mut rating_count = 0;
 
-        for node_id in &community {
-            if let Some(node) = nodes.get(node_id) {
-                for (partner_id, conn) in &node.connections {
-                    if community.contains(partner_id) {
-                        rating_sum += conn.q.value();
-                        rating_count += 1;
-                    }
+    for node_id in &community {
+        if let Some(node) = nodes.get(node_id) {
+            for (partner_id, conn) in &node.connections {
+                if community.contains(partner_id) {
+                    rating_sum += conn.q.value();
+                    rating_count += 1;
                 }
             }
         }
+    }
 
-        let mutual_rating = if rating_count > 0 {
-            rating_sum / rating_count as f64
-        } else {
-            0.0
-        };
+    let mutual_rating = if rating_count > 0 {
+        rating_sum / rating_count as f64
+    } else {
+        0.0
+    };
 
-        let cluster = CollusionCluster {
-            members: community,
-            internal_density,
-            external_ratio,
-            mutual_rating,
-            confidence: Score::new(if internal_density > 0.8 { 0.7 } else { 0.3 }),
-        };
+    let mut cluster = CollusionCluster {
+        members: community,
+        internal_density,
+        external_ratio,
+        mutual_rating,
+        confidence: Score::ZERO,
+    };
+    if cluster.is_suspicious_with(config) {
+        cluster.confidence = config.confidence(internal_density, external_ratio, mutual_rating);
+    }
+    cluster
+}
 
-        if cluster.is_suspicious() {
-            suspicious.push(cluster);
+/// Weighted graph restricted to pairs rating each other near-perfectly
+///
+/// Keeps an edge only when both directions have w >= `threshold` and
+/// q >= `min_rating`.
+fn praise_graph(
+    nodes: &HashMap<NodeId, Node>,
+    threshold: f64,
+    min_rating: f64,
+) -> HashMap<NodeId, HashMap<NodeId, f64>> {
+    let mut graph: HashMap<NodeId, HashMap<NodeId, f64>> =
+        nodes.keys().map(|id| (*id, HashMap::new())).collect();
+
+    for (id, node) in nodes {
+        for (partner, conn) in &node.connections {
+            let Some(reverse) = nodes.get(partner).and_then(|p| p.connections.get(id)) else {
+                continue;
+            };
+            let praised = |c: &Connection| {
+                c.w.value() >= threshold && c.q.value() >= min_rating
+            };
+            if praised(conn) && praised(reverse) {
+                let weight = (conn.w.value() + reverse.w.value()) / 2.0;
+                graph.entry(*id).or_default().insert(*partner, weight);
+            }
         }
     }
 
-    suspicious
+    graph
 }
 
-/// Simple connected components finder
-fn find_connected_components(graph: &HashMap<NodeId, HashSet<NodeId>>) -> Vec<HashSet<NodeId>> {
-    let mut visited: HashSet<NodeId> = HashSet::new();
-    let mut components = Vec::new();
+/// Undirected weighted graph, averaging the two directions of each connection
+///
+/// Connections below `threshold` are skipped.
+fn weighted_graph(
+    nodes: &HashMap<NodeId, Node>,
+    threshold: f64,
+) -> HashMap<NodeId, HashMap<NodeId, f64>> {
+    let mut graph: HashMap<NodeId, HashMap<NodeId, f64>> =
+        nodes.keys().map(|id| (*id, HashMap::new())).collect();
 
-    for &start in graph.keys() {
-        if visited.contains(&start) {
-            continue;
+    for (id, node) in nodes {
+        for (partner, conn) in &node.connections {
+            let w = conn.w.value();
+            if w < threshold {
+                continue;
+            }
+            let reverse = nodes
+                .get(partner)
+                .and_then(|p| p.connections.get(id))
+                .map(|c| c.w.value())
+                .unwrap_or(w);
+            let weight = (w + reverse) / 2.0;
+
+            graph.entry(*id).or_default().insert(*partner, weight);
+            graph.entry(*partner).or_default().insert(*id, weight);
         }
+    }
 
-        let mut component = HashSet::new();
-        let mut stack = vec![start];
+    graph
+}
 
-        while let Some(node) = stack.pop() {
-            if visited.contains(&node) {
-                continue;
-            }
-            visited.insert(node);
-            component.insert(node);
+/// Louvain-style community detection (local moving phase)
+///
+/// Each node repeatedly joins the neighboring community with the largest
+/// modularity gain:
+/// ΔQ ∝ k_i,in − Σ_tot · k_i / 2m
+fn find_communities(graph: &HashMap<NodeId, HashMap<NodeId, f64>>) -> Vec<HashSet<NodeId>> {
+    const MAX_PASSES: usize = 100;
+
+    // Deterministic visiting order
+    let mut order: Vec<NodeId> = graph.keys().cloned().collect();
+    order.sort();
+
+    let degree: HashMap<NodeId, f64> = graph
+        .iter()
+        .map(|(id, neighbors)| (*id, neighbors.values().sum()))
+        .collect();
+    let two_m: f64 = degree.values().sum();
+
+    let mut community: HashMap<NodeId, usize> =
+        order.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+    let mut totals: Vec<f64> = order.iter().map(|id| degree[id]).collect();
+
+    if two_m > 0.0 {
+        for _ in 0..MAX_PASSES {
+            let mut moved = false;
+
+            for id in &order {
+                let k_i = degree[id];
+                let current = community[id];
+                totals[current] -= k_i;
+
+                // Weight from this node into each neighboring community
+                let mut links: HashMap<usize, f64> = HashMap::new();
+                for (neighbor, w) in &graph[id] {
+                    if neighbor != id {
+                        *links.entry(community[neighbor]).or_default() += w;
+                    }
+                }
 
-            if let Some(neighbors) = graph.get(&node) {
-                for &neighbor in neighbors {
-                    if !visited.contains(&neighbor) {
-                        stack.push(neighbor);
+                let gain = |c: usize, k_in: f64| k_in - totals[c] * k_i / two_m;
+                let mut best = current;
+                let mut best_gain = gain(current, links.get(&current).copied().unwrap_or(0.0));
+                let mut candidates: Vec<_> = links.into_iter().collect();
+                candidates.sort_by_key(|(c, _)| *c);
+                for (c, k_in) in candidates {
+                    let g = gain(c, k_in);
+                    if g > best_gain + 1e-12 {
+                        best = c;
+                        best_gain = g;
                     }
                 }
+
+                totals[best] += k_i;
+                if best != current {
+                    community.insert(*id, best);
+                    moved = true;
+                }
             }
-        }
 
-        components.push(component);
+            if !moved {
+                break;
+            }
+        }
     }
 
-    components
+    let mut groups: HashMap<usize, HashSet<NodeId>> = HashMap::new();
+    for (id, c) in community {
+        groups.entry(c).or_default().insert(id);
+    }
+    groups.into_values().collect()
 }
 
 /// Detect quality fraud (fake positive reviews)
@@ -295,22 +582,244 @@ pub fn detect_quality_fraud(node: &Node) -> DetectionResult {
     DetectionResult::clean(node.id)
 }
 
+/// Detect on-off adversaries that alternate good and bad phases
+///
+/// Averages the node's own work in windows of OSCILLATION_WINDOW interactions
+/// and flags it when the window means swing widely and cross the overall
+/// mean repeatedly. A single defection crosses once, so it is left to
+/// [`detect_strategic_adversary`].
+pub fn detect_oscillating(node: &Node, history: &InteractionHistory) -> DetectionResult {
+    let own: Vec<f64> = history
+        .all()
+        .iter()
+        .filter(|i| i.initiator == node.id)
+        .map(|i| i.quality.value())
+        .collect();
+
+    let window_means: Vec<f64> = own
+        .chunks_exact(OSCILLATION_WINDOW)
+        .map(|w| w.iter().sum::<f64>() / w.len() as f64)
+        .collect();
+
+    if window_means.len() < 4 {
+        return DetectionResult::clean(node.id);
+    }
+
+    let n = window_means.len() as f64;
+    let mean = window_means.iter().sum::<f64>() / n;
+    let std_dev = (window_means.iter().map(|m| (m - mean).powi(2)).sum::<f64>() / n).sqrt();
+
+    let crossings = window_means
+        .windows(2)
+        .filter(|pair| (pair[0] - mean).signum() != (pair[1] - mean).signum())
+        .count();
+
+    if std_dev > OSCILLATION_THRESHOLD && crossings >= 2 {
+        return DetectionResult::threat(
+            node.id,
+            ThreatType::Strategic,
+            Score::new((std_dev / 0.3).min(1.0)),
+            format!("Quality oscillates (window std dev {std_dev:.2}, {crossings} phase changes)"),
+        );
+    }
+
+    DetectionResult::clean(node.id)
+}
+
+/// Detect nodes that persistently take more than they give
+///
+/// Two signals are checked:
+/// - History entries record the exchange from the node's own side, so its
+///   reciprocity per interaction is log(given / received). Flags a mean below
+///   RECIPROCITY_ABUSE_THRESHOLD with at least three quarters of exchanges
+///   lopsided, over at least RECIPROCITY_ABUSE_MIN_VOLUME of volume.
+/// - Connections with at least RECIPROCITY_ABUSE_MIN_INTERACTIONS whose
+///   reciprocity r has gone negative. Flags when more than
+///   RECIPROCITY_ABUSE_FRACTION of them do.
+///
+/// Connection r also goes negative for a node that gives more than it gets,
+/// so a node whose history shows it is a net giver is never flagged.
+pub fn detect_reciprocity_abuse(node: &Node) -> DetectionResult {
+    let interactions = node.history.all();
+    let volume: f64 = interactions.iter().map(|i| i.volume).sum();
+
+    if !interactions.is_empty() {
+        let own_reciprocity: Vec<f64> = interactions
+            .iter()
+            .map(|i| exchange_ratio_log(i.exchange_out, i.exchange_in))
+            .collect();
+
+        let n = own_reciprocity.len() as f64;
+        let mean = own_reciprocity.iter().sum::<f64>() / n;
+        let taking = own_reciprocity.iter().filter(|&&r| r < 0.0).count() as f64 / n;
+
+        if mean > 0.0 {
+            return DetectionResult::clean(node.id);
+        }
+        if volume >= RECIPROCITY_ABUSE_MIN_VOLUME
+            && mean < RECIPROCITY_ABUSE_THRESHOLD
+            && taking >= 0.75
+        {
+            return DetectionResult::threat(
+                node.id,
+                ThreatType::Cheating,
+                Score::new(0.5 - mean / 2.0),
+                format!(
+                    "Takes more than it gives (mean reciprocity {mean:.2}, {:.0}% lopsided)",
+                    taking * 100.0
+                ),
+            );
+        }
+    }
+
+    let established: Vec<f64> = node
+        .connections
+        .values()
+        .filter(|c| c.count >= RECIPROCITY_ABUSE_MIN_INTERACTIONS)
+        .map(|c| c.r)
+        .collect();
+    if established.is_empty() {
+        return DetectionResult::clean(node.id);
+    }
+
+    let negative = established.iter().filter(|&&r| r < 0.0).count();
+    let fraction = negative as f64 / established.len() as f64;
+    if fraction > RECIPROCITY_ABUSE_FRACTION {
+        return DetectionResult::threat(
+            node.id,
+            ThreatType::Cheating,
+            Score::new(fraction),
+            format!(
+                "Negative reciprocity on {negative} of {} established connections",
+                established.len()
+            ),
+        );
+    }
+
+    DetectionResult::clean(node.id)
+}
+
+/// Median trust velocity over TRUST_VOLATILITY_WINDOW across nodes
+pub fn median_trust_velocity<'a>(nodes: impl IntoIterator<Item = &'a Node>) -> f64 {
+    let mut velocities: Vec<f64> = nodes
+        .into_iter()
+        .map(|n| n.trust_velocity(TRUST_VOLATILITY_WINDOW))
+        .collect();
+    velocities.sort_by(|a, b| a.total_cmp(b));
+    percentile(&velocities, 0.5)
+}
+
+/// Detect trust climbing far faster than the network's (reputation farming)
+///
+/// A node with a full TRUST_VOLATILITY_WINDOW of trust history is flagged
+/// when its velocity v exceeds
+///
+/// v* = max(RAPID_GAIN_FACTOR × v_median, RAPID_GAIN_MIN_VELOCITY)
+///
+/// with confidence 0.5 + 0.5 × (1 - v* / v).
+pub fn detect_rapid_trust_gain(node: &Node, median_velocity: f64) -> DetectionResult {
+    if node.trust_history.len() < TRUST_VOLATILITY_WINDOW {
+        return DetectionResult::clean(node.id);
+    }
+
+    let velocity = node.trust_velocity(TRUST_VOLATILITY_WINDOW);
+    let threshold = (RAPID_GAIN_FACTOR * median_velocity).max(RAPID_GAIN_MIN_VELOCITY);
+    if velocity <= threshold {
+        return DetectionResult::clean(node.id);
+    }
+
+    DetectionResult::threat(
+        node.id,
+        ThreatType::Strategic,
+        Score::new(0.5 + 0.5 * (1.0 - threshold / velocity)),
+        format!("Trust climbing {velocity:.4}/tick against a median of {median_velocity:.4}"),
+    )
+}
+
 /// Run all detection checks on a node
 pub fn detect_all_threats(node: &Node, history: &InteractionHistory) -> Vec<DetectionResult> {
-    let results = vec![
-        detect_strategic_adversary(node, history),
-        detect_low_diversity(node),
-        detect_quality_fraud(node),
-    ];
-
-    // Filter to only actual threats
-    results.into_iter().filter(|r| r.is_threat()).collect()
+    DetectorRegistry::default().run_all(node, history)
+}
+
+/// A per-node threat check
+pub type Detector = Box<dyn Fn(&Node, &InteractionHistory) -> DetectionResult + Send + Sync>;
+
+/// Ordered set of per-node detectors
+///
+/// The default registry holds the built-in checks; custom detectors are
+/// appended with `register` and run after them.
+pub struct DetectorRegistry {
+    detectors: Vec<Detector>,
+}
+
+impl Default for DetectorRegistry {
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry
+            .register(detect_strategic_adversary)
+            .register(detect_oscillating)
+            .register(|node, _| detect_low_diversity(node))
+            .register(|node, _| detect_quality_fraud(node))
+            .register(|node, _| detect_reciprocity_abuse(node));
+        registry
+    }
+}
+
+impl std::fmt::Debug for DetectorRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DetectorRegistry")
+            .field("detectors", &self.detectors.len())
+            .finish()
+    }
+}
+
+impl DetectorRegistry {
+    /// Create a registry with the built-in detectors
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry with no detectors
+    pub fn empty() -> Self {
+        Self {
+            detectors: Vec::new(),
+        }
+    }
+
+    /// Add a detector, run after those already registered
+    pub fn register<F>(&mut self, detector: F) -> &mut Self
+    where
+        F: Fn(&Node, &InteractionHistory) -> DetectionResult + Send + Sync + 'static,
+    {
+        self.detectors.push(Box::new(detector));
+        self
+    }
+
+    /// Number of registered detectors
+    pub fn len(&self) -> usize {
+        self.detectors.len()
+    }
+
+    /// Check if no detectors are registered
+    pub fn is_empty(&self) -> bool {
+        self.detectors.is_empty()
+    }
+
+    /// Run every detector on a node, keeping only actual threats
+    pub fn run_all(&self, node: &Node, history: &InteractionHistory) -> Vec<DetectionResult> {
+        self.detectors
+            .iter()
+            .map(|detect| detect(node, history))
+            .filter(|r| r.is_threat())
+            .collect()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::interaction::Interaction;
+    use crate::node::NodeFlag;
     use crate::types::SignedScore;
 
     #[test]
@@ -362,18 +871,224 @@ mod tests {
         assert_eq!(result.threat_type, Some(ThreatType::Strategic));
     }
 
+    fn own_history(node: &Node, qualities: impl Iterator<Item = f64>) -> InteractionHistory {
+        let mut history = InteractionHistory::new();
+        for (i, q) in qualities.enumerate() {
+            history.add(
+                Interaction::new(node.id, NodeId::from_index(100 + i as u64 % 7))
+                    .with_outcome(Score::new(q), SignedScore::ZERO),
+            );
+        }
+        history
+    }
+
     #[test]
-    fn test_low_diversity_detection() {
+    fn test_oscillating_detection() {
         let node = Node::new(NodeId::from_index(1));
-        // New node has no history, so low diversity
 
-        let result = detect_low_diversity(&node);
+        // Alternating phases of 12 good and 12 bad interactions
+        let phases = (0..96).map(|i| if (i / 12) % 2 == 0 { 0.9 } else { 0.2 });
+        let oscillating = own_history(&node, phases);
+        let result = detect_oscillating(&node, &oscillating);
         assert!(result.is_threat());
+        assert_eq!(result.threat_type, Some(ThreatType::Strategic));
+
+        // Steady honest work with noise
+        let steady = own_history(&node, (0..96).map(|i| 0.8 + 0.05 * ((i % 3) as f64 - 1.0)));
+        assert!(!detect_oscillating(&node, &steady).is_threat());
+
+        // A one-time defection is not oscillation
+        let defection = own_history(&node, (0..96).map(|i| if i < 48 { 0.9 } else { 0.2 }));
+        assert!(!detect_oscillating(&node, &defection).is_threat());
     }
 
     #[test]
-    fn test_connected_components() {
-        let mut graph: HashMap<NodeId, HashSet<NodeId>> = HashMap::new();
+    fn test_reciprocity_abuse_from_connections() {
+        let mut node = Node::new(NodeId::from_index(1));
+        for i in 0..5 {
+            let conn = node.get_or_create_connection(NodeId::from_index(100 + i));
+            conn.r = -0.8;
+            conn.count = 50;
+        }
+
+        let result = detect_reciprocity_abuse(&node);
+        assert!(result.is_threat());
+        assert_eq!(result.threat_type, Some(ThreatType::Cheating));
+        assert!(detect_all_threats(&node, &node.history)
+            .iter()
+            .any(|r| r.threat_type == Some(ThreatType::Cheating)));
+
+        // Too few interactions to judge
+        for conn in node.connections.values_mut() {
+            conn.count = 2;
+        }
+        assert!(!detect_reciprocity_abuse(&node).is_threat());
+
+        // Mostly positive reciprocity is clean
+        for (i, conn) in node.connections.values_mut().enumerate() {
+            conn.count = 50;
+            conn.r = if i < 2 { -0.8 } else { 0.4 };
+        }
+        assert!(!detect_reciprocity_abuse(&node).is_threat());
+    }
+
+    #[test]
+    fn test_reciprocity_abuse_detection() {
+        let id = NodeId::from_index(1);
+        let with_exchanges = |count: u64, received: f64, given: f64| {
+            let mut node = Node::new(id);
+            for i in 0..count {
+                let partner = NodeId::from_index(100 + i % 10);
+                node.history.add(
+                    Interaction::new(id, partner)
+                        .with_outcome(Score::new(0.75), SignedScore::ZERO)
+                        .with_exchange(received, given),
+                );
+            }
+            node
+        };
+
+        // Takes twice what it gives, at good quality
+        let skimmer = with_exchanges(40, 1.0, 0.5);
+        let result = detect_reciprocity_abuse(&skimmer);
+        assert!(result.is_threat());
+        assert_eq!(result.threat_type, Some(ThreatType::Cheating));
+        assert!(detect_all_threats(&skimmer, &skimmer.history)
+            .iter()
+            .any(|r| r.threat_type == Some(ThreatType::Cheating)));
+
+        // Balanced exchanges and low volume are clean
+        assert!(!detect_reciprocity_abuse(&with_exchanges(40, 1.0, 1.0)).is_threat());
+        assert!(!detect_reciprocity_abuse(&with_exchanges(10, 1.0, 0.5)).is_threat());
+
+        // The partner on the other side of a lopsided exchange is not flagged
+        let mut taker = Node::new(id);
+        let mut giver = Node::new(NodeId::from_index(2));
+        for _ in 0..40 {
+            let (quality, tone) = (Score::new(0.75), SignedScore::ZERO);
+            taker.handle_outgoing_interaction(giver.id, 1.0, 1.0, 0.5, quality, tone, None);
+            giver.handle_incoming_interaction(id, 1.0, 1.0, 0.5, quality, tone, None);
+        }
+        assert!(detect_reciprocity_abuse(&taker).is_threat());
+        assert!(!detect_reciprocity_abuse(&giver).is_threat());
+    }
+
+    #[test]
+    fn test_registered_detector_runs() {
+        let node = Node::new(NodeId::from_index(1));
+        let history = InteractionHistory::new();
+
+        let mut registry = DetectorRegistry::new();
+        let builtin = registry.len();
+        assert!(registry.run_all(&node, &history).is_empty());
+
+        registry.register(|node, _| {
+            DetectionResult::threat(
+                node.id,
+                ThreatType::Cheating,
+                Score::new(0.9),
+                String::from("Always a threat"),
+            )
+        });
+        assert_eq!(registry.len(), builtin + 1);
+
+        let threats = registry.run_all(&node, &history);
+        assert_eq!(threats.len(), 1);
+        assert_eq!(threats[0].node_id, node.id);
+        assert_eq!(threats[0].reason, "Always a threat");
+
+        // An empty registry flags nothing
+        assert!(DetectorRegistry::empty().run_all(&node, &history).is_empty());
+    }
+
+    #[test]
+    fn test_rapid_trust_gain_detection() {
+        let with_trust = |index: u64, trust: &dyn Fn(usize) -> f64| {
+            let mut node = Node::new(NodeId::from_index(index));
+            for t in 0..TRUST_VOLATILITY_WINDOW {
+                node.record_trust(Score::new(trust(t)));
+            }
+            node
+        };
+
+        // Eight peers hold steady while one climbs 0.02 per tick
+        let mut nodes: Vec<Node> = (0..8).map(|i| with_trust(i, &|_| 0.5)).collect();
+        let climber = with_trust(100, &|t| 0.3 + 0.02 * t as f64);
+        assert!((climber.trust_velocity(TRUST_VOLATILITY_WINDOW) - 0.02).abs() < 1e-9);
+        nodes.push(climber);
+
+        let median = median_trust_velocity(&nodes);
+        assert_eq!(median, 0.0);
+        let flagged: Vec<_> = nodes
+            .iter()
+            .filter(|n| detect_rapid_trust_gain(n, median).is_threat())
+            .map(|n| n.id)
+            .collect();
+        assert_eq!(flagged, vec![NodeId::from_index(100)]);
+
+        // The same climb is unremarkable when every node climbs alike
+        assert!(!detect_rapid_trust_gain(&nodes[8], 0.02).is_threat());
+
+        // Too little history to judge
+        let mut newcomer = Node::new(NodeId::from_index(200));
+        newcomer.record_trust(Score::new(0.1));
+        newcomer.record_trust(Score::new(0.9));
+        assert!(!detect_rapid_trust_gain(&newcomer, 0.0).is_threat());
+    }
+
+    #[test]
+    fn test_low_diversity_detection() {
+        let node = Node::new(NodeId::from_index(1));
+        // New node has no history: low diversity, but isolated rather than Sybil
+        assert!(!detect_low_diversity(&node).is_threat());
+        assert_eq!(detect_isolation(&node).threat_type, Some(ThreatType::Isolation));
+    }
+
+    #[test]
+    fn test_isolated_node_distinguished_from_sybil_ring() {
+        let quality = Score::new(0.95);
+        let tone = SignedScore::ZERO;
+
+        // A passive node that has only been asked for help a few times
+        let mut passive = Node::new(NodeId::from_index(1));
+        let asker = NodeId::from_index(2);
+        for _ in 0..3 {
+            passive.handle_incoming_interaction(asker, 1.0, 1.0, 1.0, quality, tone, None);
+        }
+
+        // A ring member trading heavily, both ways, with its two accomplices
+        let mut member = Node::new(NodeId::from_index(10));
+        for partner in [NodeId::from_index(11), NodeId::from_index(12)] {
+            for _ in 0..10 {
+                member.handle_outgoing_interaction(partner, 1.0, 1.0, 1.0, quality, tone, None);
+                member.handle_incoming_interaction(partner, 1.0, 1.0, 1.0, quality, tone, None);
+            }
+        }
+
+        let isolation = detect_isolation(&passive);
+        assert_eq!(isolation.threat_type, Some(ThreatType::Isolation));
+        assert!(isolation.confidence.value() > 0.5);
+        assert!(!isolation.is_threat());
+        assert!(!detect_low_diversity(&passive).is_threat());
+        assert!(detect_all_threats(&passive, &passive.history).is_empty());
+
+        assert!(detect_isolation(&member).threat_type.is_none());
+        let sybil = detect_low_diversity(&member);
+        assert!(sybil.is_threat());
+        assert_eq!(sybil.threat_type, Some(ThreatType::Sybil));
+
+        // Isolation caps trust harder than low diversity alone
+        passive.check_diversity();
+        member.check_diversity();
+        assert!(passive.flags.contains(&NodeFlag::Isolated));
+        assert!(!passive.flags.contains(&NodeFlag::LowDiversity));
+        assert!(member.flags.contains(&NodeFlag::LowDiversity));
+        assert!(passive.trust_cap < member.trust_cap);
+    }
+
+    #[test]
+    fn test_find_communities() {
+        let mut graph: HashMap<NodeId, HashMap<NodeId, f64>> = HashMap::new();
 
         // Create two disconnected clusters
         // Cluster 1: nodes 1, 2, 3
@@ -381,22 +1096,223 @@ mod tests {
         let n2 = NodeId::from_index(2);
         let n3 = NodeId::from_index(3);
 
-        graph.insert(n1, [n2, n3].into_iter().collect());
-        graph.insert(n2, [n1, n3].into_iter().collect());
-        graph.insert(n3, [n1, n2].into_iter().collect());
+        graph.insert(n1, [(n2, 1.0), (n3, 1.0)].into_iter().collect());
+        graph.insert(n2, [(n1, 1.0), (n3, 1.0)].into_iter().collect());
+        graph.insert(n3, [(n1, 1.0), (n2, 1.0)].into_iter().collect());
 
         // Cluster 2: nodes 4, 5
         let n4 = NodeId::from_index(4);
         let n5 = NodeId::from_index(5);
 
-        graph.insert(n4, [n5].into_iter().collect());
-        graph.insert(n5, [n4].into_iter().collect());
+        graph.insert(n4, [(n5, 1.0)].into_iter().collect());
+        graph.insert(n5, [(n4, 1.0)].into_iter().collect());
 
-        let components = find_connected_components(&graph);
+        let components = find_communities(&graph);
         assert_eq!(components.len(), 2);
 
         let sizes: Vec<_> = components.iter().map(|c| c.len()).collect();
         assert!(sizes.contains(&3));
         assert!(sizes.contains(&2));
     }
+
+    #[test]
+    fn test_collusion_ring_separated_from_honest_core() {
+        use crate::types::Weight;
+
+        fn link(nodes: &mut HashMap<NodeId, Node>, a: NodeId, b: NodeId, w: f64, q: f64) {
+            for (from, to) in [(a, b), (b, a)] {
+                let conn = nodes.get_mut(&from).unwrap().get_or_create_connection(to);
+                conn.w = Weight::new(w);
+                conn.q = Score::new(q);
+            }
+        }
+
+        let mut nodes: HashMap<NodeId, Node> = (0..16)
+            .map(|i| (NodeId::from_index(i), Node::new(NodeId::from_index(i))))
+            .collect();
+
+        // Honest core: 12 nodes, each linked to its next three neighbors
+        for i in 0..12u64 {
+            for step in 1..=3 {
+                let j = (i + step) % 12;
+                link(&mut nodes, NodeId::from_index(i), NodeId::from_index(j), 0.3, 0.7);
+            }
+        }
+
+        // Collusion ring: 4 fully interconnected nodes rating each other highly
+        let ring: Vec<_> = (12..16).map(NodeId::from_index).collect();
+        for a in 0..ring.len() {
+            for b in (a + 1)..ring.len() {
+                link(&mut nodes, ring[a], ring[b], 1.0, 0.95);
+            }
+        }
+
+        // A single weak bridge joins the ring to the core
+        link(&mut nodes, ring[0], NodeId::from_index(0), 0.1, 0.7);
+
+        let clusters = detect_collusion(&nodes);
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters[0].members, ring.into_iter().collect());
+    }
+
+    #[test]
+    fn test_stealth_ring_flagged_despite_good_external_work() {
+        use crate::types::Weight;
+
+        fn link(nodes: &mut HashMap<NodeId, Node>, a: NodeId, b: NodeId, q: f64) {
+            for (from, to) in [(a, b), (b, a)] {
+                let conn = nodes.get_mut(&from).unwrap().get_or_create_connection(to);
+                conn.w = Weight::MAX;
+                conn.q = Score::new(q);
+            }
+        }
+
+        let mut nodes: HashMap<NodeId, Node> = (0..16)
+            .map(|i| (NodeId::from_index(i), Node::new(NodeId::from_index(i))))
+            .collect();
+        let core: Vec<_> = (0..12).map(NodeId::from_index).collect();
+        let ring: Vec<_> = (12..16).map(NodeId::from_index).collect();
+
+        // Everyone is strongly linked to everyone, so weights alone show no ring
+        for a in 0..16 {
+            for b in (a + 1)..16 {
+                link(&mut nodes, NodeId::from_index(a), NodeId::from_index(b), 0.8);
+            }
+        }
+
+        // Outsiders rate the ring's work highly; only the ring praises itself
+        for member in &ring {
+            for outsider in &core {
+                link(&mut nodes, *member, *outsider, 0.88);
+            }
+        }
+        for a in 0..ring.len() {
+            for b in (a + 1)..ring.len() {
+                link(&mut nodes, ring[a], ring[b], 0.99);
+            }
+        }
+
+        let clusters = detect_collusion(&nodes);
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters[0].members, ring.into_iter().collect());
+        assert!(clusters[0].internal_density > COLLUSION_THRESHOLD);
+        assert!(clusters[0].mutual_rating > COLLUSION_MUTUAL_RATING);
+    }
+
+    #[test]
+    fn test_collusion_thresholds_and_confidence_scale() {
+        fn cluster(density: f64, external: f64, rating: f64) -> CollusionCluster {
+            CollusionCluster {
+                members: HashSet::new(),
+                internal_density: density,
+                external_ratio: external,
+                mutual_rating: rating,
+                confidence: Score::ZERO,
+            }
+        }
+        let config = CollusionConfig::default();
+
+        // Each threshold is strict: sitting exactly on one clears the cluster
+        let (d, e, r) = (COLLUSION_THRESHOLD, COLLUSION_EXTERNAL_RATIO, COLLUSION_MUTUAL_RATING);
+        assert!(!cluster(d, 0.0, 0.99).is_suspicious());
+        assert!(!cluster(0.99, e, 0.99).is_suspicious());
+        assert!(!cluster(0.99, 0.0, r).is_suspicious());
+        assert!(cluster(d + 0.01, e - 0.01, r + 0.01).is_suspicious());
+
+        // Just past every threshold: flagged, but only modestly confident
+        let barely = config.confidence(d + 0.01, e - 0.01, r + 0.01).value();
+        assert!(barely > 0.5 && barely < 0.6);
+
+        // A dense, closed ring of near-perfect mutual praise
+        let blatant = config.confidence(0.99, 0.0, 0.98).value();
+        assert!(blatant > 0.95);
+
+        // Confidence grows with each margin
+        let denser = config.confidence(0.95, 0.5, 0.95).value();
+        assert!(denser > config.confidence(0.9, 0.5, 0.95).value());
+        assert!(denser > config.confidence(0.95, 0.8, 0.95).value());
+        assert!(denser < config.confidence(0.95, 0.5, 0.97).value());
+
+        // Looser thresholds catch what the defaults let through
+        let loose = CollusionConfig::default()
+            .with_density_threshold(0.6)
+            .with_max_external_ratio(2.0)
+            .with_mutual_rating_threshold(0.8);
+        let mild = cluster(0.7, 1.5, 0.85);
+        assert!(!mild.is_suspicious());
+        assert!(mild.is_suspicious_with(&loose));
+    }
+
+    #[test]
+    fn test_detected_cluster_confidence_uses_config() {
+        use crate::types::Weight;
+
+        let mut nodes: HashMap<NodeId, Node> = (0..4)
+            .map(|i| (NodeId::from_index(i), Node::new(NodeId::from_index(i))))
+            .collect();
+        for a in 0..4 {
+            for b in 0..4 {
+                if a != b {
+                    let conn = nodes
+                        .get_mut(&NodeId::from_index(a))
+                        .unwrap()
+                        .get_or_create_connection(NodeId::from_index(b));
+                    conn.w = Weight::MAX;
+                    conn.q = Score::new(0.93);
+                }
+            }
+        }
+
+        // A closed, complete ring just past the default rating threshold
+        let clusters = detect_collusion(&nodes);
+        assert_eq!(clusters.len(), 1);
+        let confidence = clusters[0].confidence.value();
+        assert!(confidence > 0.8 && confidence < 0.95);
+
+        // A stricter rating threshold clears it entirely
+        let strict = DetectionConfig::default()
+            .with_collusion(CollusionConfig::default().with_mutual_rating_threshold(0.95));
+        assert!(detect_collusion_with(&nodes, &strict).is_empty());
+    }
+
+    #[test]
+    fn test_weak_links_ignored_above_threshold() {
+        use crate::types::Weight;
+
+        fn link(nodes: &mut HashMap<NodeId, Node>, a: NodeId, b: NodeId, w: f64) {
+            for (from, to) in [(a, b), (b, a)] {
+                let conn = nodes.get_mut(&from).unwrap().get_or_create_connection(to);
+                conn.w = Weight::new(w);
+                conn.q = Score::new(0.95);
+            }
+        }
+
+        let ids: Vec<_> = (0..8).map(NodeId::from_index).collect();
+        let mut nodes: HashMap<NodeId, Node> = ids.iter().map(|id| (*id, Node::new(*id))).collect();
+
+        // Two fully interconnected clusters of four
+        for cluster in [&ids[..4], &ids[4..]] {
+            for a in 0..cluster.len() {
+                for b in (a + 1)..cluster.len() {
+                    link(&mut nodes, cluster[a], cluster[b], 0.9);
+                }
+            }
+        }
+
+        // Joined only by weak links, enough to look externally connected
+        for i in 0..4 {
+            link(&mut nodes, ids[i], ids[4 + i], 0.2);
+        }
+
+        let permissive = DetectionConfig::default().with_edge_weight_threshold(0.0);
+        assert!(detect_collusion_with(&nodes, &permissive).is_empty());
+
+        let strict = DetectionConfig::default().with_edge_weight_threshold(0.3);
+        let clusters = detect_collusion_with(&nodes, &strict);
+        assert_eq!(clusters.len(), 2);
+        for cluster in &clusters {
+            assert_eq!(cluster.members.len(), 4);
+            assert_eq!(cluster.external_ratio, 0.0);
+        }
+    }
 }
@@ -3,7 +3,9 @@
 use crate::routing::Task;
 use crate::types::{Hash, NodeId, Signature, Timestamp, WorkflowId};
 use crate::workflow::WorkflowContext;
+use ed25519_dalek::{Signer, SigningKey, Verifier, VerifyingKey};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// A handoff of work from one node to another
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,6 +20,8 @@ pub struct Handoff {
     pub context: HandoffContext,
     /// Timestamp of handoff
     pub timestamp: Timestamp,
+    /// Hash of the preceding handoff when part of a chain
+    pub prev_hash: Option<Hash>,
     /// Signature from the sending node
     pub signature: Signature,
 }
@@ -83,21 +87,48 @@ impl Handoff {
             task,
             context,
             timestamp: Timestamp::now(),
+            prev_hash: None,
             signature: Signature::new([0u8; 64]), // Placeholder
         }
     }
 
     /// Compute the hash of this handoff (for signing)
+    ///
+    /// Covers every field but the signature, serialized as JSON with object
+    /// keys sorted, so changing the task or context breaks the signature too.
     pub fn compute_hash(&self) -> Hash {
-        // In production, serialize properly
-        let data = format!(
-            "{}:{}:{}:{}",
-            self.from_node,
-            self.to_node,
-            self.task.id.0,
-            self.timestamp.millis()
-        );
-        Hash::compute(data.as_bytes())
+        #[derive(Serialize)]
+        struct Unsigned<'a> {
+            from_node: NodeId,
+            to_node: NodeId,
+            task: &'a Task,
+            context: &'a HandoffContext,
+            timestamp: Timestamp,
+            prev_hash: Option<Hash>,
+        }
+
+        let unsigned = Unsigned {
+            from_node: self.from_node,
+            to_node: self.to_node,
+            task: &self.task,
+            context: &self.context,
+            timestamp: self.timestamp,
+            prev_hash: self.prev_hash,
+        };
+        let value = serde_json::to_value(unsigned).expect("handoff serializes to JSON");
+        Hash::compute(&serde_json::to_vec(&value).expect("JSON value serializes"))
+    }
+
+    /// Sign this handoff's hash with the sending node's key
+    pub fn sign(&mut self, key: &SigningKey) {
+        let signature = key.sign(self.compute_hash().as_bytes());
+        self.signature = Signature::new(signature.to_bytes());
+    }
+
+    /// Verify the signature against the sending node's public key
+    pub fn verify(&self, key: &VerifyingKey) -> bool {
+        let signature = ed25519_dalek::Signature::from_bytes(self.signature.as_bytes());
+        key.verify(self.compute_hash().as_bytes(), &signature).is_ok()
     }
 
     /// Check if handoff is still valid (not too old)
@@ -106,6 +137,91 @@ impl Handoff {
     }
 }
 
+/// An ordered chain of handoffs, each linked to the hash of the one before
+///
+/// Every hop is signed by its sender over a hash that covers the previous
+/// hop's hash, so altering any hop breaks every link after it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HandoffChain {
+    /// Handoffs in the order they happened
+    handoffs: Vec<Handoff>,
+}
+
+impl HandoffChain {
+    /// Create an empty chain
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a handoff, linking it to the hash of the current last entry
+    ///
+    /// Linking changes the handoff's hash, so sign it afterwards (or use
+    /// `push_signed`).
+    pub fn push(&mut self, mut handoff: Handoff) {
+        handoff.prev_hash = self.handoffs.last().map(Handoff::compute_hash);
+        self.handoffs.push(handoff);
+    }
+
+    /// Append a handoff and sign it with the sender's key once linked
+    pub fn push_signed(&mut self, handoff: Handoff, key: &SigningKey) {
+        self.push(handoff);
+        if let Some(last) = self.handoffs.last_mut() {
+            last.sign(key);
+        }
+    }
+
+    /// Handoffs in order
+    pub fn handoffs(&self) -> &[Handoff] {
+        &self.handoffs
+    }
+
+    /// Number of hops
+    pub fn len(&self) -> usize {
+        self.handoffs.len()
+    }
+
+    /// Check if empty
+    pub fn is_empty(&self) -> bool {
+        self.handoffs.is_empty()
+    }
+
+    /// Check every link, hop continuity, and sender signature
+    ///
+    /// Each hop must start where the previous one ended, carry the previous
+    /// hop's hash, and be signed by a sender whose key is in `pubkeys`.
+    pub fn verify_chain(&self, pubkeys: &HashMap<NodeId, VerifyingKey>) -> bool {
+        let mut prev: Option<&Handoff> = None;
+        for handoff in &self.handoffs {
+            if handoff.prev_hash != prev.map(Handoff::compute_hash) {
+                return false;
+            }
+            if prev.is_some_and(|p| p.to_node != handoff.from_node) {
+                return false;
+            }
+            match pubkeys.get(&handoff.from_node) {
+                Some(key) if handoff.verify(key) => {}
+                _ => return false,
+            }
+            prev = Some(handoff);
+        }
+        true
+    }
+
+    /// Nodes that received work, in order and without repeats
+    ///
+    /// Matches `WorkflowContext::lineage` for a workflow whose steps were
+    /// handed along this chain.
+    pub fn lineage(&self) -> Vec<NodeId> {
+        let mut lineage = Vec::new();
+        for handoff in &self.handoffs {
+            if !lineage.contains(&handoff.to_node) {
+                lineage.push(handoff.to_node);
+            }
+        }
+        lineage
+    }
+}
+
 /// Result of processing a handoff
 #[derive(Debug, Clone)]
 pub enum HandoffResult {
@@ -118,7 +234,12 @@ pub enum HandoffResult {
     InvalidSignature,
     /// Missing required capability
     MissingCapability,
-    /// Node is overloaded
+    /// Node was busy and queued the task at this backlog position
+    Queued {
+        /// Position in the node's task queue
+        position: usize,
+    },
+    /// Node is overloaded and its task queue is full
     Overloaded,
     /// Handoff expired
     Expired,
@@ -163,10 +284,13 @@ impl HandoffHandler {
     }
 
     /// Process a handoff for a node
+    ///
+    /// A node too busy to start the task queues it instead, so only a full
+    /// backlog turns the handoff away.
     pub fn process(
         &self,
         handoff: &Handoff,
-        node: &crate::node::Node,
+        node: &mut crate::node::Node,
     ) -> HandoffResult {
         // Validate
         if let Some(result) = self.validate(handoff) {
@@ -181,7 +305,10 @@ impl HandoffHandler {
             }
 
             if !node.can_accept_capability_work(cap) {
-                return HandoffResult::Overloaded;
+                return match node.enqueue_task(&handoff.task) {
+                    Ok(position) => HandoffResult::Queued { position },
+                    Err(_) => HandoffResult::Overloaded,
+                };
             }
         }
 
@@ -216,8 +343,10 @@ pub fn create_handoff(
 mod tests {
     use super::*;
     use crate::capability::common;
+    use crate::constants::TASK_QUEUE_CAPACITY;
+    use crate::node::Node;
     use crate::routing::Task;
-    use crate::types::TaskId;
+    use crate::types::{Score, TaskId, WorkflowId};
 
     #[test]
     fn test_handoff_creation() {
@@ -255,6 +384,84 @@ mod tests {
         assert!(handler.validate(&handoff).is_none());
     }
 
+    #[test]
+    fn test_handoff_chain_verifies_and_detects_tampering() {
+        let keys: Vec<SigningKey> = (0..4).map(|i| SigningKey::from_bytes(&[i + 1; 32])).collect();
+        let pubkeys: HashMap<_, _> = keys
+            .iter()
+            .enumerate()
+            .map(|(i, k)| (NodeId::from_index(i as u64), k.verifying_key()))
+            .collect();
+
+        // 0 -> 1 -> 2 -> 3, each hop signed by its sender
+        let mut chain = HandoffChain::new();
+        for i in 0..3u64 {
+            let (from, to) = (NodeId::from_index(i), NodeId::from_index(i + 1));
+            let task = Task::new(TaskId::new(i), from, common::analysis().id);
+            let handoff = Handoff::new(from, to, task, HandoffContext::new());
+            chain.push_signed(handoff, &keys[i as usize]);
+        }
+
+        assert_eq!(chain.len(), 3);
+        assert!(chain.handoffs()[0].prev_hash.is_none());
+        assert!(chain.verify_chain(&pubkeys));
+        assert_eq!(
+            chain.lineage(),
+            vec![NodeId::from_index(1), NodeId::from_index(2), NodeId::from_index(3)]
+        );
+
+        // Workflow lineage can be rebuilt from the chain
+        let mut ctx = WorkflowContext::new(WorkflowId::new(1));
+        ctx.restore_lineage(&chain);
+        assert_eq!(ctx.lineage, chain.lineage());
+
+        // Redirecting the middle hop breaks its signature and the next link
+        let mut tampered = chain.clone();
+        tampered.handoffs[1].to_node = NodeId::from_index(9);
+        assert!(!tampered.verify_chain(&pubkeys));
+
+        // Rewriting the context the receiver sees breaks the signature
+        let mut rewritten = chain.clone();
+        rewritten.handoffs[0].context.accumulated.push((String::from("note"), vec![1]));
+        assert!(!rewritten.handoffs[0].verify(&pubkeys[&NodeId::from_index(0)]));
+        assert!(!rewritten.verify_chain(&pubkeys));
+
+        let mut shifted = chain.clone();
+        shifted.handoffs[2].context.step_index = 7;
+        assert!(!shifted.verify_chain(&pubkeys));
+
+        // Re-signing with the wrong key does not help
+        let mut forged = chain.clone();
+        forged.handoffs[1].task.id = TaskId::new(42);
+        forged.handoffs[1].sign(&keys[3]);
+        assert!(!forged.verify_chain(&pubkeys));
+
+        // Unknown senders cannot be verified
+        assert!(!chain.verify_chain(&HashMap::new()));
+    }
+
+    #[test]
+    fn test_busy_node_queues_handoff() {
+        let handler = HandoffHandler::new(60_000);
+        let cap = common::analysis();
+        let from = NodeId::from_index(1);
+        let mut node = Node::with_capabilities(NodeId::from_index(2), vec![cap.clone()]);
+        node.load = Score::new(0.99);
+
+        for i in 0..TASK_QUEUE_CAPACITY as u64 {
+            let task = Task::new(TaskId::new(i), from, cap.id);
+            let handoff = Handoff::new(from, node.id, task, HandoffContext::new());
+            let result = handler.process(&handoff, &mut node);
+            assert!(matches!(result, HandoffResult::Queued { position } if position == i as usize));
+        }
+
+        // Once the backlog is full the handoff is refused
+        let task = Task::new(TaskId::new(99), from, cap.id);
+        let handoff = Handoff::new(from, node.id, task, HandoffContext::new());
+        assert!(matches!(handler.process(&handoff, &mut node), HandoffResult::Overloaded));
+        assert_eq!(node.task_queue.len(), TASK_QUEUE_CAPACITY);
+    }
+
     #[test]
     fn test_expired_handoff() {
         let handler = HandoffHandler::new(0); // Immediate expiry
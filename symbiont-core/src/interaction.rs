@@ -1,9 +1,12 @@
 //! Interaction structures for recording exchanges between nodes.
 
-use crate::constants::{OMEGA_ACC, OMEGA_HELP, OMEGA_REL, OMEGA_TIME, REUSE_BOOST, REUSE_PENALTY};
+use crate::constants::INTERACTION_HISTORY_SIZE;
+use crate::constants::{OMEGA_ACC, OMEGA_HELP, OMEGA_REL, OMEGA_TIME};
+use crate::constants::{REUSE_BOOST, REUSE_PENALTY, REUSE_WEIGHT};
 use crate::constants::{TONE_WEIGHT_COLLABORATION, TONE_WEIGHT_ENGAGEMENT, TONE_WEIGHT_FRIENDLINESS};
 use crate::types::{CapabilityId, NodeId, Score, SignedScore, Timestamp};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// An interaction between two nodes
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -70,16 +73,26 @@ impl Interaction {
         self
     }
 
-    /// Create from feedback
+    /// The other party, as seen from `id`
+    pub fn partner_of(&self, id: NodeId) -> NodeId {
+        if self.responder == id {
+            self.initiator
+        } else {
+            self.responder
+        }
+    }
+
+    /// Create from feedback and observed tone signals
     pub fn from_feedback(
         initiator: NodeId,
         responder: NodeId,
         capability: Option<CapabilityId>,
         feedback: &Feedback,
+        tone_signals: &ToneSignals,
         volume: f64,
     ) -> Self {
         let quality = feedback.compute_quality();
-        let tone = SignedScore::ZERO; // Tone computed separately
+        let tone = tone_signals.compute_tone();
 
         Self {
             initiator,
@@ -143,17 +156,65 @@ impl Feedback {
         Self::new(3, 3, 3, 3, false)
     }
 
-    /// Compute quality score from feedback
+    /// Ratings in field order: helpfulness, accuracy, relevance, timeliness
+    fn ratings(&self) -> [(&'static str, u8); 4] {
+        [
+            ("helpfulness", self.helpfulness),
+            ("accuracy", self.accuracy),
+            ("relevance", self.relevance),
+            ("timeliness", self.timeliness),
+        ]
+    }
+
+    /// Check that ratings are in 1..=5 and agree with `would_reuse`
     ///
-    /// Q_raw = (ω_HELP × helpfulness + ω_ACC × accuracy +
-    ///          ω_REL × relevance + ω_TIME × timeliness) / 4
-    /// Q_multiplied = Q_raw × (REUSE_BOOST if would_reuse else REUSE_PENALTY)
-    /// Q_normalized = (Q_multiplied - 1) / 4  // Maps to [0, 1]
+    /// Top marks everywhere while declining reuse, or bottom marks everywhere
+    /// while asking for more, cannot both be honest answers.
+    pub fn validate(&self) -> Result<(), FeedbackError> {
+        for (field, value) in self.ratings() {
+            if !(1..=5).contains(&value) {
+                return Err(FeedbackError::RatingOutOfRange { field, value });
+            }
+        }
+
+        let all = |rating: u8| self.ratings().iter().all(|(_, v)| *v == rating);
+        if (all(5) && !self.would_reuse) || (all(1) && self.would_reuse) {
+            return Err(FeedbackError::InconsistentReuse);
+        }
+        Ok(())
+    }
+
+    /// Compute quality score from feedback using the default weights
     pub fn compute_quality(&self) -> Score {
-        let q_raw = OMEGA_HELP * self.helpfulness as f64
-            + OMEGA_ACC * self.accuracy as f64
-            + OMEGA_REL * self.relevance as f64
-            + OMEGA_TIME * self.timeliness as f64;
+        self.compute_quality_with(&FeedbackWeights::default())
+    }
+
+    /// Compute quality score from feedback with custom weights
+    ///
+    /// Q_raw = ω_help × helpfulness + ω_acc × accuracy +
+    ///         ω_rel × relevance + ω_time × timeliness
+    /// Q_multiplied = Q_raw × (REUSE_BOOST if would_reuse else REUSE_PENALTY)
+    /// Q_normalized = (Q_multiplied - 0.8) / (6.0 - 0.8)  // Maps to [0, 1]
+    pub fn compute_quality_with(&self, weights: &FeedbackWeights) -> Score {
+        self.compute_quality_mapped(weights, QualityMapping::Multiplier)
+    }
+
+    /// Compute quality score with custom weights and a choice of mapping
+    pub fn compute_quality_mapped(
+        &self,
+        weights: &FeedbackWeights,
+        mapping: QualityMapping,
+    ) -> Score {
+        let q_raw = weights.helpfulness * self.helpfulness as f64
+            + weights.accuracy * self.accuracy as f64
+            + weights.relevance * self.relevance as f64
+            + weights.timeliness * self.timeliness as f64;
+
+        if mapping == QualityMapping::Smooth {
+            let reuse = if self.would_reuse { 1.0 } else { 0.0 };
+            let rated = (q_raw - 1.0) / 4.0;
+            return Score::new((1.0 - REUSE_WEIGHT) * rated + REUSE_WEIGHT * reuse);
+        }
 
         let multiplier = if self.would_reuse {
             REUSE_BOOST
@@ -166,12 +227,121 @@ impl Feedback {
         // Map from [0.8, 6.0] range to [0, 1]
         // Min: 1 * 0.8 = 0.8
         // Max: 5 * 1.2 = 6.0
+        // Weights sum to 1, so the range holds for any valid weighting
         let q_normalized = (q_multiplied - 0.8) / (6.0 - 0.8);
 
         Score::new(q_normalized)
     }
 }
 
+/// Invalid or self-contradictory feedback
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum FeedbackError {
+    /// A rating outside 1..=5
+    #[error("{field} rating {value} is outside 1..=5")]
+    RatingOutOfRange { field: &'static str, value: u8 },
+    /// Ratings contradict the would_reuse answer
+    #[error("uniform ratings contradict would_reuse")]
+    InconsistentReuse,
+}
+
+/// How feedback ratings and the reuse answer map to quality
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QualityMapping {
+    /// Scale ratings by REUSE_BOOST or REUSE_PENALTY
+    ///
+    /// Toggling would_reuse jumps quality by up to 0.4 × Q_raw / 5.2, so the
+    /// jump is largest for the best-rated work.
+    #[default]
+    Multiplier,
+    /// Q = (1 - REUSE_WEIGHT) × (Q_raw - 1) / 4 + REUSE_WEIGHT × [would_reuse]
+    ///
+    /// Monotonic in every rating, and toggling would_reuse moves quality by
+    /// exactly REUSE_WEIGHT wherever the ratings sit.
+    Smooth,
+}
+
+/// Weights for aggregating feedback ratings into a quality score
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "RawFeedbackWeights")]
+pub struct FeedbackWeights {
+    /// Weight for helpfulness (ω_help)
+    helpfulness: f64,
+    /// Weight for accuracy (ω_acc)
+    accuracy: f64,
+    /// Weight for relevance (ω_rel)
+    relevance: f64,
+    /// Weight for timeliness (ω_time)
+    timeliness: f64,
+}
+
+impl Default for FeedbackWeights {
+    fn default() -> Self {
+        Self {
+            helpfulness: OMEGA_HELP,
+            accuracy: OMEGA_ACC,
+            relevance: OMEGA_REL,
+            timeliness: OMEGA_TIME,
+        }
+    }
+}
+
+/// Deserialized weights, checked by [`FeedbackWeights::new`]
+#[derive(Deserialize)]
+struct RawFeedbackWeights {
+    helpfulness: f64,
+    accuracy: f64,
+    relevance: f64,
+    timeliness: f64,
+}
+
+impl TryFrom<RawFeedbackWeights> for FeedbackWeights {
+    type Error = String;
+
+    fn try_from(raw: RawFeedbackWeights) -> Result<Self, Self::Error> {
+        Self::new(raw.helpfulness, raw.accuracy, raw.relevance, raw.timeliness)
+            .ok_or_else(|| "feedback weights must be non-negative and sum to 1".to_string())
+    }
+}
+
+impl FeedbackWeights {
+    /// Create weights, returning None unless all are non-negative and sum to 1
+    pub fn new(helpfulness: f64, accuracy: f64, relevance: f64, timeliness: f64) -> Option<Self> {
+        let weights = [helpfulness, accuracy, relevance, timeliness];
+        let sum: f64 = weights.iter().sum();
+        if weights.iter().any(|w| w.is_nan() || *w < 0.0) || (sum - 1.0).abs() > 1e-6 {
+            return None;
+        }
+
+        Some(Self {
+            helpfulness,
+            accuracy,
+            relevance,
+            timeliness,
+        })
+    }
+
+    /// Weight for helpfulness
+    pub fn helpfulness(&self) -> f64 {
+        self.helpfulness
+    }
+
+    /// Weight for accuracy
+    pub fn accuracy(&self) -> f64 {
+        self.accuracy
+    }
+
+    /// Weight for relevance
+    pub fn relevance(&self) -> f64 {
+        self.relevance
+    }
+
+    /// Weight for timeliness
+    pub fn timeliness(&self) -> f64 {
+        self.timeliness
+    }
+}
+
 /// Signals for computing tone score
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ToneSignals {
@@ -275,18 +445,25 @@ impl ToneSignals {
 }
 
 /// History of interactions for a connection
+///
+/// Keeps a running mean and sum of squared deviations (Welford's algorithm)
+/// over the retained interactions, so full-history quality queries are O(1).
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct InteractionHistory {
     /// Recent interactions (most recent first)
     interactions: Vec<Interaction>,
     /// Maximum history size
     max_size: usize,
+    /// Running mean quality of retained interactions
+    quality_mean: f64,
+    /// Running sum of squared deviations from the mean (M2)
+    quality_m2: f64,
 }
 
 impl InteractionHistory {
-    /// Create with default max size (100)
+    /// Create with default max size (INTERACTION_HISTORY_SIZE)
     pub fn new() -> Self {
-        Self::with_max_size(100)
+        Self::with_max_size(INTERACTION_HISTORY_SIZE)
     }
 
     /// Create with specific max size
@@ -294,15 +471,45 @@ impl InteractionHistory {
         Self {
             interactions: Vec::with_capacity(max_size),
             max_size,
+            quality_mean: 0.0,
+            quality_m2: 0.0,
         }
     }
 
     /// Add an interaction to history
     pub fn add(&mut self, interaction: Interaction) {
+        self.push_quality(interaction.quality.value());
         self.interactions.insert(0, interaction);
         if self.interactions.len() > self.max_size {
-            self.interactions.pop();
+            if let Some(evicted) = self.interactions.pop() {
+                self.pop_quality(evicted.quality.value());
+            }
+        }
+    }
+
+    /// Welford update for a new quality sample (call before inserting)
+    ///
+    /// δ = x - μ, μ' = μ + δ / n', M2' = M2 + δ × (x - μ')
+    fn push_quality(&mut self, x: f64) {
+        let n = (self.interactions.len() + 1) as f64;
+        let delta = x - self.quality_mean;
+        self.quality_mean += delta / n;
+        self.quality_m2 += delta * (x - self.quality_mean);
+    }
+
+    /// Reverse Welford update for an evicted sample (call after removing)
+    ///
+    /// δ = x - μ, μ' = μ - δ / n', M2' = M2 - δ × (x - μ')
+    fn pop_quality(&mut self, x: f64) {
+        let n = self.interactions.len() as f64;
+        if n == 0.0 {
+            self.quality_mean = 0.0;
+            self.quality_m2 = 0.0;
+            return;
         }
+        let delta = x - self.quality_mean;
+        self.quality_mean -= delta / n;
+        self.quality_m2 = (self.quality_m2 - delta * (x - self.quality_mean)).max(0.0);
     }
 
     /// Get recent interactions
@@ -311,6 +518,16 @@ impl InteractionHistory {
         &self.interactions[..end]
     }
 
+    /// Maximum number of interactions retained
+    pub fn max_size(&self) -> usize {
+        self.max_size
+    }
+
+    /// Approximate heap bytes held by retained interactions
+    pub fn approx_memory_bytes(&self) -> usize {
+        self.interactions.capacity() * std::mem::size_of::<Interaction>()
+    }
+
     /// Get all interactions
     pub fn all(&self) -> &[Interaction] {
         &self.interactions
@@ -327,21 +544,31 @@ impl InteractionHistory {
     }
 
     /// Mean quality of recent interactions
+    ///
+    /// O(1) when `count` covers the whole history, otherwise O(count).
     pub fn mean_quality(&self, count: usize) -> Score {
         let recent = self.recent(count);
         if recent.is_empty() {
             return Score::HALF;
         }
+        if recent.len() == self.interactions.len() {
+            return Score::new(self.quality_mean);
+        }
         let sum: f64 = recent.iter().map(|i| i.quality.value()).sum();
         Score::new(sum / recent.len() as f64)
     }
 
     /// Variance of quality scores
+    ///
+    /// O(1) when `count` covers the whole history, otherwise O(count).
     pub fn quality_variance(&self, count: usize) -> f64 {
         let recent = self.recent(count);
         if recent.len() < 2 {
             return 0.0;
         }
+        if recent.len() == self.interactions.len() {
+            return self.quality_m2 / recent.len() as f64;
+        }
 
         let mean = self.mean_quality(count).value();
         let sum_sq: f64 = recent
@@ -358,6 +585,15 @@ impl InteractionHistory {
         let partners: HashSet<_> = recent.iter().map(|i| i.responder).collect();
         partners.len()
     }
+
+    /// Interactions per partner in recent history
+    pub fn partner_counts(&self, count: usize) -> HashMap<NodeId, usize> {
+        let mut counts = HashMap::new();
+        for interaction in self.recent(count) {
+            *counts.entry(interaction.responder).or_default() += 1;
+        }
+        counts
+    }
 }
 
 #[cfg(test)]
@@ -382,6 +618,100 @@ mod tests {
         assert!(q.value() > 0.3 && q.value() < 0.7);
     }
 
+    #[test]
+    fn test_feedback_custom_weights() {
+        // Helpful and accurate, but slow
+        let feedback = Feedback::new(5, 5, 4, 1, true);
+
+        let default = feedback.compute_quality();
+        assert_eq!(default, feedback.compute_quality_with(&FeedbackWeights::default()));
+
+        let timeliness_heavy = FeedbackWeights::new(0.1, 0.1, 0.1, 0.7).unwrap();
+        let weighted = feedback.compute_quality_with(&timeliness_heavy);
+        assert!(weighted < default, "{weighted:?} vs {default:?}");
+
+        // Uniform ratings score the same under any valid weighting
+        let neutral = Feedback::neutral();
+        let diff = neutral.compute_quality().value()
+            - neutral.compute_quality_with(&timeliness_heavy).value();
+        assert!(diff.abs() < 1e-9);
+
+        // Weights must be non-negative and sum to 1
+        assert!(FeedbackWeights::new(0.5, 0.5, 0.5, 0.5).is_none());
+        assert!(FeedbackWeights::new(1.2, -0.2, 0.0, 0.0).is_none());
+        assert!(FeedbackWeights::new(f64::NAN, 0.0, 0.0, 1.0).is_none());
+
+        // Deserializing goes through the same check
+        let json = serde_json::to_string(&timeliness_heavy).unwrap();
+        let parsed: FeedbackWeights = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, timeliness_heavy);
+        let skewed = r#"{"helpfulness":0.5,"accuracy":0.5,"relevance":0.5,"timeliness":0.5}"#;
+        assert!(serde_json::from_str::<FeedbackWeights>(skewed).is_err());
+    }
+
+    #[test]
+    fn test_smooth_mapping_bounds_reuse_jump() {
+        let weights = FeedbackWeights::default();
+        let quality = |r: u8, reuse: bool, mapping| {
+            Feedback::new(r, r, r, r, reuse).compute_quality_mapped(&weights, mapping).value()
+        };
+        let jump = |r, mapping| quality(r, true, mapping) - quality(r, false, mapping);
+
+        // The multiplier's jump grows with the ratings; the smooth one does not
+        let multiplier_jumps: Vec<f64> =
+            (1..=5).map(|r| jump(r, QualityMapping::Multiplier)).collect();
+        assert!(multiplier_jumps.windows(2).all(|w| w[1] > w[0]));
+        assert!(multiplier_jumps[4] > 2.0 * REUSE_WEIGHT);
+        for r in 1..=5 {
+            assert!((jump(r, QualityMapping::Smooth) - REUSE_WEIGHT).abs() < 1e-9);
+        }
+
+        // Smooth quality spans [0, 1] and never drops as any rating rises
+        assert!(quality(1, false, QualityMapping::Smooth).abs() < 1e-9);
+        assert!((quality(5, true, QualityMapping::Smooth) - 1.0).abs() < 1e-9);
+        for reuse in [false, true] {
+            for r in 1..5 {
+                let base = Feedback::new(3, 3, r, 3, reuse);
+                let raised = Feedback::new(3, 3, r + 1, 3, reuse);
+                assert!(
+                    raised.compute_quality_mapped(&weights, QualityMapping::Smooth)
+                        > base.compute_quality_mapped(&weights, QualityMapping::Smooth)
+                );
+            }
+        }
+
+        // The default mapping is unchanged
+        let feedback = Feedback::new(4, 5, 3, 2, true);
+        assert_eq!(
+            feedback.compute_quality(),
+            feedback.compute_quality_mapped(&weights, QualityMapping::Multiplier)
+        );
+    }
+
+    #[test]
+    fn test_feedback_validation() {
+        assert_eq!(Feedback::perfect().validate(), Ok(()));
+        assert_eq!(Feedback::poor().validate(), Ok(()));
+        assert_eq!(Feedback::new(4, 5, 3, 2, false).validate(), Ok(()));
+
+        assert_eq!(
+            Feedback::new(5, 5, 5, 5, false).validate(),
+            Err(FeedbackError::InconsistentReuse)
+        );
+        assert_eq!(
+            Feedback::new(1, 1, 1, 1, true).validate(),
+            Err(FeedbackError::InconsistentReuse)
+        );
+
+        // Fields set directly bypass the constructor's clamp
+        let mut feedback = Feedback::neutral();
+        feedback.accuracy = 9;
+        assert_eq!(
+            feedback.validate(),
+            Err(FeedbackError::RatingOutOfRange { field: "accuracy", value: 9 })
+        );
+    }
+
     #[test]
     fn test_tone_computation() {
         let positive = ToneSignals::positive();
@@ -397,6 +727,21 @@ mod tests {
         assert!(tone.value().abs() < 0.3);
     }
 
+    #[test]
+    fn test_from_feedback_stores_tone() {
+        let interaction = Interaction::from_feedback(
+            NodeId::from_index(1),
+            NodeId::from_index(2),
+            None,
+            &Feedback::perfect(),
+            &ToneSignals::negative(),
+            1.0,
+        );
+
+        assert!(interaction.quality.value() > 0.9);
+        assert_eq!(interaction.tone, ToneSignals::negative().compute_tone());
+    }
+
     #[test]
     fn test_interaction_history() {
         let mut history = InteractionHistory::with_max_size(10);
@@ -417,6 +762,40 @@ mod tests {
         assert!(recent[0].quality.value() > 0.8);
     }
 
+    #[test]
+    fn test_running_quality_stats_match_slice() {
+        use crate::math::variance;
+
+        let sequences: Vec<Vec<f64>> = vec![
+            vec![0.5],
+            vec![0.1, 0.9, 0.1, 0.9, 0.1],
+            (0..25).map(|i| (i as f64 * 0.37).sin().abs()).collect(),
+            (0..40).map(|i| if i < 20 { 0.95 } else { 0.05 }).collect(),
+            vec![0.7; 30],
+        ];
+
+        for qualities in sequences {
+            let mut history = InteractionHistory::with_max_size(10);
+            for (i, &q) in qualities.iter().enumerate() {
+                history.add(
+                    Interaction::new(NodeId::from_index(1), NodeId::from_index(2))
+                        .with_outcome(Score::new(q), SignedScore::ZERO),
+                );
+
+                // Compare against a recompute over the retained window
+                let start = (i + 1).saturating_sub(10);
+                let window = &qualities[start..=i];
+                let mean = window.iter().sum::<f64>() / window.len() as f64;
+                let expected_var = if window.len() < 2 { 0.0 } else { variance(window) };
+
+                let full = history.len();
+                assert!((history.mean_quality(full).value() - mean).abs() < 1e-9);
+                assert!((history.quality_variance(full) - expected_var).abs() < 1e-9);
+                assert!((history.quality_variance(100) - expected_var).abs() < 1e-9);
+            }
+        }
+    }
+
     #[test]
     fn test_unique_partners() {
         let mut history = InteractionHistory::new();
@@ -32,6 +32,8 @@
 //! ## Modules
 //!
 //! - [`constants`] - Protocol parameters
+//! - [`clock`] - Wall and virtual time sources
+//! - [`params`] - Runtime protocol settings
 //! - [`types`] - Core types (NodeId, Score, Weight, etc.)
 //! - [`math`] - Mathematical functions (sigmoid, multipliers, etc.)
 //! - [`node`] - Node structure and state
@@ -42,12 +44,15 @@
 //! - [`defense`] - Defense signals and threat beliefs
 //! - [`routing`] - Task routing
 //! - [`workflow`] - Workflow execution
+//! - [`scheduler`] - Per-node task backlog
 //! - [`handoff`] - Handoff protocol
 //! - [`convergence`] - Convergence tracking
 //! - [`detection`] - Adversary detection
 
+pub mod clock;
 pub mod constants;
 pub mod math;
+pub mod params;
 pub mod types;
 
 // Phase 2 modules (to be implemented)
@@ -66,6 +71,7 @@ pub mod detection;
 // Phase 5 modules
 pub mod handoff;
 pub mod routing;
+pub mod scheduler;
 pub mod workflow;
 
 // Phase 6 modules
@@ -130,15 +130,37 @@ pub fn time_decay(age_ms: u64, half_life_ms: u64) -> f64 {
 // AGGREGATION FUNCTIONS
 // =============================================================================
 
+/// Replace NaN or infinite values with a fallback
+pub fn finite_or(value: f64, fallback: f64) -> f64 {
+    if value.is_finite() {
+        value
+    } else {
+        fallback
+    }
+}
+
+/// Mean of the finite values, or None if there are none
+///
+/// NaN and infinite values are skipped so one bad input cannot poison the
+/// aggregate.
+pub fn finite_mean(values: impl Iterator<Item = f64>) -> Option<f64> {
+    let (sum, count) = values
+        .filter(|v| v.is_finite())
+        .fold((0.0, 0usize), |(sum, count), v| (sum + v, count + 1));
+    (count > 0).then(|| sum / count as f64)
+}
+
 /// Weighted mean of values
 ///
-/// Returns Σ(value × weight) / Σ(weight)
+/// Returns Σ(value × weight) / Σ(weight), skipping pairs where either is
+/// NaN or infinite
 pub fn weighted_mean(values: &[f64], weights: &[f64]) -> f64 {
     debug_assert_eq!(values.len(), weights.len());
 
     let (sum, weight_sum) = values
         .iter()
         .zip(weights.iter())
+        .filter(|(v, w)| v.is_finite() && w.is_finite())
         .fold((0.0, 0.0), |(sum, w_sum), (&v, &w)| {
             (sum + v * w, w_sum + w)
         });
@@ -150,15 +172,16 @@ pub fn weighted_mean(values: &[f64], weights: &[f64]) -> f64 {
     sum / weight_sum
 }
 
-/// Variance of a slice of values
+/// Variance of a slice of values, skipping NaN and infinite values
 pub fn variance(values: &[f64]) -> f64 {
-    if values.is_empty() {
+    let Some(mean) = finite_mean(values.iter().copied()) else {
         return 0.0;
-    }
+    };
 
-    let mean = values.iter().sum::<f64>() / values.len() as f64;
-    let sum_sq_diff: f64 = values.iter().map(|&x| (x - mean).powi(2)).sum();
-    sum_sq_diff / values.len() as f64
+    let finite = values.iter().filter(|x| x.is_finite());
+    let count = finite.clone().count();
+    let sum_sq_diff: f64 = finite.map(|&x| (x - mean).powi(2)).sum();
+    sum_sq_diff / count as f64
 }
 
 /// Standard deviation
@@ -166,6 +189,22 @@ pub fn std_dev(values: &[f64]) -> f64 {
     variance(values).sqrt()
 }
 
+/// Percentile of values sorted ascending, for p in [0, 1]
+///
+/// Interpolates linearly between the closest ranks at rank p × (n - 1).
+/// Returns 0 for no values.
+pub fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let Some(&last) = sorted.last() else {
+        return 0.0;
+    };
+    let rank = p.clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+    let below = rank.floor() as usize;
+    match sorted.get(below + 1) {
+        Some(&above) => sorted[below] + (above - sorted[below]) * (rank - below as f64),
+        None => last,
+    }
+}
+
 // =============================================================================
 // REINFORCEMENT CALCULATION
 // =============================================================================
@@ -351,6 +390,33 @@ mod tests {
         assert!(variance(&same) < 0.001);
     }
 
+    #[test]
+    fn test_percentile() {
+        let values: Vec<f64> = (0..=100).map(|i| i as f64 / 100.0).collect();
+        assert!((percentile(&values, 0.1) - 0.1).abs() < 1e-9);
+        assert!((percentile(&values, 0.5) - 0.5).abs() < 1e-9);
+        assert!((percentile(&values, 0.9) - 0.9).abs() < 1e-9);
+
+        // Between ranks the value is interpolated
+        assert!((percentile(&[0.2, 0.4, 0.6, 0.8], 0.5) - 0.5).abs() < 1e-9);
+        assert_eq!(percentile(&[0.2, 0.4], 1.0), 0.4);
+        assert_eq!(percentile(&[0.3], 0.9), 0.3);
+        assert_eq!(percentile(&[], 0.5), 0.0);
+    }
+
+    #[test]
+    fn test_non_finite_inputs_skipped() {
+        let values = vec![1.0, f64::NAN, 3.0, f64::INFINITY];
+        assert_eq!(finite_mean(values.iter().copied()), Some(2.0));
+        assert_eq!(finite_mean([f64::NAN].into_iter()), None);
+        assert!((variance(&values) - 1.0).abs() < 1e-12);
+        assert_eq!(variance(&[f64::NAN]), 0.0);
+
+        let weights = vec![1.0, 1.0, f64::NAN, 1.0];
+        assert!((weighted_mean(&values, &weights) - 1.0).abs() < 1e-12);
+        assert_eq!(finite_or(f64::NEG_INFINITY, 0.5), 0.5);
+    }
+
     #[test]
     fn test_bayesian_update() {
         let belief = Score::ZERO;
@@ -1,15 +1,29 @@
 //! Node structure representing an agent in the Symbiont network.
 
-use crate::capability::{Capability, CapabilityState};
+use crate::capability::{Capability, CapabilityCategory, CapabilityState};
 use crate::connection::{Connection, ConnectionStats};
 use crate::constants::{
-    DIVERSITY_THRESHOLD, IDLE_THRESHOLD, LAMBDA, PRIMING_DECAY, PROBATION_COUNT,
-    PROBATION_THRESHOLD, SWIFT_TRUST_BASE,
+    ACTION_THRESHOLD, AFFIRMATION_DECAY, CAPABILITY_QUALITY_DECAY, DIVERSITY_MIN_SAMPLE,
+    DIVERSITY_THRESHOLD,
+    ESTABLISHED_MIN_INTERACTIONS, ESTABLISHED_MIN_TRUST, ESTABLISHED_TRUST_WINDOW, HUB_MIN_PARTNERS,
+    HUB_MIN_TRUST, IDLE_THRESHOLD, INTERACTION_HISTORY_SIZE, ISOLATION_MAX_PARTNERS,
+    ISOLATION_MAX_VOLUME, ISOLATION_TRUST_CAP, LAMBDA, LATENCY_BASE_MS, LATENCY_EXPERIENCE,
+    LATENCY_LOAD_PENALTY, LOAD_DECAY, LOAD_PER_INTERACTION, LOAD_PER_TASK, MAX_PROBATION_FAILURES,
+    PRIMING_DECAY, PROBATION_COUNT, PROBATION_THRESHOLD, QUALITY_ANOMALY_CAP,
+    QUALITY_ANOMALY_MIN_HISTORY, QUALITY_ANOMALY_VARIANCE, SWIFT_TRUST_BASE, SWIFT_TRUST_DECAY,
+    SWIFT_TRUST_FLOOR, SWIFT_TRUST_MIN_INTERACTIONS, TASKS_PER_TICK, THREAT_BELIEF_DECAY,
+    TRUST_HISTORY_SIZE, TRUST_VOLATILITY_THRESHOLD, TRUST_VOLATILITY_WINDOW, VOUCH_PENALTY,
+    VOUCH_TRUST_SHARE,
 };
 use crate::interaction::{Interaction, InteractionHistory};
-use crate::types::{CapabilityId, Hash, NodeId, Score, SignedScore, Timestamp};
+use crate::math::variance;
+use crate::params::ProtocolParams;
+use crate::routing::Task;
+use crate::scheduler::{EnqueueError, QueuedTask, TaskQueue};
+use crate::trust::{affirmation_mean, update_confidence, AffirmationStore};
+use crate::types::{CapabilityId, Hash, NodeId, Score, SignedScore, TaskId, Timestamp};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 
 /// Node status in the network
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -33,6 +47,38 @@ impl NodeStatus {
     }
 }
 
+/// Why a node's status changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum StatusReason {
+    /// Probation passed with enough quality
+    ProbationPassed,
+    /// Too many probation windows failed
+    ProbationFailed,
+    /// Trust sustained over enough interactions
+    SustainedTrust,
+    /// Enough partners and trust to act as a hub
+    HubConnectivity,
+    /// Trust fell below what the status requires
+    TrustDeclined,
+    /// Active partners fell below the hub minimum
+    ConnectivityLost,
+    /// Expelled by the network
+    Expelled,
+}
+
+/// A recorded change of node status
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusChange {
+    /// Tick the change happened at
+    pub tick: u64,
+    /// Status before the change
+    pub from: NodeStatus,
+    /// Status after the change
+    pub to: NodeStatus,
+    /// What caused it
+    pub reason: StatusReason,
+}
+
 /// Threat belief about another node
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ThreatBelief {
@@ -59,15 +105,25 @@ impl ThreatBelief {
 
     /// Update belief with new evidence (Bayesian update)
     pub fn update(&mut self, weight: f64, evidence: Option<Hash>) {
+        self.update_at(weight, evidence, Timestamp::now());
+    }
+
+    /// Update belief with new evidence observed at `now`
+    pub fn update_at(&mut self, weight: f64, evidence: Option<Hash>, now: Timestamp) {
         // belief_new = belief_old + weight × (1 - belief_old)
         let new_level = self.level.value() + weight * (1.0 - self.level.value());
         self.level = Score::new(new_level);
-        self.updated = Timestamp::now();
+        self.updated = now;
 
         if let Some(hash) = evidence {
             self.evidence.push(hash);
         }
     }
+
+    /// Fade belief absent new evidence: belief_new = belief_old × rate
+    pub fn decay(&mut self, rate: f64) {
+        self.level = Score::new(self.level.value() * rate);
+    }
 }
 
 /// Type of threat
@@ -83,6 +139,30 @@ pub enum ThreatType {
     QualityFraud,
     /// Strategic adversary (builds trust then defects)
     Strategic,
+    /// Isolated or starved of partners (informational, not an attack)
+    Isolation,
+}
+
+impl ThreatType {
+    /// Whether this type only describes a node's situation
+    ///
+    /// Informational detections cap trust but never count as threats or
+    /// trigger defense signals.
+    pub fn is_informational(&self) -> bool {
+        matches!(self, ThreatType::Isolation)
+    }
+
+    /// Short snake_case name, as recorded in metrics
+    pub fn name(&self) -> &'static str {
+        match self {
+            ThreatType::Cheating => "cheating",
+            ThreatType::Sybil => "sybil",
+            ThreatType::Collusion => "collusion",
+            ThreatType::QualityFraud => "quality_fraud",
+            ThreatType::Strategic => "strategic",
+            ThreatType::Isolation => "isolation",
+        }
+    }
 }
 
 /// Defense state of a node
@@ -97,10 +177,12 @@ pub enum DefenseState {
 }
 
 /// Flags that can be set on a node
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum NodeFlag {
     /// Low interaction diversity
     LowDiversity,
+    /// Few partners, little volume and no two-way exchanges
+    Isolated,
     /// Suspiciously high quality variance
     QualityAnomaly,
     /// Rapid trust changes
@@ -118,8 +200,15 @@ pub struct Node {
     pub trust: Score,
     /// Trust cap (can be reduced for various reasons)
     pub trust_cap: Score,
+    /// Recently computed trust values (oldest first, at most TRUST_HISTORY_SIZE)
+    pub trust_history: VecDeque<Score>,
     /// Self-confidence score
     pub confidence: Score,
+    /// Recently received affirmations feeding social proof
+    #[serde(default)]
+    pub recent_affirmations: AffirmationStore,
+    /// Number of affirmations received
+    pub affirmation_count: u32,
     /// Priming level (alertness)
     pub priming: Score,
     /// Connections to other nodes
@@ -131,6 +220,7 @@ pub struct Node {
     /// Current quality score (aggregate)
     pub quality_score: Score,
     /// Active flags
+    #[serde(with = "sorted_set")]
     pub flags: HashSet<NodeFlag>,
     /// Defense state
     pub defense_state: DefenseState,
@@ -138,10 +228,46 @@ pub struct Node {
     pub history: InteractionHistory,
     /// Probation interaction count
     pub probation_count: u32,
+    /// Number of failed probation windows
+    pub probation_failures: u32,
+    /// Node that vouched for this one when it joined
+    pub vouched_by: Option<NodeId>,
+    /// Nodes this node has vouched for
+    #[serde(with = "sorted_set")]
+    pub vouchees: HashSet<NodeId>,
+    /// Persistent multiplier on the trust cap from failed vouches
+    pub vouch_cap_multiplier: f64,
+    /// Interactions this node has initiated
+    pub initiated_count: u32,
+    /// Interactions other nodes have initiated with this one
+    #[serde(default)]
+    pub received_count: u32,
+    /// Other nodes this one could interact with, when the network knows
+    #[serde(default)]
+    pub reachable_partners: Option<usize>,
+    /// Multiplier on the trust cap that decays while the node is unproven
+    pub swift_trust_multiplier: f64,
+    /// Status changes, oldest first
+    #[serde(default)]
+    pub status_history: Vec<StatusChange>,
+    /// Status changes not yet taken by [`Node::take_status_changes`]
+    #[serde(skip)]
+    pub pending_status_changes: Vec<StatusChange>,
+    /// Latest tick the node has seen, used to stamp status changes
+    #[serde(default)]
+    pub current_tick: u64,
+    /// Time set by the driving clock; the wall clock is read when unset
+    #[serde(default)]
+    pub clock_time: Option<Timestamp>,
     /// Creation timestamp
     pub created: Timestamp,
     /// Current load (0 = idle, 1 = maxed)
     pub load: Score,
+    /// Tasks accepted but not yet started
+    #[serde(default)]
+    pub task_queue: TaskQueue,
+    /// Protocol evaluation settings
+    pub params: ProtocolParams,
 }
 
 impl Node {
@@ -152,7 +278,10 @@ impl Node {
             status: NodeStatus::Probationary,
             trust: Score::new(SWIFT_TRUST_BASE),
             trust_cap: Score::ONE,
+            trust_history: VecDeque::with_capacity(TRUST_HISTORY_SIZE),
             confidence: Score::HALF,
+            recent_affirmations: AffirmationStore::default(),
+            affirmation_count: 0,
             priming: Score::ZERO,
             connections: HashMap::new(),
             capabilities: HashMap::new(),
@@ -162,8 +291,22 @@ impl Node {
             defense_state: DefenseState::Normal,
             history: InteractionHistory::new(),
             probation_count: 0,
+            probation_failures: 0,
+            vouched_by: None,
+            vouchees: HashSet::new(),
+            vouch_cap_multiplier: 1.0,
+            initiated_count: 0,
+            received_count: 0,
+            reachable_partners: None,
+            swift_trust_multiplier: 1.0,
+            status_history: Vec::new(),
+            pending_status_changes: Vec::new(),
+            current_tick: 0,
+            clock_time: None,
             created: Timestamp::now(),
             load: Score::ZERO,
+            task_queue: TaskQueue::default(),
+            params: ProtocolParams::default(),
         }
     }
 
@@ -176,6 +319,18 @@ impl Node {
         node
     }
 
+    /// Set how many interactions the node's history retains
+    ///
+    /// Interactions already recorded are kept, up to the new size.
+    pub fn with_history_size(mut self, size: usize) -> Self {
+        let mut history = InteractionHistory::with_max_size(size);
+        for interaction in self.history.all().iter().rev() {
+            history.add(interaction.clone());
+        }
+        self.history = history;
+        self
+    }
+
     /// Add a capability to this node
     pub fn add_capability(&mut self, capability: Capability) {
         let state = CapabilityState::new(capability.clone());
@@ -198,11 +353,55 @@ impl Node {
             .unwrap_or(Score::ZERO)
     }
 
+    /// Quality of a capability shrunk toward neutral by how little it was used
+    /// (0 if the node lacks it)
+    pub fn effective_capability_quality(&self, cap_id: CapabilityId) -> Score {
+        self.capabilities
+            .get(&cap_id)
+            .map(|s| s.effective_quality())
+            .unwrap_or(Score::ZERO)
+    }
+
+    /// Fraction of this node's capabilities in a category (0 with none)
+    pub fn category_share(&self, category: CapabilityCategory) -> f64 {
+        if self.capabilities.is_empty() {
+            return 0.0;
+        }
+        let matching = self
+            .capabilities
+            .values()
+            .filter(|s| s.capability.category == category)
+            .count();
+        matching as f64 / self.capabilities.len() as f64
+    }
+
+    /// The category holding a strict majority of this node's capabilities
+    ///
+    /// None for generalists with no dominant category.
+    pub fn specialization(&self) -> Option<CapabilityCategory> {
+        let mut counts: HashMap<CapabilityCategory, usize> = HashMap::new();
+        for state in self.capabilities.values() {
+            *counts.entry(state.capability.category).or_default() += 1;
+        }
+        counts
+            .into_iter()
+            .find(|&(_, count)| 2 * count > self.capabilities.len())
+            .map(|(category, _)| category)
+    }
+
+    /// Current time: the driving clock's if set, otherwise the wall clock
+    pub fn now(&self) -> Timestamp {
+        self.clock_time.unwrap_or_else(Timestamp::now)
+    }
+
     /// Get or create a connection to another node
     pub fn get_or_create_connection(&mut self, partner_id: NodeId) -> &mut Connection {
-        self.connections
-            .entry(partner_id)
-            .or_insert_with(|| Connection::new(partner_id))
+        let now = self.now();
+        self.connections.entry(partner_id).or_insert_with(|| {
+            let mut conn = Connection::new(partner_id);
+            conn.last_active = now;
+            conn
+        })
     }
 
     /// Get connection to a specific node
@@ -236,21 +435,30 @@ impl Node {
         capability: Option<CapabilityId>,
     ) {
         let threat_level = self.get_threat_level(&partner_id);
+        let params = self.params.clone();
+        let now = self.now();
         let conn = self.get_or_create_connection(partner_id);
 
-        conn.process_interaction(
+        conn.process_interaction_with(
             volume,
             exchange_in,
             exchange_out,
             quality,
             tone,
             threat_level,
+            &params,
         );
+        conn.last_active = now;
 
-        // Update per-capability quality if this interaction involved a specific capability
+        // The rated work was ours, so it moves our own capability quality
+        // rather than our view of the partner's
         if let Some(cap_id) = capability {
             let conn = self.get_or_create_connection(partner_id);
-            conn.update_capability_quality(cap_id, quality);
+            conn.update_capability_reciprocity(cap_id, exchange_in, exchange_out, quality);
+
+            if let Some(cap_state) = self.capabilities.get_mut(&cap_id) {
+                cap_state.record_usage(quality, LAMBDA);
+            }
         }
 
         // Record in history
@@ -259,6 +467,7 @@ impl Node {
             .with_outcome(quality, tone)
             .with_exchange(exchange_in, exchange_out);
         self.history.add(interaction);
+        self.initiated_count = self.initiated_count.saturating_add(1);
 
         // Check for probation progress
         if self.status == NodeStatus::Probationary {
@@ -280,22 +489,35 @@ impl Node {
         capability: Option<CapabilityId>,
     ) {
         let threat_level = self.get_threat_level(&initiator_id);
+        let params = self.params.clone();
+        let now = self.now();
         let conn = self.get_or_create_connection(initiator_id);
 
         // For incoming, exchange direction is flipped
-        conn.process_interaction(
+        conn.process_interaction_with(
             volume,
             exchange_out, // We received what they gave
             exchange_in,  // We gave what they received
             quality,
             tone,
             threat_level,
+            &params,
         );
+        conn.last_active = now;
 
-        // Update our capability quality if we used it
+        // Serving the request adds load
+        self.load = Score::new(self.load.value() + LOAD_PER_INTERACTION);
+
+        // Note capability use and load; the quality rated is the initiator's,
+        // so it moves our view of them and leaves our own capability quality alone
         if let Some(cap_id) = capability {
+            let conn = self.get_or_create_connection(initiator_id);
+            conn.update_capability_quality(cap_id, quality);
+            conn.update_capability_reciprocity(cap_id, exchange_out, exchange_in, quality);
+
             if let Some(cap_state) = self.capabilities.get_mut(&cap_id) {
-                cap_state.record_usage(quality, LAMBDA);
+                cap_state.mark_used();
+                cap_state.add_load(LOAD_PER_INTERACTION);
             }
         }
 
@@ -305,6 +527,7 @@ impl Node {
             .with_outcome(quality, tone)
             .with_exchange(exchange_out, exchange_in);
         self.history.add(interaction);
+        self.received_count = self.received_count.saturating_add(1);
     }
 
     /// Check and update probation status
@@ -318,17 +541,125 @@ impl Node {
 
             if mean_quality.value() >= PROBATION_THRESHOLD {
                 // Passed probation
-                self.status = NodeStatus::Member;
+                self.set_status(NodeStatus::Member, StatusReason::ProbationPassed);
                 self.trust = Score::new((self.trust.value() * 1.5).min(0.8));
             } else {
                 // Failed probation - extend or expel
-                self.trust = Score::new(self.trust.value() * 0.8);
+                self.probation_failures += 1;
                 self.probation_count = 0; // Reset for another try
-                // TODO: track failure count for expulsion
+
+                if self.probation_failures >= MAX_PROBATION_FAILURES {
+                    self.expel_for(StatusReason::ProbationFailed);
+                } else {
+                    self.trust = Score::new(self.trust.value() * 0.8);
+                }
+            }
+        }
+    }
+
+    /// Expel this node from the network
+    pub fn expel(&mut self) {
+        self.expel_for(StatusReason::Expelled);
+    }
+
+    fn expel_for(&mut self, reason: StatusReason) {
+        self.set_status(NodeStatus::Expelled, reason);
+        self.trust = Score::ZERO;
+        self.trust_cap = Score::ZERO;
+    }
+
+    /// Move to a new status, recording the change at the current tick
+    pub fn set_status(&mut self, to: NodeStatus, reason: StatusReason) {
+        if self.status == to {
+            return;
+        }
+        let change = StatusChange {
+            tick: self.current_tick,
+            from: self.status,
+            to,
+            reason,
+        };
+        self.status_history.push(change);
+        self.pending_status_changes.push(change);
+        self.status = to;
+    }
+
+    /// Drain the status changes made since the last call
+    pub fn take_status_changes(&mut self) -> Vec<StatusChange> {
+        std::mem::take(&mut self.pending_status_changes)
+    }
+
+    /// Total interactions over all connections, in both directions
+    pub fn interaction_count(&self) -> u64 {
+        self.connections.values().map(|c| c.count as u64).sum()
+    }
+
+    /// Promote or demote between Member, Established and Hub
+    ///
+    /// A member becomes established once its last ESTABLISHED_TRUST_WINDOW
+    /// trust values all reach ESTABLISHED_MIN_TRUST over at least
+    /// ESTABLISHED_MIN_INTERACTIONS interactions. An established node becomes
+    /// a hub with HUB_MIN_PARTNERS active partners and HUB_MIN_TRUST trust.
+    /// Each drops back a step when its condition lapses.
+    pub fn check_promotion(&mut self) {
+        let trust = self.trust.value();
+        let sustained = self.trust_history.len() >= ESTABLISHED_TRUST_WINDOW
+            && self
+                .trust_history
+                .iter()
+                .rev()
+                .take(ESTABLISHED_TRUST_WINDOW)
+                .all(|t| t.value() >= ESTABLISHED_MIN_TRUST);
+        let well_connected = self.active_partner_count() >= HUB_MIN_PARTNERS;
+
+        match self.status {
+            NodeStatus::Member
+                if sustained && self.interaction_count() >= ESTABLISHED_MIN_INTERACTIONS =>
+            {
+                self.set_status(NodeStatus::Established, StatusReason::SustainedTrust);
+            }
+            NodeStatus::Established if trust < ESTABLISHED_MIN_TRUST => {
+                self.set_status(NodeStatus::Member, StatusReason::TrustDeclined);
+            }
+            NodeStatus::Established if well_connected && trust >= HUB_MIN_TRUST => {
+                self.set_status(NodeStatus::Hub, StatusReason::HubConnectivity);
+            }
+            NodeStatus::Hub if trust < HUB_MIN_TRUST => {
+                self.set_status(NodeStatus::Established, StatusReason::TrustDeclined);
             }
+            NodeStatus::Hub if !well_connected => {
+                self.set_status(NodeStatus::Established, StatusReason::ConnectivityLost);
+            }
+            _ => {}
         }
     }
 
+    /// Vouch for a new node, staking part of our own trust on its behavior
+    pub fn vouch_for(&mut self, target: NodeId) {
+        if target != self.id {
+            self.vouchees.insert(target);
+        }
+    }
+
+    /// Accept a vouch from an existing member, raising initial trust
+    ///
+    /// T_init = T_swift + VOUCH_TRUST_SHARE × max(T_voucher - T_swift, 0)
+    pub fn accept_vouch(&mut self, voucher: NodeId, voucher_trust: Score) {
+        let surplus = (voucher_trust.value() - SWIFT_TRUST_BASE).max(0.0);
+        let boosted = SWIFT_TRUST_BASE + VOUCH_TRUST_SHARE * surplus;
+        self.vouched_by = Some(voucher);
+        self.trust = Score::new(self.trust.value().max(boosted));
+    }
+
+    /// Penalize this node for vouching for a node that defected
+    pub fn apply_vouch_penalty(&mut self, vouchee: NodeId) {
+        if !self.vouchees.remove(&vouchee) {
+            return;
+        }
+        self.vouch_cap_multiplier *= VOUCH_PENALTY;
+        self.trust_cap = self.trust_cap * VOUCH_PENALTY;
+    }
+
     /// Update threat belief about a node
     pub fn update_threat_belief(
         &mut self,
@@ -337,30 +668,136 @@ impl Node {
         weight: f64,
         evidence: Option<Hash>,
     ) {
+        let now = self.now();
         let belief = self
             .threat_beliefs
             .entry(target)
             .or_insert_with(|| ThreatBelief::new(threat_type, Score::ZERO));
 
-        belief.update(weight, evidence);
+        belief.update_at(weight, evidence, now);
     }
 
     /// Compute current diversity score
+    ///
+    /// D = N_eff / min(W, max(n, DIVERSITY_MIN_SAMPLE), P), where N_eff is the
+    /// effective partner count over the last W interactions under the node's
+    /// diversity measure, W the diversity window (the history size unless
+    /// configured), n the interactions recorded so far and P the reachable
+    /// partners, when known. Partners are counted from both directions.
     pub fn diversity_score(&self) -> Score {
-        let unique = self.history.unique_partners(100);
-        Score::new(unique as f64 / 100.0)
+        let window = self.params.diversity_window.unwrap_or_else(|| self.history.max_size());
+        let mut partners: HashMap<NodeId, usize> = HashMap::new();
+        for interaction in self.history.recent(window) {
+            *partners.entry(interaction.partner_of(self.id)).or_default() += 1;
+        }
+        let denominator = window
+            .min(self.history.len().max(DIVERSITY_MIN_SAMPLE))
+            .min(self.reachable_partners.unwrap_or(usize::MAX))
+            .max(partners.len());
+        if denominator == 0 {
+            return Score::ZERO;
+        }
+        let counts: Vec<usize> = partners.into_values().collect();
+        let effective = self.params.diversity.effective_partners(&counts);
+        Score::new(effective / denominator as f64)
+    }
+
+    /// Number of partners this node has actually interacted with
+    pub fn active_partner_count(&self) -> usize {
+        self.connections.values().filter(|c| c.count > 0).count()
     }
 
-    /// Apply periodic decay to priming
+    /// Whether this node is isolated rather than part of a clique
+    ///
+    /// Isolated means at most ISOLATION_MAX_PARTNERS partners, under
+    /// ISOLATION_MAX_VOLUME of total volume, and no partner it has exchanged
+    /// with in both directions.
+    pub fn is_isolated(&self) -> bool {
+        if self.active_partner_count() > ISOLATION_MAX_PARTNERS {
+            return false;
+        }
+
+        let interactions = self.history.all();
+        let volume: f64 = interactions.iter().map(|i| i.volume).sum();
+        if volume >= ISOLATION_MAX_VOLUME {
+            return false;
+        }
+
+        let initiated_to: HashSet<_> = interactions
+            .iter()
+            .filter(|i| i.initiator == self.id)
+            .map(|i| i.responder)
+            .collect();
+        !interactions
+            .iter()
+            .any(|i| i.responder == self.id && initiated_to.contains(&i.initiator))
+    }
+
+    /// Apply periodic decay to priming and threat beliefs
+    ///
+    /// The node stands down to Normal once priming is low and no belief is
+    /// still above ACTION_THRESHOLD, so wrongly flagged partners can recover.
     pub fn decay_priming(&mut self) {
         self.priming = Score::new(self.priming.value() * PRIMING_DECAY);
+        for belief in self.threat_beliefs.values_mut() {
+            belief.decay(THREAT_BELIEF_DECAY);
+        }
 
-        // Update defense state based on priming
-        if self.priming.value() < 0.1 {
+        // Update defense state based on priming and remaining threats
+        let threatened = self
+            .threat_beliefs
+            .values()
+            .any(|b| b.level.value() > ACTION_THRESHOLD);
+        if self.priming.value() < 0.1 && !threatened {
             self.defense_state = DefenseState::Normal;
         }
     }
 
+    /// Decay per-connection priming (called each tick)
+    ///
+    /// π(t+1) = PRIMING_DECAY × π(t)
+    pub fn decay_connection_priming(&mut self) {
+        for conn in self.connections.values_mut() {
+            conn.decay_priming(PRIMING_DECAY);
+        }
+    }
+
+    /// Apply a batch of received affirmations as (affirmer trust, strength) pairs
+    ///
+    /// Confidence moves toward the trust-weighted mean strength, and the
+    /// batch joins the recent affirmations used for social proof.
+    pub fn receive_affirmations(&mut self, affirmations: &[(Score, Score)]) {
+        let Some(mean) = affirmation_mean(affirmations) else {
+            return;
+        };
+
+        self.confidence = update_confidence(self.confidence, mean);
+        self.recent_affirmations.record(affirmations);
+        self.affirmation_count += affirmations.len() as u32;
+    }
+
+    /// Fade recent affirmations (called each tick)
+    pub fn decay_affirmations(&mut self) {
+        self.recent_affirmations.decay(AFFIRMATION_DECAY);
+    }
+
+    /// Decay node and per-capability load (called each tick)
+    ///
+    /// load(t+1) = LOAD_DECAY × load(t)
+    pub fn decay_load(&mut self) {
+        self.load = Score::new(self.load.value() * LOAD_DECAY);
+        for cap_state in self.capabilities.values_mut() {
+            cap_state.decay_load(LOAD_DECAY);
+        }
+    }
+
+    /// Fade the quality of capabilities that have gone unused
+    pub fn decay_capability_quality(&mut self) {
+        for cap_state in self.capabilities.values_mut() {
+            cap_state.decay_quality(CAPABILITY_QUALITY_DECAY);
+        }
+    }
+
     /// Increase priming due to threat signal
     pub fn increase_priming(&mut self, boost: f64) {
         self.priming = Score::new((self.priming.value() + boost).min(1.0));
@@ -370,12 +807,13 @@ impl Node {
         }
     }
 
-    /// Apply decay to idle connections
+    /// Apply decay to connections idle longer than IDLE_THRESHOLD ms
     pub fn decay_idle_connections(&mut self) {
+        let now = self.now();
         let mut to_remove = Vec::new();
 
         for (id, conn) in self.connections.iter_mut() {
-            if conn.is_idle(IDLE_THRESHOLD) {
+            if conn.is_idle_at(IDLE_THRESHOLD, now) {
                 conn.apply_decay(1.0);
                 if conn.w.value() <= crate::constants::W_MIN {
                     to_remove.push(*id);
@@ -388,16 +826,135 @@ impl Node {
         }
     }
 
+    /// Set a newly computed trust value and retain it in the trust history
+    pub fn record_trust(&mut self, trust: Score) {
+        self.trust = trust;
+        if self.trust_history.len() == TRUST_HISTORY_SIZE {
+            self.trust_history.pop_front();
+        }
+        self.trust_history.push_back(trust);
+    }
+
+    /// Variance of the most recent `window` computed trust values
+    pub fn trust_variance(&self, window: usize) -> f64 {
+        let recent: Vec<f64> = self
+            .trust_history
+            .iter()
+            .rev()
+            .take(window)
+            .map(|t| t.value())
+            .collect();
+        variance(&recent)
+    }
+
+    /// Trust gained per tick over the most recent `window` computed values
+    ///
+    /// v = (T_last - T_first) / (k - 1) for the last k = min(window, len)
+    /// values; 0 with fewer than two.
+    pub fn trust_velocity(&self, window: usize) -> f64 {
+        let k = window.min(self.trust_history.len());
+        if k < 2 {
+            return 0.0;
+        }
+        let len = self.trust_history.len();
+        let first = self.trust_history[len - k].value();
+        let last = self.trust_history[len - 1].value();
+        (last - first) / (k - 1) as f64
+    }
+
+    /// Whether trust has stayed within `threshold` variance over `window` values
+    ///
+    /// Needs a full window of history; a node with less is not yet stable.
+    pub fn is_trust_stable(&self, window: usize, threshold: f64) -> bool {
+        self.trust_history.len() >= window && self.trust_variance(window) < threshold
+    }
+
+    /// Check and update trust volatility flag
+    pub fn check_trust_volatility(&mut self) {
+        if self.trust_history.len() < TRUST_VOLATILITY_WINDOW {
+            return;
+        }
+
+        if self.trust_variance(TRUST_VOLATILITY_WINDOW) > TRUST_VOLATILITY_THRESHOLD {
+            self.flags.insert(NodeFlag::TrustVolatility);
+        } else {
+            self.flags.remove(&NodeFlag::TrustVolatility);
+        }
+    }
+
+    /// Decay swift trust for nodes that haven't engaged (called each tick, before
+    /// `check_diversity`, which applies the multiplier to the trust cap)
+    ///
+    /// m(t+1) = max(SWIFT_TRUST_FLOOR, m(t) × SWIFT_TRUST_DECAY) while the node is
+    /// probationary with fewer than SWIFT_TRUST_MIN_INTERACTIONS interactions,
+    /// initiated or received; otherwise it recovers as
+    /// m(t+1) = min(1, m(t) / SWIFT_TRUST_DECAY)
+    pub fn decay_swift_trust(&mut self) {
+        let engaged = self.initiated_count.saturating_add(self.received_count)
+            >= SWIFT_TRUST_MIN_INTERACTIONS;
+        if self.status == NodeStatus::Probationary && !engaged {
+            let decayed = self.swift_trust_multiplier * SWIFT_TRUST_DECAY;
+            self.swift_trust_multiplier = decayed.max(SWIFT_TRUST_FLOOR);
+        } else {
+            let recovered = self.swift_trust_multiplier / SWIFT_TRUST_DECAY;
+            self.swift_trust_multiplier = recovered.min(1.0);
+        }
+    }
+
     /// Check and update diversity flag
     pub fn check_diversity(&mut self) {
+        // Expelled nodes keep their zeroed cap
+        if !self.status.is_active() {
+            return;
+        }
+
         let diversity = self.diversity_score();
 
-        if diversity.value() < DIVERSITY_THRESHOLD {
-            self.flags.insert(NodeFlag::LowDiversity);
-            self.trust_cap = Score::new(0.7);
+        let isolated = self.is_isolated();
+        let low_diversity = !isolated && diversity.value() < DIVERSITY_THRESHOLD;
+        let flags = [
+            (NodeFlag::Isolated, isolated),
+            (NodeFlag::LowDiversity, low_diversity),
+        ];
+        for (flag, set) in flags {
+            if set {
+                self.flags.insert(flag);
+            } else {
+                self.flags.remove(&flag);
+            }
+        }
+
+        let base_cap = if isolated {
+            ISOLATION_TRUST_CAP
+        } else if low_diversity {
+            0.7
+        } else {
+            1.0
+        };
+
+        let anomaly_cap = if self.flags.contains(&NodeFlag::QualityAnomaly) {
+            QUALITY_ANOMALY_CAP
         } else {
-            self.flags.remove(&NodeFlag::LowDiversity);
-            self.trust_cap = Score::ONE;
+            1.0
+        };
+
+        self.trust_cap = Score::new(
+            base_cap * anomaly_cap * self.vouch_cap_multiplier * self.swift_trust_multiplier,
+        );
+    }
+
+    /// Check and update quality anomaly flag (run before `check_diversity`,
+    /// which applies the flag to the trust cap)
+    pub fn check_quality_anomaly(&mut self) {
+        if self.history.len() < QUALITY_ANOMALY_MIN_HISTORY {
+            self.flags.remove(&NodeFlag::QualityAnomaly);
+            return;
+        }
+
+        if self.history.quality_variance(50) > QUALITY_ANOMALY_VARIANCE {
+            self.flags.insert(NodeFlag::QualityAnomaly);
+        } else {
+            self.flags.remove(&NodeFlag::QualityAnomaly);
         }
     }
 
@@ -425,6 +982,63 @@ impl Node {
         Score::new(sum / weight_sum)
     }
 
+    /// Estimate how long this node takes to complete a task (ms)
+    ///
+    /// L = L_base × (1 + P × load) / (1 + E × ln(1 + V)), where V is the total
+    /// volume handled across capabilities
+    pub fn estimated_latency_ms(&self) -> f64 {
+        let volume: f64 = self.capabilities.values().map(|c| c.volume as f64).sum();
+        let slowdown = 1.0 + LATENCY_LOAD_PENALTY * self.load.value();
+        let speedup = 1.0 + LATENCY_EXPERIENCE * volume.ln_1p();
+        LATENCY_BASE_MS * slowdown / speedup
+    }
+
+    /// Queue a task for a later tick, returning its position in the backlog
+    pub fn enqueue_task(&mut self, task: &Task) -> Result<usize, EnqueueError> {
+        if !self.status.is_active() {
+            return Err(EnqueueError::Inactive);
+        }
+        if let Some(&missing) = task.required_caps.iter().find(|&&cap| !self.has_capability(cap)) {
+            return Err(EnqueueError::MissingCapability(missing));
+        }
+        self.task_queue.push(QueuedTask::new(task, self.current_tick))
+    }
+
+    /// Check if this node can take a task now or queue it for later
+    pub fn can_queue_task(&self) -> bool {
+        self.status.is_active() && !self.task_queue.is_full()
+    }
+
+    /// Start queued tasks in order (called each tick), returning those started
+    ///
+    /// Up to TASKS_PER_TICK tasks start, each adding LOAD_PER_TASK to the node
+    /// and to every capability it draws on. Draining stops at the first task
+    /// the node has no room for; load decay frees room on later ticks.
+    pub fn drain_tasks(&mut self) -> Vec<TaskId> {
+        let mut started = Vec::new();
+        while started.len() < TASKS_PER_TICK {
+            let Some(next) = self.task_queue.front() else {
+                break;
+            };
+            if !next.capabilities.iter().all(|&cap| self.can_accept_capability_work(cap)) {
+                break;
+            }
+            let Some(task) = self.task_queue.pop() else {
+                break;
+            };
+
+            self.load = Score::new(self.load.value() + LOAD_PER_TASK);
+            for cap in &task.capabilities {
+                if let Some(cap_state) = self.capabilities.get_mut(cap) {
+                    cap_state.mark_used();
+                    cap_state.add_load(LOAD_PER_TASK);
+                }
+            }
+            started.push(task.task_id);
+        }
+        started
+    }
+
     /// Check if this node can accept work
     pub fn can_accept_work(&self) -> bool {
         self.status.is_active() && self.load.value() < 0.95
@@ -439,6 +1053,18 @@ impl Node {
                 .map(|c| c.can_accept_work())
                 .unwrap_or(false)
     }
+
+    /// Encode the node with bincode for compact persistence
+    #[cfg(feature = "persistence")]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, bincode::Error> {
+        bincode::serialize(self)
+    }
+
+    /// Decode a node written by [`Node::to_bytes`]
+    #[cfg(feature = "persistence")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, bincode::Error> {
+        bincode::deserialize(bytes)
+    }
 }
 
 /// Builder for creating test nodes
@@ -449,6 +1075,7 @@ pub struct NodeBuilder {
     trust: Score,
     capabilities: Vec<Capability>,
     connections: Vec<(NodeId, Connection)>,
+    history_size: usize,
 }
 
 impl NodeBuilder {
@@ -460,6 +1087,7 @@ impl NodeBuilder {
             trust: Score::new(SWIFT_TRUST_BASE),
             capabilities: Vec::new(),
             connections: Vec::new(),
+            history_size: INTERACTION_HISTORY_SIZE,
         }
     }
 
@@ -487,9 +1115,16 @@ impl NodeBuilder {
         self
     }
 
+    /// Set interaction history size
+    pub fn history_size(mut self, size: usize) -> Self {
+        self.history_size = size;
+        self
+    }
+
     /// Build the node
     pub fn build(self) -> Node {
-        let mut node = Node::with_capabilities(self.id, self.capabilities);
+        let mut node = Node::with_capabilities(self.id, self.capabilities)
+            .with_history_size(self.history_size);
         node.status = self.status;
         node.trust = self.trust;
 
@@ -501,10 +1136,63 @@ impl NodeBuilder {
     }
 }
 
+/// Serialize sets in sorted order, so equal nodes serialize identically
+mod sorted_set {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::HashSet;
+    use std::hash::Hash;
+
+    pub fn serialize<T: Ord + Serialize, S: Serializer>(
+        set: &HashSet<T>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut items: Vec<_> = set.iter().collect();
+        items.sort();
+        items.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<HashSet<T>, D::Error>
+    where
+        T: Eq + Hash + Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        HashSet::deserialize(deserializer)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::capability::common;
+    use crate::params::DiversityMeasure;
+
+    #[test]
+    fn test_trust_history_variance() {
+        let mut node = Node::new(NodeId::from_index(1));
+        assert!(!node.is_trust_stable(4, 0.01), "no history is not yet stable");
+
+        for _ in 0..4 {
+            node.record_trust(Score::new(0.6));
+        }
+        assert_eq!(node.trust, Score::new(0.6));
+        assert_eq!(node.trust_variance(4), 0.0);
+        assert_eq!(node.trust_velocity(4), 0.0);
+        assert!(node.is_trust_stable(4, 0.01));
+
+        // Alternating 0.2 / 0.8 has variance 0.09 over the last four values
+        for i in 0..4 {
+            node.record_trust(Score::new(if i % 2 == 0 { 0.2 } else { 0.8 }));
+        }
+        assert!((node.trust_variance(4) - 0.09).abs() < 1e-9);
+        assert!(!node.is_trust_stable(4, 0.01));
+
+        // The ring buffer keeps only the most recent values
+        for _ in 0..TRUST_HISTORY_SIZE {
+            node.record_trust(Score::HALF);
+        }
+        assert_eq!(node.trust_history.len(), TRUST_HISTORY_SIZE);
+        assert_eq!(node.trust_variance(TRUST_HISTORY_SIZE), 0.0);
+    }
 
     #[test]
     fn test_node_creation() {
@@ -591,6 +1279,152 @@ mod tests {
         assert!(node.trust.value() > SWIFT_TRUST_BASE);
     }
 
+    #[test]
+    fn test_swift_trust_decays_only_for_idle_newcomers() {
+        let mut newcomer = Node::new(NodeId::from_index(1));
+        let mut member = Node::new(NodeId::from_index(2));
+        member.status = NodeStatus::Member;
+        for _ in 0..1000 {
+            newcomer.decay_swift_trust();
+            member.decay_swift_trust();
+        }
+        assert_eq!(newcomer.swift_trust_multiplier, SWIFT_TRUST_FLOOR);
+        assert_eq!(member.swift_trust_multiplier, 1.0);
+
+        // Requests served count toward engagement, and the cap recovers
+        for _ in 0..SWIFT_TRUST_MIN_INTERACTIONS {
+            newcomer.handle_incoming_interaction(
+                NodeId::from_index(3),
+                1.0,
+                1.0,
+                1.0,
+                Score::new(0.8),
+                SignedScore::ZERO,
+                None,
+            );
+        }
+        newcomer.decay_swift_trust();
+        assert!(newcomer.swift_trust_multiplier > SWIFT_TRUST_FLOOR);
+    }
+
+    #[test]
+    fn test_status_ladder_to_hub() {
+        let id = NodeId::from_index(1);
+        let mut node = Node::new(id);
+        let interact = |node: &mut Node, partner: u64| {
+            node.handle_outgoing_interaction(
+                NodeId::from_index(partner),
+                1.0,
+                1.0,
+                1.0,
+                Score::new(0.8),
+                SignedScore::ZERO,
+                None,
+            );
+        };
+
+        // Probation with one partner
+        node.current_tick = 10;
+        for _ in 0..PROBATION_COUNT {
+            interact(&mut node, 2);
+        }
+        assert_eq!(node.status, NodeStatus::Member);
+
+        // Enough interactions but trust not yet sustained
+        node.current_tick = 20;
+        for i in 0..ESTABLISHED_MIN_INTERACTIONS {
+            interact(&mut node, 2 + i % 5);
+        }
+        node.record_trust(Score::new(0.7));
+        node.check_promotion();
+        assert_eq!(node.status, NodeStatus::Member);
+
+        for _ in 0..ESTABLISHED_TRUST_WINDOW {
+            node.record_trust(Score::new(0.7));
+        }
+        node.check_promotion();
+        assert_eq!(node.status, NodeStatus::Established);
+
+        // Too few partners for a hub until it spreads out
+        node.current_tick = 30;
+        node.check_promotion();
+        assert_eq!(node.status, NodeStatus::Established);
+        for partner in 0..HUB_MIN_PARTNERS as u64 {
+            interact(&mut node, 100 + partner);
+        }
+        node.check_promotion();
+        assert_eq!(node.status, NodeStatus::Hub);
+
+        // Losing trust drops it back a step
+        node.current_tick = 40;
+        node.record_trust(Score::new(0.55));
+        node.check_promotion();
+        assert_eq!(node.status, NodeStatus::Established);
+
+        let steps: Vec<_> = node
+            .status_history
+            .iter()
+            .map(|c| (c.tick, c.from, c.to, c.reason))
+            .collect();
+        assert_eq!(
+            steps,
+            vec![
+                (10, NodeStatus::Probationary, NodeStatus::Member, StatusReason::ProbationPassed),
+                (20, NodeStatus::Member, NodeStatus::Established, StatusReason::SustainedTrust),
+                (30, NodeStatus::Established, NodeStatus::Hub, StatusReason::HubConnectivity),
+                (40, NodeStatus::Hub, NodeStatus::Established, StatusReason::TrustDeclined),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_repeated_probation_failure_expels() {
+        let id = NodeId::from_index(1);
+        let partner = NodeId::from_index(2);
+        let mut node = Node::new(id);
+
+        for window in 1..=MAX_PROBATION_FAILURES {
+            for _ in 0..PROBATION_COUNT {
+                node.handle_outgoing_interaction(
+                    partner,
+                    1.0,
+                    1.0,
+                    1.0,
+                    Score::new(0.2),
+                    SignedScore::ZERO,
+                    None,
+                );
+            }
+            assert_eq!(node.probation_failures, window);
+        }
+
+        assert_eq!(node.status, NodeStatus::Expelled);
+        assert_eq!(node.trust, Score::ZERO);
+        assert_eq!(node.trust_cap, Score::ZERO);
+        assert_eq!(node.status_history.last().unwrap().reason, StatusReason::ProbationFailed);
+    }
+
+    #[test]
+    fn test_vouching() {
+        let mut voucher = Node::new(NodeId::from_index(1));
+        voucher.trust = Score::new(0.9);
+        let mut vouchee = Node::new(NodeId::from_index(2));
+
+        voucher.vouch_for(vouchee.id);
+        vouchee.accept_vouch(voucher.id, voucher.trust);
+
+        assert_eq!(vouchee.vouched_by, Some(voucher.id));
+        assert!(vouchee.trust.value() > SWIFT_TRUST_BASE);
+
+        // Penalty applies once per vouchee and survives diversity checks
+        voucher.apply_vouch_penalty(vouchee.id);
+        voucher.apply_vouch_penalty(vouchee.id);
+        assert!((voucher.trust_cap.value() - VOUCH_PENALTY).abs() < 1e-9);
+
+        voucher.check_diversity();
+        assert!(voucher.trust_cap.value() <= VOUCH_PENALTY);
+    }
+
     #[test]
     fn test_node_builder() {
         let id = NodeId::from_index(1);
@@ -608,4 +1442,209 @@ mod tests {
         assert!(node.has_capability(common::analysis().id));
         assert!(node.connections.contains_key(&partner));
     }
+
+    #[test]
+    fn test_diversity_measure_accounts_for_concentration() {
+        let id = NodeId::from_index(1);
+        let params = ProtocolParams::default()
+            .with_diversity(DiversityMeasure::Simpson)
+            .with_diversity_window(100);
+        let mut even = Node::new(id);
+        let mut lopsided = Node::new(id);
+
+        // Both see 50 partners over 100 interactions
+        for i in 0..100 {
+            even.history.add(Interaction::new(id, NodeId::from_index(2 + i % 50)));
+            let partner = if i < 50 { 2 + i } else { 2 };
+            lopsided.history.add(Interaction::new(id, NodeId::from_index(partner)));
+        }
+        assert_eq!(even.history.unique_partners(100), 50);
+        assert_eq!(lopsided.history.unique_partners(100), 50);
+
+        // The count-based default can't tell them apart
+        assert_eq!(even.diversity_score(), lopsided.diversity_score());
+
+        even.params = params.clone();
+        lopsided.params = params;
+        assert!((even.diversity_score().value() - 0.5).abs() < 1e-9);
+        assert!(lopsided.diversity_score().value() < 0.1);
+    }
+
+    #[test]
+    fn test_diversity_normalized_by_reachable_partners() {
+        let id = NodeId::from_index(1);
+        let mut node = Node::new(id);
+
+        // Thirty interactions spread evenly over ten partners, half incoming
+        for i in 0..30 {
+            let partner = NodeId::from_index(2 + i % 10);
+            let interaction = if i % 2 == 0 {
+                Interaction::new(id, partner)
+            } else {
+                Interaction::new(partner, id)
+            };
+            node.history.add(interaction);
+        }
+
+        // Measured against the thirty interactions seen, not the full window
+        assert!((node.diversity_score().value() - 10.0 / 30.0).abs() < 1e-9);
+
+        // Every partner the network offers is in use
+        node.reachable_partners = Some(10);
+        assert_eq!(node.diversity_score(), Score::ONE);
+
+        // A couple of interactions don't read as full diversity
+        let mut newcomer = Node::new(id);
+        newcomer.history.add(Interaction::new(id, NodeId::from_index(2)));
+        newcomer.history.add(Interaction::new(id, NodeId::from_index(3)));
+        let expected = 2.0 / DIVERSITY_MIN_SAMPLE as f64;
+        assert!((newcomer.diversity_score().value() - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_received_quality_leaves_own_capability_quality() {
+        let cap = common::analysis();
+        let mut node = Node::with_capabilities(NodeId::from_index(1), vec![cap.clone()]);
+        let partner = NodeId::from_index(2);
+        let neutral = node.capability_quality(cap.id);
+
+        // A free rider's poor work lands on us but says nothing about ours
+        for _ in 0..10 {
+            node.handle_incoming_interaction(
+                partner, 1.0, 1.0, 0.0, Score::new(0.1), SignedScore::ZERO, Some(cap.id),
+            );
+        }
+        assert_eq!(node.capability_quality(cap.id), neutral);
+        assert_eq!(node.capabilities[&cap.id].volume, 10);
+        let view = node.connections[&partner].capability_quality(cap.id);
+        assert!(view.value() < neutral.value());
+
+        // Work we perform is what moves it, and it says nothing about the partner
+        node.handle_outgoing_interaction(
+            partner, 1.0, 1.0, 1.0, Score::new(0.9), SignedScore::ZERO, Some(cap.id),
+        );
+        assert!(node.capability_quality(cap.id).value() > neutral.value());
+        assert_eq!(node.connections[&partner].capability_quality(cap.id), view);
+
+        // Initiating with a poor partner never lowers our own quality
+        for _ in 0..10 {
+            let own = node.capability_quality(cap.id);
+            node.handle_incoming_interaction(
+                partner, 1.0, 1.0, 0.0, Score::new(0.1), SignedScore::ZERO, Some(cap.id),
+            );
+            node.handle_outgoing_interaction(
+                partner, 1.0, 1.0, 1.0, Score::new(0.9), SignedScore::ZERO, Some(cap.id),
+            );
+            assert!(node.capability_quality(cap.id).value() >= own.value());
+        }
+    }
+
+    #[test]
+    fn test_specialization() {
+        let mut node = Node::with_capabilities(
+            NodeId::from_index(1),
+            vec![common::analysis(), common::generation()],
+        );
+        assert_eq!(node.specialization(), None);
+        assert_eq!(node.category_share(CapabilityCategory::Analysis), 0.5);
+
+        node.add_capability(Capability::from_name("summary", CapabilityCategory::Analysis));
+        assert_eq!(node.specialization(), Some(CapabilityCategory::Analysis));
+        assert_eq!(Node::new(NodeId::from_index(2)).specialization(), None);
+    }
+
+    #[test]
+    fn test_larger_history_tracks_more_partners() {
+        let id = NodeId::from_index(1);
+        let mut short = Node::new(id);
+        let mut long = NodeBuilder::new(id).history_size(500).build();
+        assert_eq!(short.history.max_size(), INTERACTION_HISTORY_SIZE);
+        assert_eq!(long.history.max_size(), 500);
+
+        // Same stream, cycling through 300 partners
+        for i in 0..600 {
+            let interaction = Interaction::new(id, NodeId::from_index(2 + i % 300));
+            short.history.add(interaction.clone());
+            long.history.add(interaction);
+        }
+
+        let window = |node: &Node| node.history.max_size();
+        assert_eq!(short.history.unique_partners(window(&short)), 100);
+        assert_eq!(long.history.unique_partners(window(&long)), 300);
+        assert_eq!(short.diversity_score().value(), 1.0);
+        assert!((long.diversity_score().value() - 0.6).abs() < 1e-9);
+
+        // Resizing keeps the most recent interactions
+        let resized = long.clone().with_history_size(50);
+        assert_eq!(resized.history.len(), 50);
+        assert_eq!(resized.history.all()[0].responder, long.history.all()[0].responder);
+    }
+
+    #[test]
+    fn test_quality_anomaly_flag() {
+        let id = NodeId::from_index(1);
+        let mut node = Node::new(id);
+        let add = |node: &mut Node, quality: f64| {
+            node.history.add(
+                Interaction::new(id, NodeId::from_index(2))
+                    .with_outcome(Score::new(quality), SignedScore::ZERO),
+            );
+        };
+
+        // Too little history to judge
+        for i in 0..10 {
+            add(&mut node, if i % 2 == 0 { 0.95 } else { 0.05 });
+        }
+        node.check_quality_anomaly();
+        assert!(!node.flags.contains(&NodeFlag::QualityAnomaly));
+
+        // Wildly swinging quality sets the flag and trims the trust cap
+        for i in 0..40 {
+            add(&mut node, if i % 2 == 0 { 0.95 } else { 0.05 });
+        }
+        node.check_quality_anomaly();
+        node.check_diversity();
+        assert!(node.flags.contains(&NodeFlag::QualityAnomaly));
+        let flagged_cap = node.trust_cap.value();
+
+        // A stable stretch clears it and restores the cap
+        for _ in 0..50 {
+            add(&mut node, 0.8);
+        }
+        node.check_quality_anomaly();
+        node.check_diversity();
+        assert!(!node.flags.contains(&NodeFlag::QualityAnomaly));
+        assert!((flagged_cap - node.trust_cap.value() * QUALITY_ANOMALY_CAP).abs() < 1e-9);
+    }
+
+    #[cfg(feature = "persistence")]
+    #[test]
+    fn test_bincode_round_trip() {
+        let mut node = Node::with_capabilities(NodeId::from_index(1), vec![common::analysis()]);
+        for i in 2..6 {
+            let partner = NodeId::from_index(i);
+            node.handle_outgoing_interaction(
+                partner,
+                1.0,
+                1.0,
+                1.0,
+                Score::new(0.8),
+                SignedScore::new(0.5),
+                Some(common::analysis().id),
+            );
+            node.update_threat_belief(partner, ThreatType::Cheating, 0.4, None);
+        }
+        node.flags.insert(NodeFlag::LowDiversity);
+        node.vouched_by = Some(NodeId::from_index(9));
+        node.set_status(NodeStatus::Member, StatusReason::ProbationPassed);
+
+        let bytes = node.to_bytes().unwrap();
+        let restored = Node::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            serde_json::to_value(&restored).unwrap(),
+            serde_json::to_value(&node).unwrap()
+        );
+        assert!(bytes.len() < serde_json::to_vec(&node).unwrap().len());
+        assert!(Node::from_bytes(&bytes[..bytes.len() / 2]).is_err());
+    }
 }
@@ -0,0 +1,171 @@
+//! Runtime-selectable protocol parameters.
+//!
+//! Constants in [`crate::constants`] give the protocol's default coefficients;
+//! these settings choose how the dynamics are evaluated and let the weight
+//! equation's coefficients be overridden, e.g. for parameter sweeps.
+
+use crate::constants::{ALPHA, DELTA, GAMMA, MU};
+use serde::{Deserialize, Serialize};
+
+/// Numerical integrator for the connection weight ODE
+///
+/// dw/dt = Φ - α×w - D
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum WeightIntegrator {
+    /// Single explicit Euler step per interaction
+    #[default]
+    Euler,
+    /// 4th-order Runge-Kutta split into `substeps` per interaction
+    Rk4 {
+        /// Number of RK4 steps per update
+        substeps: u32,
+    },
+}
+
+/// How a node's interaction diversity is measured
+///
+/// Each measure yields an effective partner count N_eff over the diversity
+/// window; all agree when interactions are spread evenly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DiversityMeasure {
+    /// N_eff = number of distinct partners
+    #[default]
+    UniquePartners,
+    /// N_eff = 1 / Σ p_i² (inverse Simpson index)
+    Simpson,
+    /// N_eff = exp(-Σ p_i ln p_i) (exponential Shannon entropy)
+    Shannon,
+}
+
+impl DiversityMeasure {
+    /// Effective partner count from per-partner interaction counts
+    pub fn effective_partners(&self, counts: &[usize]) -> f64 {
+        let total: usize = counts.iter().sum();
+        if total == 0 {
+            return 0.0;
+        }
+        let shares = counts.iter().map(|&c| c as f64 / total as f64);
+        match self {
+            DiversityMeasure::UniquePartners => counts.iter().filter(|&&c| c > 0).count() as f64,
+            DiversityMeasure::Simpson => 1.0 / shares.map(|p| p * p).sum::<f64>(),
+            DiversityMeasure::Shannon => {
+                let entropy: f64 = shares.filter(|&p| p > 0.0).map(|p| -p * p.ln()).sum();
+                entropy.exp()
+            }
+        }
+    }
+}
+
+/// Error setting a protocol parameter by name
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("unknown protocol parameter {0:?} (expected one of: gamma, mu, alpha, delta)")]
+pub struct UnknownParam(pub String);
+
+/// Protocol evaluation settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProtocolParams {
+    /// Integrator for connection weight updates
+    pub weight_integrator: WeightIntegrator,
+    /// γ - reinforcement rate
+    pub gamma: f64,
+    /// μ - flow exponent
+    pub mu: f64,
+    /// α - passive weight decay rate
+    pub alpha: f64,
+    /// δ - defense dampening factor
+    pub delta: f64,
+    /// How interaction diversity is measured
+    pub diversity: DiversityMeasure,
+    /// Recent interactions diversity is measured over (None = whole history)
+    pub diversity_window: Option<usize>,
+}
+
+impl Default for ProtocolParams {
+    fn default() -> Self {
+        Self {
+            weight_integrator: WeightIntegrator::default(),
+            gamma: GAMMA,
+            mu: MU,
+            alpha: ALPHA,
+            delta: DELTA,
+            diversity: DiversityMeasure::default(),
+            diversity_window: None,
+        }
+    }
+}
+
+impl ProtocolParams {
+    /// Names of the numeric parameters that can be set with [`Self::with_param`]
+    pub const NAMES: [&'static str; 4] = ["gamma", "mu", "alpha", "delta"];
+
+    /// Set the weight integrator
+    pub fn with_weight_integrator(mut self, integrator: WeightIntegrator) -> Self {
+        self.weight_integrator = integrator;
+        self
+    }
+
+    /// Set the diversity measure
+    pub fn with_diversity(mut self, measure: DiversityMeasure) -> Self {
+        self.diversity = measure;
+        self
+    }
+
+    /// Measure diversity over the most recent `window` interactions
+    pub fn with_diversity_window(mut self, window: usize) -> Self {
+        self.diversity_window = Some(window);
+        self
+    }
+
+    /// Override a numeric parameter by name
+    pub fn with_param(mut self, name: &str, value: f64) -> Result<Self, UnknownParam> {
+        let field = match name {
+            "gamma" => &mut self.gamma,
+            "mu" => &mut self.mu,
+            "alpha" => &mut self.alpha,
+            "delta" => &mut self.delta,
+            _ => return Err(UnknownParam(name.to_string())),
+        };
+        *field = value;
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_param_by_name() {
+        let params = ProtocolParams::default().with_param("gamma", 0.2).unwrap();
+        assert_eq!(params.gamma, 0.2);
+        assert_eq!(params.alpha, ALPHA);
+
+        for name in ProtocolParams::NAMES {
+            assert!(ProtocolParams::default().with_param(name, 1.0).is_ok());
+        }
+        assert_eq!(
+            ProtocolParams::default().with_param("beta", 1.0),
+            Err(UnknownParam("beta".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_effective_partners() {
+        let even = [5, 5, 5, 5];
+        for measure in [
+            DiversityMeasure::UniquePartners,
+            DiversityMeasure::Simpson,
+            DiversityMeasure::Shannon,
+        ] {
+            assert!((measure.effective_partners(&even) - 4.0).abs() < 1e-9);
+            assert_eq!(measure.effective_partners(&[]), 0.0);
+        }
+
+        let skewed = [17, 1, 1, 1];
+        assert_eq!(DiversityMeasure::UniquePartners.effective_partners(&skewed), 4.0);
+        let simpson = DiversityMeasure::Simpson.effective_partners(&skewed);
+        let shannon = DiversityMeasure::Shannon.effective_partners(&skewed);
+        assert!(simpson > 1.0 && simpson < shannon && shannon < 4.0);
+    }
+}
@@ -3,15 +3,20 @@
 //! Routes tasks to the best-suited nodes based on trust, capability quality,
 //! availability, and connection strength.
 
-use crate::constants::W_INIT;
+use crate::capability::CapabilityCategory;
+use crate::constants::{
+    CONNECTION_PRIMING_PENALTY, LOAD_PER_INTERACTION, QUEUE_DEPTH_PENALTY, SPECIALIZATION_BONUS,
+    W_INIT,
+};
+use crate::math::reciprocity_sigmoid;
 use crate::node::Node;
 use crate::types::{CapabilityId, NodeId, Score, TaskId, Timestamp};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Priority level for tasks
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[derive(Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Priority {
     Low,
     #[default]
@@ -20,6 +25,28 @@ pub enum Priority {
     Critical,
 }
 
+/// How per-capability quality is combined for multi-capability tasks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CapabilityAggregation {
+    /// Product of qualities (a weak capability drags the score down hard)
+    #[default]
+    Product,
+    /// Arithmetic mean of qualities
+    Mean,
+}
+
+impl CapabilityAggregation {
+    /// Combine per-capability qualities into a single factor
+    pub fn aggregate(&self, qualities: &[f64]) -> f64 {
+        if qualities.is_empty() {
+            return 0.0;
+        }
+        match self {
+            CapabilityAggregation::Product => qualities.iter().product(),
+            CapabilityAggregation::Mean => qualities.iter().sum::<f64>() / qualities.len() as f64,
+        }
+    }
+}
 
 /// Constraints for task routing
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -36,6 +63,16 @@ pub struct TaskConstraints {
     pub preferred_nodes: Vec<NodeId>,
     /// Nodes to exclude
     pub excluded_nodes: Vec<NodeId>,
+    /// How to combine quality across multiple required capabilities
+    #[serde(default)]
+    pub capability_aggregation: CapabilityAggregation,
+    /// How hard ensemble routing down-weights candidates resembling earlier
+    /// picks, in [0, 1] (0 = plain top-k)
+    #[serde(default)]
+    pub diversity_penalty: f64,
+    /// Capability category whose specialists are favored
+    #[serde(default)]
+    pub preferred_category: Option<CapabilityCategory>,
 }
 
 impl TaskConstraints {
@@ -68,12 +105,36 @@ impl TaskConstraints {
         self
     }
 
+    /// Set the time allowed to complete the task
+    pub fn with_timeout(mut self, timeout_ms: u64) -> Self {
+        self.timeout_ms = Some(timeout_ms);
+        self
+    }
+
     /// Set priority
     pub fn with_priority(mut self, priority: Priority) -> Self {
         self.priority = priority;
         self
     }
 
+    /// Set how multi-capability quality is aggregated
+    pub fn with_capability_aggregation(mut self, aggregation: CapabilityAggregation) -> Self {
+        self.capability_aggregation = aggregation;
+        self
+    }
+
+    /// Set the ensemble diversity penalty
+    pub fn with_diversity_penalty(mut self, penalty: f64) -> Self {
+        self.diversity_penalty = penalty.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Favor candidates specialized in a capability category
+    pub fn with_preferred_category(mut self, category: CapabilityCategory) -> Self {
+        self.preferred_category = Some(category);
+        self
+    }
+
     /// Check if a node meets the constraints
     pub fn is_acceptable(&self, node: &Node, capability: CapabilityId) -> bool {
         // Check exclusions
@@ -95,8 +156,39 @@ impl TaskConstraints {
             }
         }
 
+        // Check the node can plausibly finish in time
+        if let Some(timeout) = self.timeout_ms {
+            if node.estimated_latency_ms() > timeout as f64 {
+                return false;
+            }
+        }
+
         true
     }
+
+    /// Timeliness factor for a node under this task's timeout
+    ///
+    /// t = 1 - L / (2 × timeout), clamped to [0.5, 1]; 1 when there is no timeout
+    pub fn timeliness(&self, node: &Node) -> f64 {
+        match self.timeout_ms {
+            Some(timeout) if timeout > 0 => {
+                let ratio = node.estimated_latency_ms() / timeout as f64;
+                (1.0 - ratio / 2.0).clamp(0.5, 1.0)
+            }
+            Some(_) => 0.5,
+            None => 1.0,
+        }
+    }
+
+    /// Specialization factor for a node under this task's category preference
+    ///
+    /// s = 1 + SPECIALIZATION_BONUS × share, where share is the fraction of the
+    /// node's capabilities in the preferred category; 1 with no preference
+    pub fn specialization(&self, node: &Node) -> f64 {
+        self.preferred_category
+            .map(|category| 1.0 + SPECIALIZATION_BONUS * node.category_share(category))
+            .unwrap_or(1.0)
+    }
 }
 
 /// A task to be routed
@@ -155,45 +247,97 @@ pub struct CandidateScore {
 pub struct ScoreComponents {
     /// Trust contribution
     pub trust: f64,
-    /// Capability quality contribution
+    /// Capability quality contribution (aggregated across required capabilities)
     pub capability_quality: f64,
+    /// Per-capability effective quality before aggregation
+    pub capability_qualities: Vec<(CapabilityId, f64)>,
     /// Availability (1 - load) contribution
     pub availability: f64,
+    /// Backlog (queued tasks) contribution
+    pub backlog: f64,
     /// Connection weight contribution
     pub connection: f64,
-    /// Defense (1 - threat) contribution
+    /// Reciprocity contribution (averaged across required capabilities)
+    pub reciprocity: f64,
+    /// Defense (1 - threat) × (1 - penalty × π) contribution
     pub defense: f64,
+    /// Timeliness (estimated latency against the task timeout) contribution
+    pub timeliness: f64,
+    /// Specialization in the task's preferred category contribution
+    pub specialization: f64,
     /// Preference bonus
     pub preference_bonus: f64,
 }
 
 /// Compute routing score for a candidate
 ///
-/// S_route = T(n) × q_cap(n) × (1 - load) × w_conn × (1 - threat) × pref_bonus
+/// S_route = T(n) × q_cap(n) × (1 - load) × b × w_conn × ρ_cap × d × t × s × pref_bonus
+///
+/// where the defense factor d = (1 - threat) × (1 - CONNECTION_PRIMING_PENALTY × π)
+/// also mildly discounts a connection primed by signals before any belief forms,
+/// and b = 1 / (1 + QUEUE_DEPTH_PENALTY × depth) steers work away from nodes
+/// with a backlog.
 pub fn compute_routing_score(
     from_node: &Node,
     candidate: &Node,
     capability: CapabilityId,
     constraints: &TaskConstraints,
+) -> CandidateScore {
+    compute_multi_routing_score(from_node, candidate, &[capability], constraints)
+}
+
+/// Compute routing score for a candidate across several required capabilities
+///
+/// q_cap(n) is the per-capability effective quality, shrunk toward neutral
+/// for little-used capabilities, combined according to
+/// `constraints.capability_aggregation`. ρ_cap = 1 + σ(r̄)/2, where r̄ is the
+/// mean per-capability reciprocity on our connection to the candidate, so a
+/// partner that free-rides on the requested capabilities ranks lower.
+pub fn compute_multi_routing_score(
+    from_node: &Node,
+    candidate: &Node,
+    capabilities: &[CapabilityId],
+    constraints: &TaskConstraints,
 ) -> CandidateScore {
     // Trust
     let trust = candidate.trust.value();
 
     // Capability quality
-    let cap_quality = candidate.capability_quality(capability).value();
+    let capability_qualities: Vec<(CapabilityId, f64)> = capabilities
+        .iter()
+        .map(|&cap| (cap, candidate.effective_capability_quality(cap).value()))
+        .collect();
+    let qualities: Vec<f64> = capability_qualities.iter().map(|(_, q)| *q).collect();
+    let cap_quality = constraints.capability_aggregation.aggregate(&qualities);
 
     // Availability
     let availability = 1.0 - candidate.load.value();
+    let backlog = 1.0 / (1.0 + QUEUE_DEPTH_PENALTY * candidate.task_queue.len() as f64);
 
     // Connection weight
-    let connection = from_node
-        .get_connection(&candidate.id)
-        .map(|c| c.w.value())
-        .unwrap_or(W_INIT);
+    let conn = from_node.get_connection(&candidate.id);
+    let connection = conn.map(|c| c.w.value()).unwrap_or(W_INIT);
+
+    // Reciprocity on the requested capabilities
+    let mean_r = conn
+        .filter(|_| !capabilities.is_empty())
+        .map(|c| {
+            let sum: f64 = capabilities.iter().map(|&cap| c.capability_reciprocity(cap)).sum();
+            sum / capabilities.len() as f64
+        })
+        .unwrap_or(0.0);
+    let reciprocity = 1.0 + reciprocity_sigmoid(mean_r).value() / 2.0;
 
-    // Defense factor
+    // Defense factor, including alertness primed on this connection
     let threat = from_node.get_threat_level(&candidate.id);
-    let defense = 1.0 - threat;
+    let primed = conn.map(|c| c.pi.value()).unwrap_or(0.0);
+    let defense = (1.0 - threat) * (1.0 - CONNECTION_PRIMING_PENALTY * primed);
+
+    // Timeliness against the task timeout
+    let timeliness = constraints.timeliness(candidate);
+
+    // Specialization in the preferred category
+    let specialization = constraints.specialization(candidate);
 
     // Preference bonus
     let preference_bonus = if constraints.preferred_nodes.contains(&candidate.id) {
@@ -202,7 +346,16 @@ pub fn compute_routing_score(
         1.0
     };
 
-    let score = trust * cap_quality * availability * connection * defense * preference_bonus;
+    let score = trust
+        * cap_quality
+        * availability
+        * backlog
+        * connection
+        * reciprocity
+        * defense
+        * timeliness
+        * specialization
+        * preference_bonus;
 
     CandidateScore {
         node_id: candidate.id,
@@ -210,14 +363,62 @@ pub fn compute_routing_score(
         components: ScoreComponents {
             trust,
             capability_quality: cap_quality,
+            capability_qualities,
             availability,
+            backlog,
             connection,
+            reciprocity,
             defense,
+            timeliness,
+            specialization,
             preference_bonus,
         },
     }
 }
 
+/// Check if a candidate can take a task: it must hold every required
+/// capability, have room for the work now or in its backlog, and satisfy the
+/// task constraints
+///
+/// `min_trust` is also checked against trust discounted by the origin's
+/// threat belief, T × (1 - threat), so a suspected node can't slip through
+/// on its global trust alone.
+fn is_eligible(from_node: &Node, candidate: &Node, task: &Task) -> bool {
+    let discounted_trust =
+        candidate.trust.value() * (1.0 - from_node.get_threat_level(&candidate.id));
+    let trusted = task
+        .constraints
+        .min_trust
+        .map_or(true, |min| discounted_trust >= min.value());
+
+    candidate.id != from_node.id // Don't route to self
+        && trusted
+        && task.required_caps.iter().all(|&cap| {
+            candidate.has_capability(cap)
+                && (candidate.can_accept_capability_work(cap) || candidate.can_queue_task())
+                && task.constraints.is_acceptable(candidate, cap)
+        })
+}
+
+/// Score every eligible candidate for a task, best first
+fn score_candidates(
+    from_node: &Node,
+    task: &Task,
+    candidates: &HashMap<NodeId, Node>,
+) -> Vec<CandidateScore> {
+    let mut scored: Vec<CandidateScore> = candidates
+        .values()
+        .filter(|node| is_eligible(from_node, node, task))
+        .map(|node| {
+            compute_multi_routing_score(from_node, node, &task.required_caps, &task.constraints)
+        })
+        .collect();
+
+    // Sort by score descending
+    scored.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    scored
+}
+
 /// Result of routing attempt
 #[derive(Debug, Clone)]
 pub enum RoutingResult {
@@ -254,32 +455,135 @@ pub fn route_task(
         return RoutingResult::NoCandidates;
     }
 
-    let required_cap = task.required_caps[0];
+    let mut scored = score_candidates(from_node, task, candidates);
 
-    // Find candidates with the required capability
-    let mut scored: Vec<CandidateScore> = candidates
-        .values()
-        .filter(|node| {
-            // Must have capability and be able to accept work
-            node.has_capability(required_cap)
-                && node.can_accept_capability_work(required_cap)
-                && task.constraints.is_acceptable(node, required_cap)
-                && node.id != from_node.id // Don't route to self
-        })
-        .map(|node| compute_routing_score(from_node, node, required_cap, &task.constraints))
-        .collect();
+    if scored.is_empty() {
+        return RoutingResult::NoCandidates;
+    }
+
+    RoutingResult::Success(scored.remove(0))
+}
+
+/// Route a task to a candidate sampled by score
+///
+/// P(n) = exp(S_n / temperature) / Σ_m exp(S_m / temperature)
+///
+/// Near-equal candidates share work instead of the top one taking it all.
+/// Lower temperatures favor the best candidate; a temperature of zero or
+/// less behaves like [`route_task`].
+pub fn route_task_probabilistic<R: Rng + ?Sized>(
+    from_node: &Node,
+    task: &Task,
+    candidates: &HashMap<NodeId, Node>,
+    temperature: f64,
+    rng: &mut R,
+) -> RoutingResult {
+    if temperature <= 0.0 {
+        return route_task(from_node, task, candidates);
+    }
+    if task.required_caps.is_empty() {
+        return RoutingResult::NoCandidates;
+    }
 
+    // Fixed order so a seeded rng picks the same candidate every run
+    let mut scored = score_candidates(from_node, task, candidates);
     if scored.is_empty() {
         return RoutingResult::NoCandidates;
     }
+    scored.sort_by_key(|c| c.node_id);
 
-    // Sort by score descending
-    scored.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    // Shift by the best score so the exponentials cannot overflow
+    let best = scored.iter().map(|c| c.score).fold(f64::NEG_INFINITY, f64::max);
+    let weights: Vec<f64> = scored
+        .iter()
+        .map(|c| ((c.score - best) / temperature).exp())
+        .collect();
 
-    RoutingResult::Success(scored.remove(0))
+    let mut draw = rng.gen::<f64>() * weights.iter().sum::<f64>();
+    let mut pick = scored.len() - 1;
+    for (i, weight) in weights.iter().enumerate() {
+        if draw < *weight {
+            pick = i;
+            break;
+        }
+        draw -= weight;
+    }
+    RoutingResult::Success(scored.swap_remove(pick))
+}
+
+/// Assign `n` copies of a task to workers, spreading them by load
+///
+/// Loads don't change between routes within a tick, so routing each copy
+/// with [`route_task`] would send all of them to the same node. Instead each
+/// pick adds LOAD_PER_INTERACTION to a local estimate of the worker's load L̂,
+/// and candidates are re-ranked by
+///
+/// S' = S × (1 - L̂) / (1 - L)
+///
+/// Workers whose estimate reaches capacity drop out, so fewer than `n` ids
+/// come back when the pool runs dry. A much stronger worker may still be
+/// picked more than once.
+pub fn route_fan_out(
+    from_node: &Node,
+    task: &Task,
+    candidates: &HashMap<NodeId, Node>,
+    n: usize,
+) -> Vec<NodeId> {
+    if task.required_caps.is_empty() {
+        return Vec::new();
+    }
+
+    let mut pool: Vec<(CandidateScore, f64)> = score_candidates(from_node, task, candidates)
+        .into_iter()
+        .map(|c| {
+            let load = candidates[&c.node_id].load.value();
+            (c, load)
+        })
+        .collect();
+
+    let mut workers = Vec::with_capacity(n);
+    while workers.len() < n && !pool.is_empty() {
+        let adjusted = |(c, load): &(CandidateScore, f64)| {
+            c.score * (1.0 - load) / c.components.availability
+        };
+        // First maximum wins, so ties keep the score order
+        let mut best = 0;
+        for i in 1..pool.len() {
+            if adjusted(&pool[i]) > adjusted(&pool[best]) {
+                best = i;
+            }
+        }
+        workers.push(pool[best].0.node_id);
+
+        pool[best].1 += LOAD_PER_INTERACTION;
+        if pool[best].1 >= 0.95 {
+            pool.remove(best);
+        }
+    }
+    workers
+}
+
+/// Overlap of two nodes' neighborhoods: |N(a) ∩ N(b)| / |N(a) ∪ N(b)|
+fn neighborhood_similarity(a: &Node, b: &Node) -> f64 {
+    let na: HashSet<_> = a.connections.keys().collect();
+    let nb: HashSet<_> = b.connections.keys().collect();
+    let union = na.union(&nb).count();
+    if union == 0 {
+        return 0.0;
+    }
+    na.intersection(&nb).count() as f64 / union as f64
 }
 
 /// Get top-k candidates for ensemble routing
+///
+/// Picks are made greedily. With a `diversity_penalty` λ, each remaining
+/// candidate is ranked by
+///
+/// S' = S × (1 - λ × max_j sim(n, p_j))
+///
+/// where sim is the neighborhood overlap with earlier picks p_j, so k clones
+/// hanging off one hub don't crowd out the rest. Returned scores are the
+/// unpenalized S, in pick order.
 pub fn route_ensemble(
     from_node: &Node,
     task: &Task,
@@ -290,30 +594,43 @@ pub fn route_ensemble(
         return Vec::new();
     }
 
-    let required_cap = task.required_caps[0];
-
-    let mut scored: Vec<CandidateScore> = candidates
-        .values()
-        .filter(|node| {
-            node.has_capability(required_cap)
-                && node.can_accept_capability_work(required_cap)
-                && task.constraints.is_acceptable(node, required_cap)
-                && node.id != from_node.id
-        })
-        .map(|node| compute_routing_score(from_node, node, required_cap, &task.constraints))
-        .collect();
-
-    scored.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    let mut remaining = score_candidates(from_node, task, candidates);
+    let penalty = task.constraints.diversity_penalty;
+    if penalty <= 0.0 {
+        remaining.truncate(k);
+        return remaining;
+    }
 
-    scored.into_iter().take(k).collect()
+    let mut picks: Vec<CandidateScore> = Vec::with_capacity(k);
+    while picks.len() < k && !remaining.is_empty() {
+        let adjusted = |c: &CandidateScore| {
+            let similarity = picks
+                .iter()
+                .map(|p| neighborhood_similarity(&candidates[&c.node_id], &candidates[&p.node_id]))
+                .fold(0.0, f64::max);
+            c.score * (1.0 - penalty * similarity)
+        };
+        // First maximum wins, so ties keep the unpenalized order
+        let mut best = 0;
+        for i in 1..remaining.len() {
+            if adjusted(&remaining[i]) > adjusted(&remaining[best]) {
+                best = i;
+            }
+        }
+        picks.push(remaining.remove(best));
+    }
+    picks
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::capability::common;
-    use crate::node::NodeBuilder;
-    use crate::types::Weight;
+    use crate::connection::Connection;
+    use crate::node::{NodeBuilder, ThreatType};
+    use crate::types::{SignedScore, Weight};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
 
     fn setup_test_network() -> (Node, HashMap<NodeId, Node>) {
         let from_id = NodeId::from_index(0);
@@ -381,6 +698,119 @@ mod tests {
         assert_eq!(result.selected_node(), Some(NodeId::from_index(1)));
     }
 
+    #[test]
+    fn test_probabilistic_routing_shares_work() {
+        let from_node = Node::new(NodeId::from_index(0));
+        let cap = common::analysis();
+        let candidates: HashMap<_, _> = [0.9, 0.85, 0.8]
+            .iter()
+            .enumerate()
+            .map(|(i, &trust)| {
+                let node = NodeBuilder::new(NodeId::from_index(i as u64 + 1))
+                    .trust(Score::new(trust))
+                    .capability(cap.clone())
+                    .build();
+                (node.id, node)
+            })
+            .collect();
+        let task = Task::new(TaskId::random(), from_node.id, cap.id);
+
+        // Temperature on the scale of the gap between best and worst
+        let scores: Vec<f64> = score_candidates(&from_node, &task, &candidates)
+            .iter()
+            .map(|c| c.score)
+            .collect();
+        let temperature = scores[0] - scores[2];
+
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut wins: HashMap<NodeId, usize> = HashMap::new();
+        for _ in 0..2000 {
+            let result =
+                route_task_probabilistic(&from_node, &task, &candidates, temperature, &mut rng);
+            *wins.entry(result.selected_node().unwrap()).or_default() += 1;
+        }
+        let count = |i: u64| wins.get(&NodeId::from_index(i)).copied().unwrap_or(0);
+        assert!(count(1) > count(2) && count(2) > count(3), "{wins:?}");
+        assert!(count(3) > 100, "{wins:?}");
+
+        // Zero temperature is deterministic
+        let result = route_task_probabilistic(&from_node, &task, &candidates, 0.0, &mut rng);
+        assert_eq!(result.selected_node(), Some(NodeId::from_index(1)));
+    }
+
+    #[test]
+    fn test_load_shifts_routing() {
+        let (from_node, mut candidates) = setup_test_network();
+        let cap = common::analysis().id;
+        let task = Task::new(TaskId::random(), from_node.id, cap);
+        let busy_id = NodeId::from_index(1);
+
+        // Hammer the best node with incoming work
+        let busy = candidates.get_mut(&busy_id).unwrap();
+        for _ in 0..15 {
+            busy.handle_incoming_interaction(
+                from_node.id,
+                1.0,
+                0.0,
+                0.0,
+                Score::HALF,
+                SignedScore::ZERO,
+                Some(cap),
+            );
+        }
+        assert!(busy.capabilities[&cap].load.value() > 0.5);
+
+        let score = compute_routing_score(&from_node, busy, cap, &TaskConstraints::default());
+        assert!(score.components.availability < 0.5);
+
+        let result = route_task(&from_node, &task, &candidates);
+        assert_eq!(result.selected_node(), Some(NodeId::from_index(2)));
+
+        // Once the load decays, the best node wins again
+        let busy = candidates.get_mut(&busy_id).unwrap();
+        for _ in 0..20 {
+            busy.decay_load();
+        }
+        let result = route_task(&from_node, &task, &candidates);
+        assert_eq!(result.selected_node(), Some(busy_id));
+    }
+
+    #[test]
+    fn test_timeout_excludes_slow_node() {
+        let from_node = NodeBuilder::new(NodeId::from_index(0)).build();
+        let cap = common::analysis().id;
+        let busy_id = NodeId::from_index(1);
+        let idle_id = NodeId::from_index(2);
+
+        let mut busy = NodeBuilder::new(busy_id)
+            .trust(Score::new(0.95))
+            .capability(common::analysis())
+            .build();
+        busy.load = Score::new(0.5);
+        let idle = NodeBuilder::new(idle_id)
+            .trust(Score::new(0.3))
+            .capability(common::analysis())
+            .build();
+        assert!((idle.estimated_latency_ms() - 100.0).abs() < 1e-9);
+        assert!((busy.estimated_latency_ms() - 550.0).abs() < 1e-9);
+
+        let mut candidates = HashMap::new();
+        candidates.insert(busy_id, busy);
+        candidates.insert(idle_id, idle);
+
+        // Without a deadline, the trusted node wins despite its load
+        let task = Task::new(TaskId::random(), from_node.id, cap);
+        assert_eq!(route_task(&from_node, &task, &candidates).selected_node(), Some(busy_id));
+
+        // A tight timeout rules it out
+        let task = task.with_constraints(TaskConstraints::new().with_timeout(400));
+        let result = route_task(&from_node, &task, &candidates);
+        assert_eq!(result.selected_node(), Some(idle_id));
+        if let RoutingResult::Success(score) = result {
+            assert!((score.components.timeliness - 0.875).abs() < 1e-9);
+        }
+    }
+
     #[test]
     fn test_route_with_constraints() {
         let (from_node, candidates) = setup_test_network();
@@ -420,6 +850,74 @@ mod tests {
         assert!(!result.is_success());
     }
 
+    #[test]
+    fn test_single_node_routing() {
+        let from_node = NodeBuilder::new(NodeId::from_index(0))
+            .trust(Score::new(0.8))
+            .capability(common::analysis())
+            .build();
+        let task = Task::new(TaskId::random(), from_node.id, common::analysis().id);
+        let mut rng = StdRng::seed_from_u64(1);
+
+        // Neither an empty network nor one holding only the router has anyone to route to
+        let empty = HashMap::new();
+        let alone: HashMap<_, _> = [(from_node.id, from_node.clone())].into_iter().collect();
+        for candidates in [&empty, &alone] {
+            assert!(matches!(
+                route_task(&from_node, &task, candidates),
+                RoutingResult::NoCandidates
+            ));
+            let sampled = route_task_probabilistic(&from_node, &task, candidates, 0.5, &mut rng);
+            assert!(sampled.selected_node().is_none());
+            assert!(route_fan_out(&from_node, &task, candidates, 3).is_empty());
+            assert!(route_ensemble(&from_node, &task, candidates, 3).is_empty());
+            let diverse = Task::new(TaskId::random(), from_node.id, common::analysis().id)
+                .with_constraints(TaskConstraints::new().with_diversity_penalty(0.5));
+            assert!(route_ensemble(&from_node, &diverse, candidates, 3).is_empty());
+        }
+
+        // A single worker fills at most one ensemble slot
+        let mut one = alone;
+        let worker = NodeBuilder::new(NodeId::from_index(1))
+            .trust(Score::new(0.8))
+            .capability(common::analysis())
+            .build();
+        one.insert(worker.id, worker);
+        let top_k = route_ensemble(&from_node, &task, &one, 3);
+        assert_eq!(top_k.len(), 1);
+        assert_eq!(top_k[0].node_id, NodeId::from_index(1));
+        assert_eq!(route_ensemble(&from_node, &task, &one, 0).len(), 0);
+    }
+
+    #[test]
+    fn test_proven_capability_beats_lucky_one() {
+        let from_node = Node::new(NodeId::from_index(0));
+        let cap = common::analysis();
+        let with_record = |index: u64, quality: f64, volume: u32| {
+            let mut node = NodeBuilder::new(NodeId::from_index(index))
+                .trust(Score::new(0.8))
+                .capability(cap.clone())
+                .build();
+            let state = node.capabilities.get_mut(&cap.id).unwrap();
+            state.quality = Score::new(quality);
+            state.volume = volume;
+            node
+        };
+
+        // One use at 0.95 against 500 uses at 0.85
+        let lucky = with_record(1, 0.95, 1);
+        let proven = with_record(2, 0.85, 500);
+        let candidates: HashMap<_, _> = [lucky, proven].into_iter().map(|n| (n.id, n)).collect();
+
+        let task = Task::new(TaskId::random(), from_node.id, cap.id);
+        let result = route_task(&from_node, &task, &candidates);
+        assert_eq!(result.selected_node(), Some(NodeId::from_index(2)));
+
+        let scored = route_ensemble(&from_node, &task, &candidates, 2);
+        assert!(scored[1].components.capability_quality < 0.55);
+        assert!(scored[0].components.capability_quality > 0.8);
+    }
+
     #[test]
     fn test_route_ensemble() {
         let (from_node, candidates) = setup_test_network();
@@ -439,9 +937,152 @@ mod tests {
     }
 
     #[test]
-    fn test_connection_weight_affects_routing() {
-        use crate::connection::Connection;
+    fn test_fan_out_spreads_across_equal_workers() {
+        let from_node = NodeBuilder::new(NodeId::from_index(0)).build();
+        let candidates: HashMap<_, _> = (1..=5)
+            .map(|i| {
+                let node = NodeBuilder::new(NodeId::from_index(i))
+                    .trust(Score::new(0.8))
+                    .capability(common::analysis())
+                    .build();
+                (node.id, node)
+            })
+            .collect();
+        let workflow = crate::workflow::patterns::fan_out_fan_in(
+            from_node.id,
+            common::analysis().id,
+            3,
+            common::analysis().id,
+        );
+        let parallel: Vec<&Task> = workflow.steps[..3].iter().map(|s| &s.task).collect();
+
+        // Routed one at a time, every copy lands on the same node
+        let naive: HashSet<_> = parallel
+            .iter()
+            .filter_map(|t| route_task(&from_node, t, &candidates).selected_node())
+            .collect();
+        assert_eq!(naive.len(), 1);
+
+        let workers = route_fan_out(&from_node, parallel[0], &candidates, parallel.len());
+        assert_eq!(workers.len(), 3);
+        assert_eq!(workers.iter().collect::<HashSet<_>>().len(), 3);
+
+        // A lone worker takes copies until its estimated load hits capacity
+        let single: HashMap<_, _> =
+            candidates.into_iter().filter(|(id, _)| *id == NodeId::from_index(1)).collect();
+        assert_eq!(route_fan_out(&from_node, parallel[0], &single, 100).len(), 19);
+    }
 
+    #[test]
+    fn test_diverse_ensemble_spreads_past_hub_clones() {
+        let from_node = NodeBuilder::new(NodeId::from_index(0))
+            .capability(common::analysis())
+            .build();
+        let hub = NodeId::from_index(100);
+
+        // Three near-identical nodes hanging off one hub, two independent ones
+        let mut candidates = HashMap::new();
+        for (i, trust, neighbor) in [
+            (1, 0.9, hub),
+            (2, 0.9, hub),
+            (3, 0.9, hub),
+            (4, 0.8, NodeId::from_index(101)),
+            (5, 0.8, NodeId::from_index(102)),
+        ] {
+            let mut node = NodeBuilder::new(NodeId::from_index(i))
+                .trust(Score::new(trust))
+                .capability(common::analysis())
+                .build();
+            node.get_or_create_connection(neighbor);
+            candidates.insert(node.id, node);
+        }
+
+        let picked = |task: &Task| {
+            let mut ids: Vec<_> = route_ensemble(&from_node, task, &candidates, 3)
+                .iter()
+                .map(|c| c.node_id)
+                .collect();
+            ids.sort();
+            ids
+        };
+
+        let task = Task::new(TaskId::random(), from_node.id, common::analysis().id);
+        let greedy = picked(&task);
+        assert_eq!(greedy, (1..=3).map(NodeId::from_index).collect::<Vec<_>>());
+
+        let task = task.with_constraints(TaskConstraints::new().with_diversity_penalty(0.5));
+        let diverse = picked(&task);
+        assert_eq!(diverse.len(), 3);
+        assert_eq!(diverse.iter().filter(|id| greedy.contains(id)).count(), 1);
+        assert!(diverse.contains(&NodeId::from_index(4)));
+        assert!(diverse.contains(&NodeId::from_index(5)));
+    }
+
+    #[test]
+    fn test_min_trust_discounts_threat() {
+        let (mut from_node, candidates) = setup_test_network();
+        let suspect = NodeId::from_index(1);
+        let task = Task::new(TaskId::random(), from_node.id, common::analysis().id)
+            .with_constraints(TaskConstraints::new().with_min_trust(Score::new(0.5)));
+        assert_eq!(route_task(&from_node, &task, &candidates).selected_node(), Some(suspect));
+
+        // 0.9 trust × (1 - 0.5 threat) falls below the 0.5 floor
+        from_node.update_threat_belief(suspect, ThreatType::Cheating, 0.5, None);
+        assert!(!route_task(&from_node, &task, &candidates).is_success());
+    }
+
+    #[test]
+    fn test_primed_connection_mildly_penalized() {
+        let (mut from_node, candidates) = setup_test_network();
+        let candidate = &candidates[&NodeId::from_index(1)];
+        let cap = common::analysis().id;
+        let constraints = TaskConstraints::default();
+        let baseline = compute_routing_score(&from_node, candidate, cap, &constraints);
+
+        // Primed by signals but no threat belief yet
+        from_node.get_or_create_connection(candidate.id).increase_priming(1.0);
+        let primed = compute_routing_score(&from_node, candidate, cap, &constraints);
+        assert_eq!(from_node.get_threat_level(&candidate.id), 0.0);
+        assert!(
+            (primed.components.defense - (1.0 - CONNECTION_PRIMING_PENALTY)).abs() < 1e-9
+        );
+        assert!(primed.score < baseline.score);
+        assert!(primed.score > baseline.score / 2.0);
+    }
+
+    #[test]
+    fn test_specialist_preferred_for_its_category() {
+        let from_node = NodeBuilder::new(NodeId::from_index(0)).build();
+        let specialist = NodeBuilder::new(NodeId::from_index(1))
+            .trust(Score::new(0.6))
+            .capability(common::analysis())
+            .build();
+        let generalist = NodeBuilder::new(NodeId::from_index(2))
+            .trust(Score::new(0.75))
+            .capability(common::analysis())
+            .capability(common::generation())
+            .capability(common::transformation())
+            .capability(common::validation())
+            .build();
+        let candidates: HashMap<_, _> =
+            [specialist, generalist].into_iter().map(|n| (n.id, n)).collect();
+
+        let task = Task::new(TaskId::random(), from_node.id, common::analysis().id);
+        let plain = route_task(&from_node, &task, &candidates);
+        assert_eq!(plain.selected_node(), Some(NodeId::from_index(2)));
+
+        let task = task.with_constraints(
+            TaskConstraints::new().with_preferred_category(CapabilityCategory::Analysis),
+        );
+        let RoutingResult::Success(best) = route_task(&from_node, &task, &candidates) else {
+            panic!("expected a candidate");
+        };
+        assert_eq!(best.node_id, NodeId::from_index(1));
+        assert_eq!(best.components.specialization, 1.0 + SPECIALIZATION_BONUS);
+    }
+
+    #[test]
+    fn test_connection_weight_affects_routing() {
         let from_id = NodeId::from_index(0);
         let mut from_node = NodeBuilder::new(from_id)
             .trust(Score::new(0.8))
@@ -492,4 +1133,88 @@ mod tests {
         assert!((score1.components.connection - 0.9).abs() < 0.01);
         assert!((score2.components.connection - 0.2).abs() < 0.01);
     }
+
+    #[test]
+    fn test_capability_reciprocity_affects_routing() {
+        let analysis = common::analysis();
+        let generation = common::generation();
+        let selective = NodeId::from_index(1);
+        let even = NodeId::from_index(2);
+
+        // Generous on analysis, free-rides on generation
+        let mut selective_conn = Connection::new(selective);
+        selective_conn.capability_reciprocity.insert(analysis.id, 1.0);
+        selective_conn.capability_reciprocity.insert(generation.id, -1.0);
+
+        let from_node = NodeBuilder::new(NodeId::from_index(0))
+            .connection(selective, selective_conn)
+            .connection(even, Connection::new(even))
+            .build();
+
+        let mut candidates = HashMap::new();
+        for id in [selective, even] {
+            let node = NodeBuilder::new(id)
+                .trust(Score::new(0.8))
+                .capability(analysis.clone())
+                .capability(generation.clone())
+                .build();
+            candidates.insert(id, node);
+        }
+
+        let task = Task::new(TaskId::random(), from_node.id, analysis.id);
+        assert_eq!(route_task(&from_node, &task, &candidates).selected_node(), Some(selective));
+
+        let task = Task::new(TaskId::random(), from_node.id, generation.id);
+        assert_eq!(route_task(&from_node, &task, &candidates).selected_node(), Some(even));
+    }
+
+    #[test]
+    fn test_multi_capability_routing() {
+        let from_node = NodeBuilder::new(NodeId::from_index(0))
+            .trust(Score::new(0.8))
+            .build();
+
+        // Strong single-capability nodes
+        let analyst = NodeBuilder::new(NodeId::from_index(1))
+            .trust(Score::new(0.95))
+            .capability(common::analysis())
+            .build();
+        let validator = NodeBuilder::new(NodeId::from_index(2))
+            .trust(Score::new(0.95))
+            .capability(common::validation())
+            .build();
+
+        // Weaker node holding both capabilities
+        let generalist = NodeBuilder::new(NodeId::from_index(3))
+            .trust(Score::new(0.5))
+            .capability(common::analysis())
+            .capability(common::validation())
+            .build();
+
+        let mut candidates = HashMap::new();
+        candidates.insert(analyst.id, analyst);
+        candidates.insert(validator.id, validator);
+        candidates.insert(generalist.id, generalist);
+
+        let task = Task::new(TaskId::random(), from_node.id, common::analysis().id)
+            .require_cap(common::validation().id);
+
+        let result = route_task(&from_node, &task, &candidates);
+        assert_eq!(result.selected_node(), Some(NodeId::from_index(3)));
+
+        // Ensemble routing applies the same filter
+        let top_k = route_ensemble(&from_node, &task, &candidates, 3);
+        assert_eq!(top_k.len(), 1);
+        assert_eq!(top_k[0].components.capability_qualities.len(), 2);
+
+        // Default aggregation is the product of per-capability quality (0.5 × 0.5)
+        assert!((top_k[0].components.capability_quality - 0.25).abs() < 1e-9);
+
+        // Mean aggregation is configurable
+        let mean_task = task.with_constraints(
+            TaskConstraints::new().with_capability_aggregation(CapabilityAggregation::Mean),
+        );
+        let top_k = route_ensemble(&from_node, &mean_task, &candidates, 3);
+        assert!((top_k[0].components.capability_quality - 0.5).abs() < 1e-9);
+    }
 }
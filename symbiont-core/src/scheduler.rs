@@ -0,0 +1,222 @@
+//! Bounded task backlog for nodes.
+//!
+//! A node that is momentarily too busy queues incoming tasks instead of
+//! refusing them, then starts them tick by tick as its load allows.
+
+use crate::constants::TASK_QUEUE_CAPACITY;
+use crate::routing::Task;
+use crate::types::{CapabilityId, TaskId};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use thiserror::Error;
+
+/// Why a node refused to queue a task
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum EnqueueError {
+    /// The node is not an active participant
+    #[error("node is not active")]
+    Inactive,
+    /// The node lacks a capability the task requires
+    #[error("node lacks required capability {0}")]
+    MissingCapability(CapabilityId),
+    /// The backlog is at capacity
+    #[error("task queue full ({capacity} tasks)")]
+    QueueFull {
+        /// Queue capacity
+        capacity: usize,
+    },
+}
+
+/// A task waiting in a node's backlog
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueuedTask {
+    /// Task identifier
+    pub task_id: TaskId,
+    /// Capabilities the task draws on
+    pub capabilities: Vec<CapabilityId>,
+    /// Tick the task was queued at
+    pub enqueued_tick: u64,
+}
+
+impl QueuedTask {
+    /// Queue entry for a task
+    pub fn new(task: &Task, tick: u64) -> Self {
+        Self {
+            task_id: task.id,
+            capabilities: task.required_caps.clone(),
+            enqueued_tick: tick,
+        }
+    }
+}
+
+/// First-in, first-out backlog holding at most `capacity` tasks
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskQueue {
+    tasks: VecDeque<QueuedTask>,
+    capacity: usize,
+}
+
+impl Default for TaskQueue {
+    fn default() -> Self {
+        Self::new(TASK_QUEUE_CAPACITY)
+    }
+}
+
+impl TaskQueue {
+    /// Create an empty queue
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            tasks: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Maximum number of queued tasks
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of queued tasks
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// Check if nothing is queued
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    /// Check if no more tasks fit
+    pub fn is_full(&self) -> bool {
+        self.tasks.len() >= self.capacity
+    }
+
+    /// Add a task at the back, returning its position
+    pub fn push(&mut self, task: QueuedTask) -> Result<usize, EnqueueError> {
+        if self.is_full() {
+            return Err(EnqueueError::QueueFull {
+                capacity: self.capacity,
+            });
+        }
+        self.tasks.push_back(task);
+        Ok(self.tasks.len() - 1)
+    }
+
+    /// Oldest queued task
+    pub fn front(&self) -> Option<&QueuedTask> {
+        self.tasks.front()
+    }
+
+    /// Remove the oldest queued task
+    pub fn pop(&mut self) -> Option<QueuedTask> {
+        self.tasks.pop_front()
+    }
+
+    /// Queued tasks, oldest first
+    pub fn iter(&self) -> impl Iterator<Item = &QueuedTask> {
+        self.tasks.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capability::common;
+    use crate::constants::{TASKS_PER_TICK, TASK_QUEUE_CAPACITY};
+    use crate::node::{Node, NodeStatus};
+    use crate::routing::{route_task, Task};
+    use crate::types::{NodeId, Score};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_burst_queues_and_drains_over_ticks() {
+        let cap = common::analysis();
+        let mut node = Node::with_capabilities(NodeId::from_index(1), vec![cap.clone()]);
+        let origin = NodeId::from_index(0);
+
+        // A spike leaves the node too busy to start anything right now
+        node.load = Score::new(0.96);
+        assert!(!node.can_accept_work());
+
+        let burst: Vec<_> = (0..6).map(|i| Task::new(TaskId::new(i), origin, cap.id)).collect();
+        for (position, task) in burst.iter().enumerate() {
+            assert_eq!(node.enqueue_task(task), Ok(position));
+        }
+        assert!(node.drain_tasks().is_empty());
+
+        // As load decays, tasks start in arrival order, a few per tick
+        let mut started = Vec::new();
+        let mut ticks = 0;
+        while !node.task_queue.is_empty() {
+            node.decay_load();
+            let batch = node.drain_tasks();
+            assert!(batch.len() <= TASKS_PER_TICK);
+            assert!(node.load.value() < 1.0);
+            started.extend(batch);
+            ticks += 1;
+            assert!(ticks < 50, "queue never drained");
+        }
+        assert_eq!(started, burst.iter().map(|t| t.id).collect::<Vec<_>>());
+        assert!(ticks >= burst.len() / TASKS_PER_TICK);
+        assert!(node.capabilities[&cap.id].load.value() > 0.0);
+    }
+
+    #[test]
+    fn test_enqueue_respects_capacity_and_capabilities() {
+        let cap = common::analysis();
+        let mut node = Node::with_capabilities(NodeId::from_index(1), vec![cap.clone()]);
+        let origin = NodeId::from_index(0);
+
+        for i in 0..TASK_QUEUE_CAPACITY as u64 {
+            node.enqueue_task(&Task::new(TaskId::new(i), origin, cap.id)).unwrap();
+        }
+        assert!(node.task_queue.is_full());
+        assert!(!node.can_queue_task());
+        let overflow = Task::new(TaskId::new(99), origin, cap.id);
+        assert_eq!(
+            node.enqueue_task(&overflow),
+            Err(EnqueueError::QueueFull { capacity: TASK_QUEUE_CAPACITY })
+        );
+
+        let other = common::validation().id;
+        let mut idle = Node::with_capabilities(NodeId::from_index(2), vec![cap.clone()]);
+        let wrong = Task::new(TaskId::new(1), origin, other);
+        assert_eq!(idle.enqueue_task(&wrong), Err(EnqueueError::MissingCapability(other)));
+
+        idle.status = NodeStatus::Expelled;
+        let task = Task::new(TaskId::new(2), origin, cap.id);
+        assert_eq!(idle.enqueue_task(&task), Err(EnqueueError::Inactive));
+    }
+
+    #[test]
+    fn test_routing_prefers_shorter_queue() {
+        let cap = common::analysis();
+        let origin = Node::new(NodeId::from_index(0));
+        let backlogged_id = NodeId::from_index(1);
+        let free_id = NodeId::from_index(2);
+
+        let mut backlogged = Node::with_capabilities(backlogged_id, vec![cap.clone()]);
+        backlogged.trust = Score::new(0.6);
+        let mut free = Node::with_capabilities(free_id, vec![cap.clone()]);
+        free.trust = Score::new(0.5);
+
+        let mut candidates = HashMap::new();
+        candidates.insert(backlogged_id, backlogged);
+        candidates.insert(free_id, free);
+        let task = Task::new(TaskId::new(100), origin.id, cap.id);
+        assert_eq!(route_task(&origin, &task, &candidates).selected_node(), Some(backlogged_id));
+
+        // A deep backlog outweighs the trust edge
+        let backlogged = candidates.get_mut(&backlogged_id).unwrap();
+        for i in 0..4 {
+            backlogged.enqueue_task(&Task::new(TaskId::new(i), origin.id, cap.id)).unwrap();
+        }
+        assert_eq!(route_task(&origin, &task, &candidates).selected_node(), Some(free_id));
+
+        // A saturated node with queue room stays routable rather than dropped
+        let free = candidates.get_mut(&free_id).unwrap();
+        free.load = Score::new(0.99);
+        candidates.remove(&backlogged_id);
+        assert_eq!(route_task(&origin, &task, &candidates).selected_node(), Some(free_id));
+    }
+}
@@ -4,13 +4,15 @@
 //! with a diversity cap to prevent high trust without broad interaction.
 
 use crate::constants::{
-    CONFIDENCE_MEMORY, TRUST_WEIGHT_DIVERSITY, TRUST_WEIGHT_QUALITY, TRUST_WEIGHT_RECIPROCITY,
-    TRUST_WEIGHT_SOCIAL,
+    AFFIRMATION_HALF_WEIGHT, CONFIDENCE_MEMORY, GLOBAL_TRUST_DAMPING, TRUST_WEIGHT_DIVERSITY,
+    TRUST_WEIGHT_QUALITY, TRUST_WEIGHT_RECIPROCITY, TRUST_WEIGHT_SOCIAL,
 };
-use crate::math::{apply_diversity_cap, sigmoid};
+use crate::math::{apply_diversity_cap, finite_mean, finite_or, percentile, sigmoid};
 use crate::node::Node;
 use crate::types::{NodeId, Score};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
 
 /// Compute global trust for a node
 ///
@@ -18,18 +20,77 @@ use std::collections::HashMap;
 ///
 /// With diversity cap: T_final = min(T(n), D_diversity + 0.3)
 pub fn compute_trust(node: &Node) -> Score {
+    explain_trust(node).trust
+}
+
+/// Which cap, if any, bound a node's trust
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrustBound {
+    /// The weighted components were used as-is
+    Uncapped,
+    /// Limited by the diversity cap D + 0.3
+    Diversity,
+    /// Limited by the node's trust cap
+    TrustCap,
+}
+
+/// Contribution of each component to a node's trust
+///
+/// Components are already weighted, so `raw` is their sum.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrustBreakdown {
+    /// w_Q × Q_agg / Σw
+    pub quality: f64,
+    /// w_R × σ(R_agg) / Σw
+    pub reciprocity: f64,
+    /// w_S × S_social / Σw
+    pub social: f64,
+    /// w_D × D_diversity / Σw
+    pub diversity: f64,
+    /// Trust before either cap
+    pub raw: Score,
+    /// Diversity cap min(1, D + 0.3)
+    pub diversity_cap: Score,
+    /// The node's trust cap
+    pub trust_cap: Score,
+    /// Which cap bound the result
+    pub bound: TrustBound,
+    /// Final trust, as returned by [`compute_trust`]
+    pub trust: Score,
+}
+
+impl fmt::Display for TrustBreakdown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Q {:.3} + R {:.3} + S {:.3} + D {:.3} = {:.3}",
+            self.quality,
+            self.reciprocity,
+            self.social,
+            self.diversity,
+            self.raw.value()
+        )?;
+        match self.bound {
+            TrustBound::Uncapped => Ok(()),
+            TrustBound::Diversity => {
+                write!(f, ", capped by diversity at {:.3}", self.diversity_cap.value())
+            }
+            TrustBound::TrustCap => {
+                write!(f, ", capped by trust cap at {:.3}", self.trust_cap.value())
+            }
+        }
+    }
+}
+
+/// Break a node's trust down into weighted components and caps
+pub fn explain_trust(node: &Node) -> TrustBreakdown {
     // Aggregate quality (weighted by capability volume)
     let q_agg = node.aggregate_capability_quality();
 
-    // Aggregate reciprocity from connections
-    let r_agg = if node.connections.is_empty() {
-        0.0
-    } else {
-        let sum: f64 = node.connections.values().map(|c| c.r).sum();
-        sum / node.connections.len() as f64
-    };
+    // Aggregate reciprocity from connections; non-finite r counts as absent
+    let r_agg = finite_mean(node.connections.values().map(|c| c.r)).unwrap_or(0.0);
 
-    // Social proof (could be computed from affirmations, for now use connection quality)
+    // Social proof from connection quality and received affirmations
     let s_social = compute_social_proof(node);
 
     // Diversity
@@ -41,30 +102,115 @@ pub fn compute_trust(node: &Node) -> Score {
         + TRUST_WEIGHT_SOCIAL
         + TRUST_WEIGHT_DIVERSITY;
 
-    let trust = (TRUST_WEIGHT_QUALITY * q_agg.value()
-        + TRUST_WEIGHT_RECIPROCITY * sigmoid(r_agg).value()
-        + TRUST_WEIGHT_SOCIAL * s_social.value()
-        + TRUST_WEIGHT_DIVERSITY * d_diversity.value())
-        / total_weight;
-
-    let base_trust = Score::new(trust);
+    // Non-finite inputs fall back to neutral rather than poisoning the sum
+    let quality = TRUST_WEIGHT_QUALITY * finite_or(q_agg.value(), 0.5) / total_weight;
+    let reciprocity = TRUST_WEIGHT_RECIPROCITY * sigmoid(r_agg).value() / total_weight;
+    let social = TRUST_WEIGHT_SOCIAL * finite_or(s_social.value(), 0.0) / total_weight;
+    let diversity = TRUST_WEIGHT_DIVERSITY * d_diversity.value() / total_weight;
+    let raw = Score::new(quality + reciprocity + social + diversity);
+
+    // Apply diversity cap and trust cap, noting the tighter one that binds
+    let diversity_cap = apply_diversity_cap(Score::ONE, d_diversity);
+    let trust_cap = node.trust_cap;
+    let (trust, bound) = if raw.value() <= diversity_cap.value().min(trust_cap.value()) {
+        (raw, TrustBound::Uncapped)
+    } else if diversity_cap.value() <= trust_cap.value() {
+        (diversity_cap, TrustBound::Diversity)
+    } else {
+        (trust_cap, TrustBound::TrustCap)
+    };
 
-    // Apply diversity cap and trust cap
-    let capped = apply_diversity_cap(base_trust, d_diversity);
-    Score::new(capped.value().min(node.trust_cap.value()))
+    debug_assert!(trust.value().is_finite(), "non-finite trust for {}", node.id);
+    TrustBreakdown {
+        quality,
+        reciprocity,
+        social,
+        diversity,
+        raw,
+        diversity_cap,
+        trust_cap,
+        bound,
+        trust,
+    }
 }
 
 /// Compute social proof score
 ///
-/// Based on the quality of connections and their trust levels
+/// S_social = (1 - β) × Q_conn + β × A_recent
+///
+/// WHERE:
+///     Q_conn   = mean connection quality (0 without connections)
+///     A_recent = trust-weighted mean strength of recent affirmations
+///     β        = W / (W + AFFIRMATION_HALF_WEIGHT), W = decayed affirmer trust
 fn compute_social_proof(node: &Node) -> Score {
-    if node.connections.is_empty() {
-        return Score::ZERO;
+    let connection_quality =
+        finite_mean(node.connections.values().map(|c| c.q.value())).unwrap_or(0.0);
+
+    let recent = &node.recent_affirmations;
+    let Some(affirmed) = recent.mean() else {
+        return Score::new(connection_quality);
+    };
+
+    let beta = recent.influence();
+    Score::new((1.0 - beta) * connection_quality + beta * affirmed)
+}
+
+/// Recently received affirmations, fading each tick
+///
+/// Holds decayed running sums rather than individual affirmations, so a
+/// burst counts fully at once and then fades by AFFIRMATION_DECAY per tick.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct AffirmationStore {
+    /// W = Σ decayed affirmer trust
+    pub weight: f64,
+    /// Σ decayed affirmer trust × strength
+    pub weighted_strength: f64,
+}
+
+impl AffirmationStore {
+    /// Add a batch of (affirmer trust, strength) pairs
+    pub fn record(&mut self, affirmations: &[(Score, Score)]) {
+        for (trust, strength) in affirmations {
+            self.weight += trust.value();
+            self.weighted_strength += trust.value() * strength.value();
+        }
+    }
+
+    /// Fade everything held by `factor`
+    pub fn decay(&mut self, factor: f64) {
+        self.weight *= factor;
+        self.weighted_strength *= factor;
+    }
+
+    /// A_recent = Σ(T_affirmer × strength) / Σ(T_affirmer), None if empty
+    pub fn mean(&self) -> Option<f64> {
+        (self.weight > 0.0).then(|| self.weighted_strength / self.weight)
+    }
+
+    /// Share of social proof taken by recent affirmations
+    ///
+    /// β = W / (W + AFFIRMATION_HALF_WEIGHT)
+    pub fn influence(&self) -> f64 {
+        self.weight / (self.weight + AFFIRMATION_HALF_WEIGHT)
+    }
+}
+
+/// Trust-weighted mean strength of a batch of affirmations
+///
+/// A_mean = Σ(T_affirmer × strength) / Σ(T_affirmer)
+///
+/// Takes (affirmer trust, strength) pairs; None if the total trust is zero.
+pub fn affirmation_mean(affirmations: &[(Score, Score)]) -> Option<f64> {
+    let total_trust: f64 = affirmations.iter().map(|(t, _)| t.value()).sum();
+    if total_trust <= 0.0 {
+        return None;
     }
 
-    // Use mean connection quality as proxy for social proof
-    let sum: f64 = node.connections.values().map(|c| c.q.value()).sum();
-    Score::new(sum / node.connections.len() as f64)
+    let weighted: f64 = affirmations
+        .iter()
+        .map(|(t, s)| t.value() * s.value())
+        .sum();
+    Some(weighted / total_trust)
 }
 
 /// Update self-confidence based on affirmations
@@ -79,6 +225,16 @@ pub fn update_confidence(current: Score, affirmation_strength: f64) -> Score {
     Score::new(new_conf)
 }
 
+/// Highest trust a node may hold: min(trust cap, diversity cap), or 0 once
+/// the node is no longer active
+pub fn trust_ceiling(node: &Node) -> Score {
+    if !node.status.is_active() {
+        return Score::ZERO;
+    }
+    let diversity_cap = apply_diversity_cap(Score::ONE, node.diversity_score());
+    Score::new(diversity_cap.value().min(node.trust_cap.value()))
+}
+
 /// Compute trust for all nodes in a network
 pub fn compute_network_trust(nodes: &HashMap<NodeId, Node>) -> HashMap<NodeId, Score> {
     nodes
@@ -87,6 +243,77 @@ pub fn compute_network_trust(nodes: &HashMap<NodeId, Node>) -> HashMap<NodeId, S
         .collect()
 }
 
+/// Compute transitive trust over the connection graph (EigenTrust-style)
+///
+/// t(k+1) = (1 - α) × Cᵀ t(k) + α × t_local
+///
+/// WHERE:
+///     C_ij    = w_ij / Σ_j w_ij  (row-normalized connection weights)
+///     t_local = compute_trust for each node, also the starting vector
+///     α       = GLOBAL_TRUST_DAMPING
+///
+/// Trust held by nodes without outgoing weight is spread in proportion to
+/// local trust, so total trust is conserved and scores stay on the local scale.
+/// Each result is then clamped to [`trust_ceiling`], so endorsements can't
+/// lift a node past its own caps.
+pub fn compute_global_trust(
+    nodes: &HashMap<NodeId, Node>,
+    iterations: usize,
+) -> HashMap<NodeId, Score> {
+    let mut ids: Vec<NodeId> = nodes.keys().cloned().collect();
+    ids.sort();
+    let index: HashMap<NodeId, usize> = ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+
+    let local: Vec<f64> = ids.iter().map(|id| compute_trust(&nodes[id]).value()).collect();
+    let total_local: f64 = local.iter().sum();
+    let n = ids.len() as f64;
+    let personalization: Vec<f64> = local
+        .iter()
+        .map(|t| if total_local > 0.0 { t / total_local } else { 1.0 / n })
+        .collect();
+
+    // Row-normalized outgoing weights, ignoring partners outside the network
+    let edges: Vec<Vec<(usize, f64)>> = ids
+        .iter()
+        .map(|id| {
+            let mut out: Vec<(usize, f64)> = nodes[id]
+                .connections
+                .iter()
+                .filter_map(|(partner, conn)| index.get(partner).map(|&j| (j, conn.w.value())))
+                .filter(|(_, w)| *w > 0.0)
+                .collect();
+            out.sort_by_key(|(j, _)| *j);
+            let sum: f64 = out.iter().map(|(_, w)| w).sum();
+            out.iter_mut().for_each(|(_, w)| *w /= sum);
+            out
+        })
+        .collect();
+
+    let mut trust = local.clone();
+    for _ in 0..iterations {
+        let mut next = vec![0.0; ids.len()];
+        let mut dangling = 0.0;
+        for (i, out) in edges.iter().enumerate() {
+            if out.is_empty() {
+                dangling += trust[i];
+            }
+            for &(j, c) in out {
+                next[j] += c * trust[i];
+            }
+        }
+        for (j, t) in next.iter_mut().enumerate() {
+            let inflow = *t + dangling * personalization[j];
+            *t = (1.0 - GLOBAL_TRUST_DAMPING) * inflow + GLOBAL_TRUST_DAMPING * local[j];
+        }
+        trust = next;
+    }
+
+    ids.into_iter()
+        .zip(trust)
+        .map(|(id, t)| (id, Score::new(t.min(trust_ceiling(&nodes[&id]).value()))))
+        .collect()
+}
+
 /// Trust metrics for a network
 #[derive(Debug, Clone, Default)]
 pub struct TrustMetrics {
@@ -98,12 +325,20 @@ pub struct TrustMetrics {
     pub min_trust: f64,
     /// Maximum trust
     pub max_trust: f64,
+    /// Median trust
+    pub median_trust: f64,
+    /// 10th percentile of trust
+    pub p10_trust: f64,
+    /// 90th percentile of trust
+    pub p90_trust: f64,
     /// Number of nodes
     pub node_count: usize,
     /// Number of high-trust nodes (> 0.7)
     pub high_trust_count: usize,
     /// Number of low-trust nodes (< 0.3)
     pub low_trust_count: usize,
+    /// Trust scores sorted ascending, for percentile queries
+    sorted: Vec<f64>,
 }
 
 impl TrustMetrics {
@@ -126,17 +361,29 @@ impl TrustMetrics {
         let high_trust_count = values.iter().filter(|&&v| v > 0.7).count();
         let low_trust_count = values.iter().filter(|&&v| v < 0.3).count();
 
+        let mut sorted = values.clone();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+
         Self {
             mean_trust,
             trust_variance,
             min_trust,
             max_trust,
+            median_trust: percentile(&sorted, 0.5),
+            p10_trust: percentile(&sorted, 0.1),
+            p90_trust: percentile(&sorted, 0.9),
             node_count: values.len(),
             high_trust_count,
             low_trust_count,
+            sorted,
         }
     }
 
+    /// Trust at percentile p in [0, 1], interpolating between nodes
+    pub fn percentile(&self, p: f64) -> f64 {
+        percentile(&self.sorted, p)
+    }
+
     /// Compute metrics from a network
     pub fn from_network(nodes: &HashMap<NodeId, Node>) -> Self {
         let trust_scores = compute_network_trust(nodes);
@@ -146,7 +393,8 @@ impl TrustMetrics {
 
 /// Check if a node has achieved stable trust
 ///
-/// A node has stable trust if its trust variance over recent history is low
+/// Proxies stability by low quality variance over recent history; use
+/// `Node::is_trust_stable` to check the computed trust values themselves
 pub fn is_trust_stable(node: &Node, variance_threshold: f64) -> bool {
     let quality_variance = node.history.quality_variance(50);
     quality_variance < variance_threshold
@@ -157,8 +405,8 @@ pub fn trust_delta(current: Score, previous: Score) -> f64 {
     current.value() - previous.value()
 }
 
-/// Categories of trust level
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Categories of trust level, ordered from lowest to highest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum TrustLevel {
     /// Very low trust (0 - 0.2)
     VeryLow,
@@ -173,6 +421,15 @@ pub enum TrustLevel {
 }
 
 impl TrustLevel {
+    /// All levels, from lowest to highest
+    pub const ALL: [TrustLevel; 5] = [
+        TrustLevel::VeryLow,
+        TrustLevel::Low,
+        TrustLevel::Medium,
+        TrustLevel::High,
+        TrustLevel::VeryHigh,
+    ];
+
     /// Categorize a trust score
     pub fn from_score(score: Score) -> Self {
         match score.value() {
@@ -183,6 +440,24 @@ impl TrustLevel {
             _ => TrustLevel::VeryHigh,
         }
     }
+
+    /// Bounds (low, high) of the scores in this level
+    ///
+    /// Each level covers low ≤ T < high, except VeryHigh, which includes 1.0.
+    pub fn to_score_range(self) -> (f64, f64) {
+        match self {
+            TrustLevel::VeryLow => (0.0, 0.2),
+            TrustLevel::Low => (0.2, 0.4),
+            TrustLevel::Medium => (0.4, 0.6),
+            TrustLevel::High => (0.6, 0.8),
+            TrustLevel::VeryHigh => (0.8, 1.0),
+        }
+    }
+
+    /// Check if a score reaches at least this level
+    pub fn min_required(self, score: Score) -> bool {
+        TrustLevel::from_score(score) >= self
+    }
 }
 
 #[cfg(test)]
@@ -190,6 +465,112 @@ mod tests {
     use super::*;
     use crate::capability::common;
     use crate::connection::Connection;
+    use crate::types::Weight;
+
+    #[test]
+    fn test_affirmation_mean_weights_by_trust() {
+        let mean = affirmation_mean(&[
+            (Score::new(0.9), Score::ONE),
+            (Score::new(0.1), Score::ZERO),
+        ])
+        .unwrap();
+        assert!((mean - 0.9).abs() < 1e-12);
+
+        assert!(affirmation_mean(&[(Score::ZERO, Score::ONE)]).is_none());
+    }
+
+    #[test]
+    fn test_social_proof_includes_affirmations() {
+        let mut node = Node::new(NodeId::from_index(1));
+        let partner = NodeId::from_index(2);
+        let mut conn = Connection::new(partner);
+        conn.q = Score::new(0.5);
+        node.connections.insert(partner, conn);
+
+        assert!((compute_social_proof(&node).value() - 0.5).abs() < 1e-12);
+
+        node.receive_affirmations(&[(Score::new(0.9), Score::new(0.9))]);
+        let beta = 0.9 / (0.9 + AFFIRMATION_HALF_WEIGHT);
+        let expected = (1.0 - beta) * 0.5 + beta * 0.9;
+        assert!((compute_social_proof(&node).value() - expected).abs() < 1e-12);
+
+        // Without fresh affirmations social proof drifts back
+        for _ in 0..200 {
+            node.decay_affirmations();
+        }
+        assert!((compute_social_proof(&node).value() - 0.5).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_affirmation_burst_outpaces_connection_drift() {
+        let partner = NodeId::from_index(2);
+        let mut node = Node::new(NodeId::from_index(1));
+        node.connections.insert(partner, Connection::new(partner));
+
+        // Ten good interactions nudge connection quality up through its EMA
+        let mut drifted = node.clone();
+        for _ in 0..10 {
+            drifted.connections.get_mut(&partner).unwrap().update_quality(Score::new(0.9));
+        }
+
+        // One tick's burst of affirmations from trusted peers
+        let mut affirmed = node.clone();
+        affirmed.receive_affirmations(&[(Score::new(0.9), Score::new(0.9)); 5]);
+
+        // Compare before the diversity cap, which binds both equally here
+        let raw = |n: &Node| explain_trust(n).raw.value();
+        assert!(raw(&drifted) > raw(&node));
+        assert!(raw(&affirmed) > raw(&drifted));
+    }
+
+    #[test]
+    fn test_global_trust_flows_to_endorsed_node() {
+        let mut nodes = HashMap::new();
+        let endorsed = NodeId::from_index(0);
+
+        // Three capable nodes all put their weight on the endorsed node
+        for i in 1..=3 {
+            let id = NodeId::from_index(i);
+            let mut node = Node::with_capabilities(id, vec![common::analysis()]);
+            if let Some(state) = node.capabilities.get_mut(&common::analysis().id) {
+                state.quality = Score::new(0.9);
+            }
+            let mut conn = Connection::new(endorsed);
+            conn.w = Weight::new(1.0);
+            node.connections.insert(endorsed, conn);
+            nodes.insert(id, node);
+        }
+
+        // The endorsed node's own record is poor, leaving room under its caps;
+        // it spreads its weight thinly back
+        let mut node = Node::with_capabilities(endorsed, vec![common::analysis()]);
+        if let Some(state) = node.capabilities.get_mut(&common::analysis().id) {
+            state.quality = Score::ZERO;
+            state.volume = 10;
+        }
+        for i in 1..=3 {
+            node.connections.insert(NodeId::from_index(i), Connection::new(NodeId::from_index(i)));
+        }
+        nodes.insert(endorsed, node);
+
+        let local = compute_network_trust(&nodes);
+        let global = compute_global_trust(&nodes, 50);
+
+        assert!(global[&endorsed].value() > local[&endorsed].value());
+        for i in 1..=3 {
+            let id = NodeId::from_index(i);
+            assert!(global[&id].value() < local[&id].value());
+        }
+
+        // Endorsements never lift a node past its own caps
+        for (id, node) in &nodes {
+            assert!(global[id].value() <= trust_ceiling(node).value());
+        }
+        assert_eq!(global[&endorsed], trust_ceiling(&nodes[&endorsed]));
+
+        // Zero iterations is just local trust
+        assert_eq!(compute_global_trust(&nodes, 0), local);
+    }
 
     #[test]
     fn test_compute_trust_new_node() {
@@ -261,6 +642,50 @@ mod tests {
         assert!(trust.value() <= diversity.value() + 0.31); // Small epsilon for float
     }
 
+    #[test]
+    fn test_explain_trust_components_and_cap() {
+        let id = NodeId::from_index(1);
+        let mut node = Node::new(id);
+        for _ in 0..100 {
+            node.handle_outgoing_interaction(
+                NodeId::from_index(2),
+                1.0,
+                1.0,
+                1.0,
+                Score::ONE,
+                crate::types::SignedScore::ONE,
+                None,
+            );
+        }
+
+        // One partner: strong components, bound by the diversity cap
+        let breakdown = explain_trust(&node);
+        let sum = breakdown.quality + breakdown.reciprocity + breakdown.social
+            + breakdown.diversity;
+        assert!((sum - breakdown.raw.value()).abs() < 1e-12);
+        assert!(breakdown.raw.value() > breakdown.diversity_cap.value());
+        assert_eq!(breakdown.bound, TrustBound::Diversity);
+        assert_eq!(breakdown.trust, breakdown.diversity_cap);
+        assert_eq!(breakdown.trust, compute_trust(&node));
+        assert!(breakdown.to_string().contains("capped by diversity"));
+
+        // A tighter trust cap takes over
+        node.trust_cap = Score::new(0.2);
+        let breakdown = explain_trust(&node);
+        assert_eq!(breakdown.bound, TrustBound::TrustCap);
+        assert_eq!(breakdown.trust, compute_trust(&node));
+        assert_eq!(breakdown.trust.value(), 0.2);
+
+        // Lifting both caps leaves the raw sum
+        node.trust_cap = Score::ONE;
+        for i in 0..100 {
+            node.history.add(crate::interaction::Interaction::new(id, NodeId::from_index(10 + i)));
+        }
+        let breakdown = explain_trust(&node);
+        assert_eq!(breakdown.bound, TrustBound::Uncapped);
+        assert_eq!(breakdown.trust, breakdown.raw);
+    }
+
     #[test]
     fn test_update_confidence() {
         let current = Score::HALF;
@@ -283,6 +708,36 @@ mod tests {
         assert_eq!(TrustLevel::from_score(Score::new(0.9)), TrustLevel::VeryHigh);
     }
 
+    #[test]
+    fn test_trust_level_ordering_and_ranges() {
+        // ALL is already sorted, lowest first
+        assert!(TrustLevel::ALL.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(TrustLevel::VeryHigh >= TrustLevel::High);
+        assert!(TrustLevel::Low < TrustLevel::Medium);
+        assert_eq!(TrustLevel::ALL.iter().max(), Some(&TrustLevel::VeryHigh));
+
+        // Ranges tile [0, 1] without gaps
+        assert_eq!(TrustLevel::VeryLow.to_score_range().0, 0.0);
+        assert_eq!(TrustLevel::VeryHigh.to_score_range().1, 1.0);
+        for pair in TrustLevel::ALL.windows(2) {
+            assert_eq!(pair[0].to_score_range().1, pair[1].to_score_range().0);
+        }
+
+        // Every score falls into the range of the level it maps to
+        for i in 0..=100 {
+            let score = Score::new(i as f64 / 100.0);
+            let (low, high) = TrustLevel::from_score(score).to_score_range();
+            let v = score.value();
+            assert!(low <= v && (v < high || v == 1.0), "{v} outside [{low}, {high})");
+        }
+
+        // Bucket bounds are inclusive below, exclusive above
+        assert!(TrustLevel::High.min_required(Score::new(0.6)));
+        assert!(TrustLevel::High.min_required(Score::new(0.95)));
+        assert!(!TrustLevel::High.min_required(Score::new(0.59)));
+        assert!(TrustLevel::VeryLow.min_required(Score::ZERO));
+    }
+
     #[test]
     fn test_trust_metrics() {
         let scores = vec![
@@ -298,5 +753,22 @@ mod tests {
         assert!((metrics.mean_trust - 0.5).abs() < 0.01);
         assert_eq!(metrics.low_trust_count, 1);
         assert_eq!(metrics.high_trust_count, 1);
+        assert!((metrics.median_trust - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_trust_metrics_percentiles() {
+        // 0.00, 0.01, ..., 1.00 in shuffled order
+        let scores = (0..=100).map(|i| Score::new(((i * 37) % 101) as f64 / 100.0));
+        let metrics = TrustMetrics::from_scores(scores);
+
+        assert!((metrics.p10_trust - 0.1).abs() < 1e-9);
+        assert!((metrics.median_trust - 0.5).abs() < 1e-9);
+        assert!((metrics.p90_trust - 0.9).abs() < 1e-9);
+        assert!((metrics.percentile(0.25) - 0.25).abs() < 1e-9);
+        assert_eq!(metrics.percentile(0.0), metrics.min_trust);
+        assert_eq!(metrics.percentile(1.0), metrics.max_trust);
+
+        assert_eq!(TrustMetrics::default().percentile(0.5), 0.0);
     }
 }
@@ -5,14 +5,35 @@
 use crate::constants::{W_MAX, W_MIN};
 use serde::{Deserialize, Serialize};
 use std::fmt;
-use std::ops::{Add, Mul, Sub};
+use std::ops::{Add, Div, Mul, Sub};
+use std::str::FromStr;
+
+// =============================================================================
+// PARSING
+// =============================================================================
+
+/// Error parsing a hex-encoded identifier
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ParseError {
+    /// Hex input must have an even number of digits
+    #[error("odd number of hex digits ({0})")]
+    OddLength(usize),
+    /// Input contained a non-hex character
+    #[error("invalid hex character {0:?}")]
+    InvalidChar(char),
+    /// Decoded to the wrong number of bytes
+    #[error("expected {expected} bytes, got {actual}")]
+    InvalidLength { expected: usize, actual: usize },
+}
 
 // =============================================================================
 // NODE IDENTIFIER
 // =============================================================================
 
 /// Cryptographic node identifier (32 bytes)
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+///
+/// Serializes as a hex string in human-readable formats so it can key JSON maps.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct NodeId(pub [u8; 32]);
 
 impl NodeId {
@@ -39,6 +60,24 @@ impl NodeId {
     pub fn as_bytes(&self) -> &[u8; 32] {
         &self.0
     }
+
+    /// Parse from 64 hex characters
+    pub fn from_hex(s: &str) -> Result<Self, ParseError> {
+        hex::decode_32(s).map(Self)
+    }
+
+    /// Full 64-character hex encoding
+    pub fn to_hex(&self) -> String {
+        hex::encode(&self.0)
+    }
+}
+
+impl FromStr for NodeId {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_hex(s)
+    }
 }
 
 impl fmt::Debug for NodeId {
@@ -53,12 +92,33 @@ impl fmt::Display for NodeId {
     }
 }
 
+impl Serialize for NodeId {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&self.to_hex())
+        } else {
+            self.0.serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for NodeId {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let s = String::deserialize(deserializer)?;
+            Self::from_hex(&s).map_err(serde::de::Error::custom)
+        } else {
+            <[u8; 32]>::deserialize(deserializer).map(Self)
+        }
+    }
+}
+
 // =============================================================================
 // SCORE (0 to 1)
 // =============================================================================
 
 /// A normalized score in the range [0, 1]
-#[derive(Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
 pub struct Score(f64);
 
 impl Score {
@@ -86,6 +146,19 @@ impl Score {
 
     /// Middle score (0.5)
     pub const HALF: Score = Score(0.5);
+
+    /// Linear interpolation toward `other`, with t clamped to [0, 1]
+    ///
+    /// lerp = a + t × (b - a)
+    pub fn lerp(self, other: Score, t: f64) -> Score {
+        let t = t.clamp(0.0, 1.0);
+        Score::new(self.0 + t * (other.0 - self.0))
+    }
+
+    /// Restrict to [lo, hi]
+    pub fn clamp_range(self, lo: Score, hi: Score) -> Score {
+        Score(self.0.clamp(lo.0, hi.0))
+    }
 }
 
 impl Default for Score {
@@ -127,18 +200,45 @@ impl Mul<f64> for Score {
     }
 }
 
+impl Div<f64> for Score {
+    type Output = Score;
+    /// Clamped to [0, 1]; 0 / 0 gives zero
+    fn div(self, rhs: f64) -> Self::Output {
+        let ratio = self.0 / rhs;
+        if ratio.is_nan() {
+            Score::ZERO
+        } else {
+            Score::new(ratio)
+        }
+    }
+}
+
 impl From<f64> for Score {
     fn from(value: f64) -> Self {
         Score::new(value)
     }
 }
 
+/// Serialized as a plain number
+impl Serialize for Score {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(self.0)
+    }
+}
+
+/// Deserialized from a plain number, clamped to [0, 1]
+impl<'de> Deserialize<'de> for Score {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        f64::deserialize(deserializer).map(Self::new)
+    }
+}
+
 // =============================================================================
 // SIGNED SCORE (-1 to 1)
 // =============================================================================
 
 /// A signed normalized score in the range [-1, 1]
-#[derive(Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
 pub struct SignedScore(f64);
 
 impl SignedScore {
@@ -166,6 +266,12 @@ impl SignedScore {
 
     /// Maximum negative
     pub const NEG_ONE: SignedScore = SignedScore(-1.0);
+
+    /// Linear interpolation toward `other`, with t clamped to [0, 1]
+    pub fn lerp(self, other: SignedScore, t: f64) -> SignedScore {
+        let t = t.clamp(0.0, 1.0);
+        SignedScore::new(self.0 + t * (other.0 - self.0))
+    }
 }
 
 impl Default for SignedScore {
@@ -199,12 +305,26 @@ impl From<f64> for SignedScore {
     }
 }
 
+/// Serialized as a plain number
+impl Serialize for SignedScore {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(self.0)
+    }
+}
+
+/// Deserialized from a plain number, clamped to [-1, 1]
+impl<'de> Deserialize<'de> for SignedScore {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        f64::deserialize(deserializer).map(Self::new)
+    }
+}
+
 // =============================================================================
 // WEIGHT (W_MIN to W_MAX)
 // =============================================================================
 
 /// Connection weight in the range [W_MIN, W_MAX]
-#[derive(Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
 pub struct Weight(f64);
 
 impl Weight {
@@ -224,6 +344,11 @@ impl Weight {
         self.0
     }
 
+    /// Signed difference self - other (negative when self is lighter)
+    pub fn delta(self, other: Weight) -> f64 {
+        self.0 - other.0
+    }
+
     /// Minimum weight
     pub const MIN: Weight = Weight(W_MIN);
 
@@ -266,6 +391,27 @@ impl Add<f64> for Weight {
     }
 }
 
+impl Sub<f64> for Weight {
+    type Output = Weight;
+    fn sub(self, rhs: f64) -> Self::Output {
+        Weight::new(self.0 - rhs)
+    }
+}
+
+/// Serialized as a plain number
+impl Serialize for Weight {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(self.0)
+    }
+}
+
+/// Deserialized from a plain number, clamped to [W_MIN, W_MAX]
+impl<'de> Deserialize<'de> for Weight {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        f64::deserialize(deserializer).map(Self::new)
+    }
+}
+
 // =============================================================================
 // HASH AND SIGNATURE
 // =============================================================================
@@ -284,6 +430,24 @@ impl Hash {
     pub fn as_bytes(&self) -> &[u8; 32] {
         &self.0
     }
+
+    /// Parse from 64 hex characters
+    pub fn from_hex(s: &str) -> Result<Self, ParseError> {
+        hex::decode_32(s).map(Self)
+    }
+
+    /// Full 64-character hex encoding
+    pub fn to_hex(&self) -> String {
+        hex::encode(&self.0)
+    }
+}
+
+impl FromStr for Hash {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_hex(s)
+    }
 }
 
 impl fmt::Debug for Hash {
@@ -394,9 +558,40 @@ impl Timestamp {
         self.0
     }
 
+    /// Timestamp `millis` later, saturating at the end of time
+    pub fn add_millis(&self, millis: u64) -> Self {
+        Self(self.0.saturating_add(millis))
+    }
+
+    /// Milliseconds elapsed since `earlier`, or 0 if it is not earlier
+    pub fn saturating_sub(&self, earlier: Timestamp) -> u64 {
+        self.0.saturating_sub(earlier.0)
+    }
+
     /// Check if timestamp is older than given duration (in ms)
     pub fn is_older_than(&self, duration_ms: u64) -> bool {
-        Timestamp::now().0.saturating_sub(self.0) > duration_ms
+        self.is_older_than_at(duration_ms, Timestamp::now())
+    }
+
+    /// Check if timestamp is older than given duration (in ms) as of `now`
+    pub fn is_older_than_at(&self, duration_ms: u64, now: Timestamp) -> bool {
+        now - *self > duration_ms
+    }
+}
+
+/// Advances by a number of milliseconds, saturating
+impl Add<u64> for Timestamp {
+    type Output = Timestamp;
+    fn add(self, rhs: u64) -> Self::Output {
+        self.add_millis(rhs)
+    }
+}
+
+/// Elapsed milliseconds between two timestamps, saturating at 0
+impl Sub for Timestamp {
+    type Output = u64;
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.saturating_sub(rhs)
     }
 }
 
@@ -511,6 +706,8 @@ impl fmt::Debug for StepId {
 // =============================================================================
 
 mod hex {
+    use super::ParseError;
+
     const HEX_CHARS: &[u8; 16] = b"0123456789abcdef";
 
     pub fn encode(bytes: &[u8]) -> String {
@@ -521,12 +718,80 @@ mod hex {
         }
         s
     }
+
+    pub fn decode(s: &str) -> Result<Vec<u8>, ParseError> {
+        let digits = s.as_bytes();
+        if digits.len() % 2 != 0 {
+            return Err(ParseError::OddLength(digits.len()));
+        }
+
+        let digit = |c: u8| {
+            (c as char)
+                .to_digit(16)
+                .ok_or(ParseError::InvalidChar(c as char))
+        };
+        digits
+            .chunks(2)
+            .map(|pair| Ok((digit(pair[0])? * 16 + digit(pair[1])?) as u8))
+            .collect()
+    }
+
+    pub fn decode_32(s: &str) -> Result<[u8; 32], ParseError> {
+        let bytes = decode(s)?;
+        bytes
+            .try_into()
+            .map_err(|bytes: Vec<u8>| ParseError::InvalidLength {
+                expected: 32,
+                actual: bytes.len(),
+            })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_bounded_serde_clamps() {
+        assert_eq!(serde_json::to_string(&Score::new(0.25)).unwrap(), "0.25");
+        assert_eq!(serde_json::to_string(&SignedScore::new(-0.5)).unwrap(), "-0.5");
+        assert_eq!(serde_json::to_string(&[Score::ONE, Score::ZERO]).unwrap(), "[1.0,0.0]");
+
+        let score: Score = serde_json::from_str("1.7").unwrap();
+        assert_eq!(score, Score::ONE);
+        let score: Score = serde_json::from_str("-3").unwrap();
+        assert_eq!(score, Score::ZERO);
+        let signed: SignedScore = serde_json::from_str("-2.5").unwrap();
+        assert_eq!(signed, SignedScore::NEG_ONE);
+        let weight: Weight = serde_json::from_str("100.0").unwrap();
+        assert_eq!(weight, Weight::MAX);
+        let weight: Weight = serde_json::from_str("-1").unwrap();
+        assert_eq!(weight, Weight::MIN);
+
+        // In-range values round-trip unchanged
+        let weight: Weight = serde_json::from_str(&serde_json::to_string(&Weight::INIT).unwrap())
+            .unwrap();
+        assert_eq!(weight, Weight::INIT);
+
+        assert!(serde_json::from_str::<Score>("\"high\"").is_err());
+    }
+
+    #[cfg(feature = "persistence")]
+    #[test]
+    fn test_bincode_round_trip() {
+        let mut bytes = [0u8; 64];
+        bytes.iter_mut().enumerate().for_each(|(i, b)| *b = i as u8);
+        let signature = Signature::new(bytes);
+        let encoded = bincode::serialize(&signature).unwrap();
+        assert_eq!(bincode::deserialize::<Signature>(&encoded).unwrap(), signature);
+
+        // Binary formats skip the hex encoding used for JSON
+        let id = NodeId::from_index(7);
+        let encoded = bincode::serialize(&id).unwrap();
+        assert_eq!(encoded.len(), 32);
+        assert_eq!(bincode::deserialize::<NodeId>(&encoded).unwrap(), id);
+    }
+
     #[test]
     fn test_score_clamping() {
         assert_eq!(Score::new(-0.5).value(), 0.0);
@@ -534,6 +799,32 @@ mod tests {
         assert_eq!(Score::new(1.5).value(), 1.0);
     }
 
+    #[test]
+    fn test_score_division() {
+        assert_eq!((Score::new(0.6) / 2.0).value(), 0.3);
+        // Ratio above 1.0 clamps
+        assert_eq!((Score::new(0.8) / 0.5).value(), 1.0);
+        assert_eq!((Score::new(0.5) / 0.0).value(), 1.0);
+        assert_eq!((Score::ZERO / 0.0).value(), 0.0);
+        assert_eq!((Score::new(0.5) / -1.0).value(), 0.0);
+    }
+
+    #[test]
+    fn test_score_lerp_and_clamp_range() {
+        assert_eq!(Score::ZERO.lerp(Score::ONE, 0.5).value(), 0.5);
+        assert!((Score::new(0.2).lerp(Score::new(0.6), 0.25).value() - 0.3).abs() < 1e-12);
+        assert_eq!(Score::ZERO.lerp(Score::ONE, 2.0), Score::ONE);
+
+        let lo = Score::new(0.2);
+        let hi = Score::new(0.7);
+        assert_eq!(Score::new(0.1).clamp_range(lo, hi), lo);
+        assert_eq!(Score::new(0.9).clamp_range(lo, hi), hi);
+        assert_eq!(Score::HALF.clamp_range(lo, hi), Score::HALF);
+
+        assert_eq!(SignedScore::NEG_ONE.lerp(SignedScore::ONE, 0.5), SignedScore::ZERO);
+        assert_eq!(SignedScore::ZERO.lerp(SignedScore::NEG_ONE, 0.25).value(), -0.25);
+    }
+
     #[test]
     fn test_signed_score_clamping() {
         assert_eq!(SignedScore::new(-1.5).value(), -1.0);
@@ -548,6 +839,18 @@ mod tests {
         assert_eq!(Weight::new(2.0).value(), W_MAX);
     }
 
+    #[test]
+    fn test_weight_sub_and_delta() {
+        let w = Weight::new(0.5);
+        assert!(((w - 0.2).value() - 0.3).abs() < 1e-12);
+        assert_eq!(w - 1.0, Weight::MIN);
+        assert_eq!(w - -1.0, Weight::MAX);
+
+        assert!((w.delta(Weight::new(0.2)) - 0.3).abs() < 1e-12);
+        assert!((Weight::new(0.2).delta(w) + 0.3).abs() < 1e-12);
+        assert_eq!(w.delta(w), 0.0);
+    }
+
     #[test]
     fn test_node_id_from_index() {
         let id1 = NodeId::from_index(1);
@@ -568,6 +871,31 @@ mod tests {
         assert_ne!(h1, h3);
     }
 
+    #[test]
+    fn test_hex_round_trip() {
+        let id = NodeId::random();
+        assert_eq!(id.to_hex().len(), 64);
+        assert_eq!(NodeId::from_hex(&id.to_hex()), Ok(id));
+        assert_eq!(id.to_hex().parse::<NodeId>(), Ok(id));
+
+        let hash = Hash::compute(b"symbiont");
+        assert_eq!(Hash::from_hex(&hash.to_hex()), Ok(hash));
+        assert_eq!(hash.to_hex().to_uppercase().parse::<Hash>(), Ok(hash));
+    }
+
+    #[test]
+    fn test_hex_parse_errors() {
+        assert_eq!(NodeId::from_hex("abc"), Err(ParseError::OddLength(3)));
+        assert_eq!(
+            NodeId::from_hex(&"zz".repeat(32)),
+            Err(ParseError::InvalidChar('z'))
+        );
+        assert_eq!(
+            Hash::from_hex("abcd"),
+            Err(ParseError::InvalidLength { expected: 32, actual: 2 })
+        );
+    }
+
     #[test]
     fn test_capability_id_from_name() {
         let cap1 = CapabilityId::from_name("analysis");
@@ -577,4 +905,27 @@ mod tests {
         assert_eq!(cap1, cap2);
         assert_ne!(cap1, cap3);
     }
+
+    #[test]
+    fn test_timestamp_arithmetic_saturates() {
+        let start = Timestamp::new(1_000);
+        let later = start.add_millis(250);
+        assert_eq!(later, Timestamp::new(1_250));
+        assert_eq!(start + 250, later);
+        assert_eq!(later - start, 250);
+        assert_eq!(later.saturating_sub(start), 250);
+
+        // Ages never go negative
+        assert_eq!(start - later, 0);
+        assert_eq!(Timestamp::new(0) - Timestamp::new(u64::MAX), 0);
+
+        // Advancing past the end of time stays there
+        let end = Timestamp::new(u64::MAX);
+        assert_eq!(end.add_millis(1), end);
+        assert_eq!(Timestamp::new(u64::MAX - 5) + 10, end);
+        assert_eq!(end - Timestamp::new(0), u64::MAX);
+
+        assert!(!Timestamp::now().is_older_than(60_000));
+        assert!(Timestamp::new(0).is_older_than(60_000));
+    }
 }
@@ -2,6 +2,8 @@
 //!
 //! Supports sequential, parallel, and DAG-based workflows.
 
+use crate::clock::{Clock, SystemClock};
+use crate::handoff::HandoffChain;
 use crate::routing::{route_task, RoutingResult, Task};
 use crate::node::Node;
 use crate::types::{NodeId, Score, StepId, TaskId, Timestamp, WorkflowId};
@@ -74,6 +76,10 @@ pub struct WorkflowStep {
     pub status: StepStatus,
     /// Result (if completed)
     pub result: Option<StepResult>,
+    /// Extra candidates to try after the first one fails
+    pub max_retries: u32,
+    /// Number of candidates tried so far
+    pub attempts: u32,
 }
 
 impl WorkflowStep {
@@ -86,9 +92,17 @@ impl WorkflowStep {
             depends_on: Vec::new(),
             status: StepStatus::Pending,
             result: None,
+            max_retries: 0,
+            attempts: 0,
         }
     }
 
+    /// Set how many fallback candidates to try on failure
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
     /// Add a dependency
     pub fn depends_on(mut self, step_id: StepId) -> Self {
         self.depends_on.push(step_id);
@@ -137,6 +151,11 @@ impl WorkflowContext {
         self.step_index += 1;
     }
 
+    /// Rebuild lineage from the handoffs that carried this workflow
+    pub fn restore_lineage(&mut self, chain: &HandoffChain) {
+        self.lineage = chain.lineage();
+    }
+
     /// Set data
     pub fn set_data(&mut self, key: impl Into<String>, value: Vec<u8>) {
         self.data.insert(key.into(), value);
@@ -167,6 +186,9 @@ pub struct Workflow {
     pub started: Option<Timestamp>,
     /// When the workflow completed
     pub completed: Option<Timestamp>,
+    /// Time by which the workflow must finish, if any
+    #[serde(default)]
+    pub deadline: Option<Timestamp>,
 }
 
 impl Workflow {
@@ -181,9 +203,16 @@ impl Workflow {
             created: Timestamp::now(),
             started: None,
             completed: None,
+            deadline: None,
         }
     }
 
+    /// Set the time by which the workflow must finish
+    pub fn with_deadline(mut self, deadline: Timestamp) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
     /// Add a step
     pub fn add_step(&mut self, step: WorkflowStep) {
         self.steps.push(step);
@@ -231,6 +260,97 @@ impl Workflow {
         self.check_completion();
     }
 
+    /// Mark a step as failed and skip everything downstream of it
+    pub fn fail_step(&mut self, step_id: StepId) {
+        let mut failed = vec![step_id];
+
+        if let Some(step) = self.steps.iter_mut().find(|s| s.id == step_id) {
+            step.status = StepStatus::Failed;
+        }
+
+        // Propagate skips through the dependency graph
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for step in self.steps.iter_mut() {
+                if step.status == StepStatus::Pending
+                    && step.depends_on.iter().any(|d| failed.contains(d))
+                {
+                    step.status = StepStatus::Skipped;
+                    failed.push(step.id);
+                    changed = true;
+                }
+            }
+        }
+
+        self.check_completion();
+    }
+
+    /// Re-route unfinished steps assigned to a node that has left
+    ///
+    /// Each step is routed again from its task origin with the departed node
+    /// excluded. Steps with no remaining candidate fail. Returns the tasks
+    /// that found a new executor.
+    pub fn reassign_from(
+        &mut self,
+        departed: NodeId,
+        network: &HashMap<NodeId, Node>,
+    ) -> Vec<TaskId> {
+        let mut reassigned = Vec::new();
+        let mut stranded = Vec::new();
+
+        for step in self.steps.iter_mut() {
+            let in_flight = matches!(step.status, StepStatus::Ready | StepStatus::Running);
+            if !in_flight || step.assigned_to != Some(departed) {
+                continue;
+            }
+
+            let mut task = step.task.clone();
+            task.constraints = task.constraints.exclude(departed);
+            let routing = network
+                .get(&task.origin)
+                .map(|origin| route_task(origin, &task, network));
+
+            match routing {
+                Some(RoutingResult::Success(candidate)) => {
+                    step.assigned_to = Some(candidate.node_id);
+                    reassigned.push(task.id);
+                }
+                _ => stranded.push(step.id),
+            }
+        }
+
+        for step_id in stranded {
+            self.fail_step(step_id);
+        }
+        reassigned
+    }
+
+    /// Fail a running workflow that is past its deadline at `now`
+    ///
+    /// Steps in flight fail and pending steps are skipped. Returns the first
+    /// step left unfinished, or None if the workflow did not time out.
+    pub fn check_deadline(&mut self, now: Timestamp) -> Option<StepId> {
+        let deadline = self.deadline?;
+        if self.status != WorkflowStatus::Running || now <= deadline {
+            return None;
+        }
+
+        let mut timed_out = None;
+        for step in self.steps.iter_mut() {
+            step.status = match step.status {
+                StepStatus::Ready | StepStatus::Running => StepStatus::Failed,
+                StepStatus::Pending => StepStatus::Skipped,
+                _ => continue,
+            };
+            timed_out.get_or_insert(step.id);
+        }
+
+        self.status = WorkflowStatus::Failed;
+        self.completed = Some(now);
+        timed_out
+    }
+
     /// Check if workflow is complete
     fn check_completion(&mut self) {
         let all_done = self.steps.iter().all(|s| {
@@ -279,40 +399,169 @@ pub enum WorkflowResult {
     InProgress,
 }
 
-/// Execute a sequential workflow
+/// Fail `workflow` with a timeout reason if it is past its deadline at `now`
+fn check_timeout(workflow: &mut Workflow, now: Timestamp) -> Option<WorkflowResult> {
+    let deadline = workflow.deadline?;
+    let step_id = workflow.check_deadline(now)?;
+    Some(WorkflowResult::Failed {
+        step_id,
+        reason: format!("Deadline exceeded by {} ms", now.saturating_sub(deadline)),
+    })
+}
+
+/// Execute a sequential workflow against the wall clock
 pub fn execute_sequential_workflow(
     workflow: &mut Workflow,
     current_node: &Node,
     network: &HashMap<NodeId, Node>,
 ) -> WorkflowResult {
-    for step in workflow.steps.iter_mut() {
+    execute_sequential_workflow_with(workflow, current_node, network, &SystemClock, |_, _| true)
+}
+
+/// Execute a sequential workflow, running each assignment through `execute`
+///
+/// `execute` reports whether the chosen node carried out the task. When it
+/// fails, the step is re-routed with every previously tried node excluded,
+/// up to the step's `max_retries`, before the workflow fails. The deadline
+/// is checked against `clock` after each step, so a step that overruns it
+/// fails with the rest of the workflow skipped.
+pub fn execute_sequential_workflow_with<F>(
+    workflow: &mut Workflow,
+    current_node: &Node,
+    network: &HashMap<NodeId, Node>,
+    clock: &dyn Clock,
+    mut execute: F,
+) -> WorkflowResult
+where
+    F: FnMut(&Task, NodeId) -> bool,
+{
+    if workflow.status == WorkflowStatus::Pending {
+        workflow.status = WorkflowStatus::Running;
+        workflow.started = Some(clock.now());
+    }
+
+    for i in 0..workflow.steps.len() {
+        let step = &mut workflow.steps[i];
         if step.status != StepStatus::Pending {
             continue;
         }
 
-        // Route to best candidate
-        let routing = route_task(current_node, &step.task, network);
+        let mut task = step.task.clone();
+        loop {
+            // Route to best candidate not yet tried
+            let routing = route_task(current_node, &task, network);
+
+            let reason = match routing {
+                RoutingResult::Success(candidate) => {
+                    step.attempts += 1;
+                    if execute(&task, candidate.node_id) {
+                        step.assigned_to = Some(candidate.node_id);
+                        step.status = StepStatus::Ready;
+                        break;
+                    }
+                    if step.attempts <= step.max_retries {
+                        task.constraints = task.constraints.exclude(candidate.node_id);
+                        continue;
+                    }
+                    format!("Step failed after {} attempts", step.attempts)
+                }
+                RoutingResult::NoCandidates | RoutingResult::ConstraintsNotMet => {
+                    String::from("No suitable candidates")
+                }
+            };
 
-        match routing {
-            RoutingResult::Success(candidate) => {
-                step.assigned_to = Some(candidate.node_id);
-                step.status = StepStatus::Ready;
-                // In real implementation, would execute here
-            }
-            RoutingResult::NoCandidates | RoutingResult::ConstraintsNotMet => {
-                step.status = StepStatus::Failed;
-                workflow.status = WorkflowStatus::Failed;
-                return WorkflowResult::Failed {
-                    step_id: step.id,
-                    reason: String::from("No suitable candidates"),
-                };
-            }
+            step.status = StepStatus::Failed;
+            workflow.status = WorkflowStatus::Failed;
+            return WorkflowResult::Failed {
+                step_id: step.id,
+                reason,
+            };
+        }
+
+        if let Some(result) = check_timeout(workflow, clock.now()) {
+            return result;
         }
     }
 
     WorkflowResult::InProgress
 }
 
+/// Execute a workflow of any type to completion
+///
+/// Repeatedly takes the steps whose dependencies are satisfied, routes each
+/// to the best candidate, and records a result synthesized from the chosen
+/// node's capability quality. Steps that become ready together run in the
+/// same round, so parallel branches complete before any step that merges them.
+/// The deadline is checked against `clock` before each round.
+pub fn execute_workflow(
+    workflow: &mut Workflow,
+    origin: &Node,
+    network: &HashMap<NodeId, Node>,
+    clock: &dyn Clock,
+) -> WorkflowResult {
+    if workflow.status == WorkflowStatus::Pending {
+        workflow.status = WorkflowStatus::Running;
+        workflow.started = Some(clock.now());
+    }
+
+    loop {
+        if let Some(result) = check_timeout(workflow, clock.now()) {
+            return result;
+        }
+
+        let ready: Vec<StepId> = workflow.ready_steps().iter().map(|s| s.id).collect();
+        if ready.is_empty() {
+            break;
+        }
+
+        for step_id in ready {
+            let Some(step) = workflow.steps.iter().find(|s| s.id == step_id) else {
+                continue;
+            };
+
+            match route_task(origin, &step.task, network) {
+                RoutingResult::Success(candidate) => {
+                    let executor = candidate.node_id;
+                    let quality = network
+                        .get(&executor)
+                        .map(|node| {
+                            let caps = &step.task.required_caps;
+                            let sum: f64 =
+                                caps.iter().map(|c| node.capability_quality(*c).value()).sum();
+                            Score::new(sum / caps.len().max(1) as f64)
+                        })
+                        .unwrap_or(Score::ZERO);
+
+                    workflow.start_step(step_id, executor);
+                    workflow.complete_step(
+                        step_id,
+                        StepResult {
+                            step_id,
+                            success: true,
+                            output: step_id.0.to_le_bytes().to_vec(),
+                            quality,
+                            executor,
+                            duration_ms: 0,
+                        },
+                    );
+                }
+                RoutingResult::NoCandidates | RoutingResult::ConstraintsNotMet => {
+                    workflow.fail_step(step_id);
+                    return WorkflowResult::Failed {
+                        step_id,
+                        reason: String::from("No suitable candidates"),
+                    };
+                }
+            }
+        }
+    }
+
+    match workflow.status {
+        WorkflowStatus::Completed => WorkflowResult::Success(workflow.context.clone()),
+        _ => WorkflowResult::InProgress,
+    }
+}
+
 /// Common workflow patterns
 pub mod patterns {
     use super::*;
@@ -376,10 +625,75 @@ pub mod patterns {
     }
 }
 
+/// Merge the results of an ensemble fan-out into one result
+///
+/// Failed results are ignored. The aggregate quality is the trust-weighted mean:
+///
+/// q = Σ T_i × q_i / Σ T_i
+///
+/// Identical outputs pool their votes, each worth T_i × q_i, and the output with
+/// the most votes wins. Unknown executors count with neutral trust (0.5).
+/// Returns None when no result succeeded.
+pub fn aggregate_ensemble(
+    results: &[StepResult],
+    weights_by_trust: &HashMap<NodeId, Score>,
+) -> Option<StepResult> {
+    let succeeded: Vec<_> = results.iter().filter(|r| r.success).collect();
+    let first = succeeded.first()?;
+
+    let trust_of = |r: &StepResult| {
+        weights_by_trust
+            .get(&r.executor)
+            .map(|t| t.value())
+            .unwrap_or(0.5)
+    };
+
+    let total_trust: f64 = succeeded.iter().map(|r| trust_of(r)).sum();
+    let quality = if total_trust > 0.0 {
+        succeeded
+            .iter()
+            .map(|r| trust_of(r) * r.quality.value())
+            .sum::<f64>()
+            / total_trust
+    } else {
+        succeeded.iter().map(|r| r.quality.value()).sum::<f64>() / succeeded.len() as f64
+    };
+
+    // Tally votes per distinct output, keeping first-seen order for ties
+    let mut tallies: Vec<(&[u8], f64, &StepResult)> = Vec::new();
+    for result in &succeeded {
+        let vote = trust_of(result) * result.quality.value();
+        match tallies.iter_mut().find(|(out, _, _)| *out == result.output.as_slice()) {
+            Some((_, votes, best)) => {
+                *votes += vote;
+                if vote > trust_of(best) * best.quality.value() {
+                    *best = result;
+                }
+            }
+            None => tallies.push((result.output.as_slice(), vote, result)),
+        }
+    }
+    let (output, _, representative) = tallies
+        .into_iter()
+        .reduce(|best, t| if t.1 > best.1 { t } else { best })?;
+
+    Some(StepResult {
+        step_id: first.step_id,
+        success: true,
+        output: output.to_vec(),
+        quality: Score::new(quality),
+        executor: representative.executor,
+        // Fan-in waits for the slowest member
+        duration_ms: succeeded.iter().map(|r| r.duration_ms).max().unwrap_or(0),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::capability::common;
+    use crate::capability::{common, Capability};
+    use crate::clock::VirtualClock;
+    use crate::types::CapabilityId;
 
     #[test]
     fn test_workflow_creation() {
@@ -471,6 +785,232 @@ mod tests {
         assert_eq!(context.get_data("key1"), Some(&vec![1, 2, 3]));
     }
 
+    /// One specialist node per capability, indexed from 1
+    fn specialist_network(caps: Vec<Capability>) -> (Node, HashMap<NodeId, Node>) {
+        use crate::node::NodeBuilder;
+
+        let origin = NodeBuilder::new(NodeId::from_index(0)).build();
+        let mut network = HashMap::new();
+        for (i, cap) in caps.into_iter().enumerate() {
+            let node = NodeBuilder::new(NodeId::from_index(i as u64 + 1))
+                .trust(Score::new(0.8))
+                .capability(cap)
+                .build();
+            network.insert(node.id, node);
+        }
+        (origin, network)
+    }
+
+    #[test]
+    fn test_execute_chain_workflow() {
+        let (origin, network) = specialist_network(vec![
+            common::analysis(),
+            common::transformation(),
+            common::validation(),
+        ]);
+        let caps = vec![
+            common::analysis().id,
+            common::transformation().id,
+            common::validation().id,
+        ];
+        let mut workflow = patterns::chain(origin.id, caps);
+        let clock = VirtualClock::starting_at(Timestamp::new(1_000), 100);
+
+        let result = execute_workflow(&mut workflow, &origin, &network, &clock);
+
+        assert!(matches!(result, WorkflowResult::Success(_)));
+        assert_eq!(workflow.status, WorkflowStatus::Completed);
+        assert_eq!(workflow.started, Some(Timestamp::new(1_000)));
+        assert!(workflow.completed.is_some());
+
+        // Lineage follows the chain order
+        assert_eq!(
+            workflow.context.lineage,
+            vec![NodeId::from_index(1), NodeId::from_index(2), NodeId::from_index(3)]
+        );
+        assert!(workflow.overall_quality().value() > 0.0);
+    }
+
+    #[test]
+    fn test_execute_fan_out_workflow() {
+        let (origin, network) =
+            specialist_network(vec![common::analysis(), common::transformation()]);
+        let mut workflow = patterns::fan_out_fan_in(
+            origin.id,
+            common::analysis().id,
+            3,
+            common::transformation().id,
+        );
+
+        let result = execute_workflow(&mut workflow, &origin, &network, &SystemClock);
+
+        assert!(matches!(result, WorkflowResult::Success(_)));
+        assert_eq!(workflow.status, WorkflowStatus::Completed);
+
+        // The merge step runs only after all three parallel steps
+        let order: Vec<_> = workflow.context.prior_results.iter().map(|r| r.step_id).collect();
+        assert_eq!(order.len(), 4);
+        assert_eq!(*order.last().unwrap(), StepId::new(3));
+        assert_eq!(
+            workflow.context.lineage,
+            vec![NodeId::from_index(1), NodeId::from_index(2)]
+        );
+    }
+
+    #[test]
+    fn test_execute_workflow_failure_skips_dependents() {
+        // Nobody offers the merge capability
+        let (origin, network) = specialist_network(vec![common::analysis()]);
+        let mut workflow = patterns::chain(
+            origin.id,
+            vec![common::analysis().id, CapabilityId::new(999), common::analysis().id],
+        );
+
+        let result = execute_workflow(&mut workflow, &origin, &network, &SystemClock);
+
+        assert!(matches!(
+            result,
+            WorkflowResult::Failed { step_id, .. } if step_id == StepId::new(1)
+        ));
+        assert_eq!(workflow.status, WorkflowStatus::Failed);
+        assert_eq!(workflow.steps[2].status, StepStatus::Skipped);
+    }
+
+    #[test]
+    fn test_sequential_retry_falls_back_to_next_candidate() {
+        use crate::node::NodeBuilder;
+
+        let origin = NodeBuilder::new(NodeId::from_index(0)).build();
+        let best = NodeBuilder::new(NodeId::from_index(1))
+            .trust(Score::new(0.9))
+            .capability(common::analysis())
+            .build();
+        let second = NodeBuilder::new(NodeId::from_index(2))
+            .trust(Score::new(0.6))
+            .capability(common::analysis())
+            .build();
+        let network: HashMap<NodeId, Node> =
+            [(best.id, best), (second.id, second)].into_iter().collect();
+
+        let build = |max_retries| {
+            let task = Task::new(TaskId::random(), origin.id, common::analysis().id);
+            let mut workflow = Workflow::new(WorkflowId::random(), WorkflowType::Sequential);
+            let step = WorkflowStep::new(StepId::new(0), task).with_max_retries(max_retries);
+            workflow.add_step(step);
+            workflow
+        };
+        // The top candidate always fails
+        let fail_best = |_: &Task, node: NodeId| node != NodeId::from_index(1);
+        let run = |workflow: &mut Workflow| {
+            execute_sequential_workflow_with(workflow, &origin, &network, &SystemClock, fail_best)
+        };
+
+        let mut workflow = build(1);
+        let result = run(&mut workflow);
+        assert!(matches!(result, WorkflowResult::InProgress));
+        assert_eq!(workflow.steps[0].assigned_to, Some(NodeId::from_index(2)));
+        assert_eq!(workflow.steps[0].attempts, 2);
+
+        // Without retries the first failure fails the workflow
+        let mut workflow = build(0);
+        let result = run(&mut workflow);
+        assert!(matches!(result, WorkflowResult::Failed { .. }));
+        assert_eq!(workflow.steps[0].attempts, 1);
+        assert_eq!(workflow.status, WorkflowStatus::Failed);
+    }
+
+    #[test]
+    fn test_overrunning_step_times_out_workflow() {
+        let (origin, network) = specialist_network(vec![common::analysis()]);
+        let caps = vec![common::analysis().id; 3];
+        let start = Timestamp::new(1_000);
+        let mut workflow = patterns::chain(origin.id, caps).with_deadline(start.add_millis(5));
+
+        // By the first check a 20 ms tick has passed, longer than the deadline
+        let mut clock = VirtualClock::starting_at(start, 20);
+        clock.on_tick(1);
+        let result =
+            execute_sequential_workflow_with(&mut workflow, &origin, &network, &clock, |_, _| true);
+
+        assert!(matches!(
+            result,
+            WorkflowResult::Failed { step_id, ref reason }
+                if step_id == StepId::new(0) && reason.starts_with("Deadline exceeded")
+        ));
+        assert_eq!(workflow.status, WorkflowStatus::Failed);
+        assert_eq!(workflow.steps[0].status, StepStatus::Failed);
+        assert_eq!(workflow.steps[1].status, StepStatus::Skipped);
+        assert_eq!(workflow.steps[2].status, StepStatus::Skipped);
+
+        // Finished or unstarted workflows are left alone
+        let far_future = Timestamp::new(u64::MAX);
+        assert!(workflow.check_deadline(far_future).is_none());
+        let mut pending = Workflow::new(WorkflowId::random(), WorkflowType::Single)
+            .with_deadline(Timestamp::new(0));
+        assert!(pending.check_deadline(far_future).is_none());
+        assert_eq!(pending.status, WorkflowStatus::Pending);
+    }
+
+    #[test]
+    fn test_reassign_from_departed_node() {
+        let (origin, mut network) =
+            specialist_network(vec![common::analysis(), common::analysis()]);
+        network.insert(origin.id, origin.clone());
+        let mut workflow = patterns::chain(origin.id, vec![common::analysis().id]);
+        execute_sequential_workflow(&mut workflow, &origin, &network);
+        let departed = workflow.steps[0].assigned_to.unwrap();
+
+        network.remove(&departed);
+        let reassigned = workflow.reassign_from(departed, &network);
+        assert_eq!(reassigned, vec![workflow.steps[0].task.id]);
+        let successor = workflow.steps[0].assigned_to.unwrap();
+        assert_ne!(successor, departed);
+
+        // With nobody left to take it, the step fails
+        network.remove(&successor);
+        assert!(workflow.reassign_from(successor, &network).is_empty());
+        assert_eq!(workflow.steps[0].status, StepStatus::Failed);
+        assert_eq!(workflow.status, WorkflowStatus::Failed);
+    }
+
+    #[test]
+    fn test_aggregate_ensemble_weighted_quality() {
+        let result = |i: u64, output: Vec<u8>, quality: f64| StepResult {
+            step_id: StepId::new(0),
+            success: true,
+            output,
+            quality: Score::new(quality),
+            executor: NodeId::from_index(i),
+            duration_ms: 10 * (i + 1),
+        };
+        let results = vec![
+            result(0, vec![1], 0.9),
+            result(1, vec![2], 0.6),
+            result(2, vec![2], 0.4),
+        ];
+        let trusts: HashMap<_, _> = [(0, 0.8), (1, 0.5), (2, 0.2)]
+            .into_iter()
+            .map(|(i, t)| (NodeId::from_index(i), Score::new(t)))
+            .collect();
+
+        let merged = aggregate_ensemble(&results, &trusts).unwrap();
+
+        let expected = (0.8 * 0.9 + 0.5 * 0.6 + 0.2 * 0.4) / (0.8 + 0.5 + 0.2);
+        assert!((merged.quality.value() - expected).abs() < 1e-9);
+        // Output [1] has 0.72 votes, [2] pools 0.30 + 0.08
+        assert_eq!(merged.output, vec![1]);
+        assert_eq!(merged.executor, NodeId::from_index(0));
+        assert_eq!(merged.duration_ms, 30);
+
+        // Agreeing executors outvote a single stronger one
+        let trusts: HashMap<_, _> = (0..3).map(|i| (NodeId::from_index(i), Score::HALF)).collect();
+        let merged = aggregate_ensemble(&results, &trusts).unwrap();
+        assert_eq!(merged.output, vec![2]);
+        assert_eq!(merged.executor, NodeId::from_index(1));
+
+        assert!(aggregate_ensemble(&[], &trusts).is_none());
+    }
+
     #[test]
     fn test_ready_steps() {
         let id = WorkflowId::random();
@@ -0,0 +1,67 @@
+//! Benchmarks of the trust engine fed by a generated interaction stream.
+//!
+//! Run with `cargo bench -p symbiont-sim`.
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use symbiont_sim::network::NetworkConfig;
+use symbiont_sim::topology::Topology;
+use symbiont_sim::{generate_interactions, InteractionStreamConfig, Network};
+
+/// Events handled per benchmark iteration
+const EVENTS: usize = 1_000;
+
+const SEED: u64 = 42;
+
+fn network(nodes: usize) -> Network {
+    let config = NetworkConfig::default().with_nodes(nodes).with_connection_prob(0.0);
+    Network::from_config(config.with_seed(SEED))
+}
+
+fn bench_handle_event(c: &mut Criterion) {
+    let mut group = c.benchmark_group("handle_event");
+    for nodes in [100, 1_000] {
+        let config = InteractionStreamConfig::default()
+            .with_nodes(nodes)
+            .with_topology(Topology::ScaleFree(3));
+        let events: Vec<_> = generate_interactions(&config, SEED).take(EVENTS).collect();
+
+        group.bench_with_input(BenchmarkId::from_parameter(nodes), &events, |b, events| {
+            b.iter_batched(
+                || (network(nodes), events.clone()),
+                |(mut network, events)| {
+                    for event in events {
+                        network.handle_event(event);
+                    }
+                    network
+                },
+                BatchSize::LargeInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+fn bench_tick(c: &mut Criterion) {
+    let config = InteractionStreamConfig::default().with_nodes(1_000);
+    let events: Vec<_> = generate_interactions(&config, SEED).take(EVENTS).collect();
+
+    c.bench_function("tick_with_1000_queued", |b| {
+        b.iter_batched(
+            || {
+                let mut network = network(1_000);
+                for event in events.iter().cloned() {
+                    network.queue_event(event);
+                }
+                network
+            },
+            |mut network| {
+                network.tick();
+                network
+            },
+            BatchSize::LargeInput,
+        );
+    });
+}
+
+criterion_group!(benches, bench_handle_event, bench_tick);
+criterion_main!(benches);
@@ -1,20 +1,61 @@
 //! Agent behavior models for simulation.
 
-use crate::events::Event;
+use crate::events::{AgentType, Event};
+use rand::rngs::StdRng;
 use rand::Rng;
 use std::collections::HashMap;
+use symbiont_core::interaction::ToneSignals;
 use symbiont_core::node::Node;
-use symbiont_core::types::{NodeId, Score, SignedScore};
+use symbiont_core::types::{CapabilityId, NodeId, Score, SignedScore};
 
 /// Trait for agent behavior
 pub trait Agent: Send + Sync {
     /// Called each tick to determine agent's actions
-    fn act(&self, node: &Node, network: &HashMap<NodeId, Node>, tick: u64) -> Vec<Event>;
+    ///
+    /// All randomness must come from `rng`, which the network seeds per agent
+    /// and tick so that runs with the same seed are reproducible.
+    fn act(
+        &self,
+        node: &Node,
+        network: &HashMap<NodeId, Node>,
+        tick: u64,
+        rng: &mut StdRng,
+    ) -> Vec<Event>;
 
     /// Agent type name for logging
     fn agent_type(&self) -> &'static str;
 }
 
+/// A node's connection partners sorted by id, so picks don't depend on map order
+fn sorted_partners(node: &Node) -> Vec<NodeId> {
+    let mut partners: Vec<_> = node.connections.keys().cloned().collect();
+    partners.sort();
+    partners
+}
+
+/// A uniformly random connection partner, or None with no partners
+fn random_partner<R: Rng>(node: &Node, rng: &mut R) -> Option<NodeId> {
+    let partners = sorted_partners(node);
+    if partners.is_empty() {
+        return None;
+    }
+    Some(partners[rng.gen_range(0..partners.len())])
+}
+
+/// With probability `rate`, a random connection partner to interact with
+fn maybe_partner<R: Rng>(node: &Node, rate: f64, rng: &mut R) -> Option<NodeId> {
+    if rng.gen::<f64>() < rate {
+        random_partner(node, rng)
+    } else {
+        None
+    }
+}
+
+/// The capability an agent works with: its lowest-id one
+fn primary_capability(node: &Node) -> Option<CapabilityId> {
+    node.capabilities.keys().min_by_key(|id| id.0).cloned()
+}
+
 /// An honest agent that follows the protocol faithfully
 pub struct HonestAgent {
     /// Probability of initiating an interaction each tick
@@ -23,6 +64,10 @@ pub struct HonestAgent {
     base_quality: f64,
     /// Quality variance (for realism)
     quality_variance: f64,
+    /// Baseline tone signals (random tone if unset)
+    tone_signals: Option<ToneSignals>,
+    /// Range interaction volumes are drawn from (inclusive)
+    volume_range: (f64, f64),
 }
 
 impl HonestAgent {
@@ -32,9 +77,34 @@ impl HonestAgent {
             interaction_rate,
             base_quality: 0.8,
             quality_variance: 0.1,
+            tone_signals: None,
+            volume_range: (1.0, 1.0),
+        }
+    }
+
+    /// Draw each interaction's volume uniformly from [min, max]
+    pub fn with_volume_range(mut self, min: f64, max: f64) -> Self {
+        let min = min.max(0.0);
+        self.volume_range = (min, max.max(min));
+        self
+    }
+
+    /// Sample an interaction volume
+    fn sample_volume<R: Rng>(&self, rng: &mut R) -> f64 {
+        let (min, max) = self.volume_range;
+        if min < max {
+            rng.gen_range(min..=max)
+        } else {
+            min
         }
     }
 
+    /// Emit these tone signals with every interaction
+    pub fn with_tone_signals(mut self, signals: ToneSignals) -> Self {
+        self.tone_signals = Some(signals);
+        self
+    }
+
     /// Create with custom quality settings
     pub fn with_quality(mut self, base: f64, variance: f64) -> Self {
         self.base_quality = base;
@@ -48,7 +118,8 @@ impl HonestAgent {
         // 80% chance to use existing connections, 20% to explore network
         if !node.connections.is_empty() && rng.gen::<f64>() < 0.8 {
             // Weight selection by connection strength
-            let connections: Vec<_> = node.connections.iter().collect();
+            let mut connections: Vec<_> = node.connections.iter().collect();
+            connections.sort_by_key(|(id, _)| **id);
             let total_weight: f64 = connections.iter().map(|(_, c)| c.w.value()).sum();
 
             if total_weight > 0.0 {
@@ -61,21 +132,17 @@ impl HonestAgent {
                 }
             }
             // Fallback to random
-            Some(*connections[rng.gen_range(0..connections.len())].0)
+            random_partner(node, rng)
         } else {
             // Explore: pick a random node from the network we're not already connected to
-            let candidates: Vec<_> = network.keys()
+            let mut candidates: Vec<_> = network.keys()
                 .filter(|&id| *id != node.id && !node.connections.contains_key(id))
                 .collect();
+            candidates.sort();
 
             if candidates.is_empty() {
                 // Fall back to existing connections
-                if node.connections.is_empty() {
-                    None
-                } else {
-                    let partners: Vec<_> = node.connections.keys().collect();
-                    Some(*partners[rng.gen_range(0..partners.len())])
-                }
+                random_partner(node, rng)
             } else {
                 Some(*candidates[rng.gen_range(0..candidates.len())])
             }
@@ -84,27 +151,36 @@ impl HonestAgent {
 }
 
 impl Agent for HonestAgent {
-    fn act(&self, node: &Node, network: &HashMap<NodeId, Node>, _tick: u64) -> Vec<Event> {
-        let mut rng = rand::thread_rng();
+    fn act(
+        &self,
+        node: &Node,
+        network: &HashMap<NodeId, Node>,
+        _tick: u64,
+        rng: &mut StdRng,
+    ) -> Vec<Event> {
         let mut events = Vec::new();
 
         // Maybe initiate an interaction
         if rng.gen::<f64>() < self.interaction_rate {
             // Prefer partners with higher connection weight (trust-based selection)
-            let partner = self.select_partner(node, network, &mut rng);
+            let partner = self.select_partner(node, network, rng);
 
             if let Some(partner_id) = partner {
                 // Generate quality with variance
                 let quality = (self.base_quality + rng.gen_range(-self.quality_variance..self.quality_variance))
                     .clamp(0.0, 1.0);
+                let volume = self.sample_volume(rng);
 
                 events.push(Event::Interaction {
                     from: node.id,
                     to: partner_id,
-                    volume: 1.0,
+                    volume,
+                    exchange_in: 1.0,
+                    exchange_out: 1.0,
                     quality: Score::new(quality),
                     tone: SignedScore::new(rng.gen_range(-0.2..0.3)),
-                    capability: node.capabilities.keys().next().cloned(),
+                    capability: primary_capability(node),
+                    tone_signals: self.tone_signals.clone(),
                 });
             }
         }
@@ -142,14 +218,16 @@ impl StrategicAdversary {
 }
 
 impl Agent for StrategicAdversary {
-    fn act(&self, node: &Node, _network: &HashMap<NodeId, Node>, tick: u64) -> Vec<Event> {
-        let mut rng = rand::thread_rng();
+    fn act(
+        &self,
+        node: &Node,
+        _network: &HashMap<NodeId, Node>,
+        tick: u64,
+        rng: &mut StdRng,
+    ) -> Vec<Event> {
         let mut events = Vec::new();
 
-        if rng.gen::<f64>() < self.interaction_rate && !node.connections.is_empty() {
-            let partners: Vec<_> = node.connections.keys().collect();
-            let partner = partners[rng.gen_range(0..partners.len())];
-
+        if let Some(partner) = maybe_partner(node, self.interaction_rate, rng) {
             // Switch behavior based on tick
             let quality = if tick < self.defection_tick {
                 self.good_quality
@@ -159,11 +237,14 @@ impl Agent for StrategicAdversary {
 
             events.push(Event::Interaction {
                 from: node.id,
-                to: *partner,
+                to: partner,
                 volume: 1.0,
+                exchange_in: 1.0,
+                exchange_out: 1.0,
                 quality: Score::new(quality),
                 tone: SignedScore::ZERO,
-                capability: node.capabilities.keys().next().cloned(),
+                capability: primary_capability(node),
+                tone_signals: None,
             });
         }
 
@@ -175,6 +256,167 @@ impl Agent for StrategicAdversary {
     }
 }
 
+/// An on-off adversary that alternates good and bad phases
+///
+/// Each period starts with a good phase lasting `duty_cycle` of the period,
+/// followed by a bad phase, so trust recovers before each defection.
+pub struct OscillatingAdversary {
+    /// Length of one good+bad cycle in ticks
+    period: u64,
+    /// Fraction of each period spent behaving well
+    duty_cycle: f64,
+    /// Quality during good phases
+    good_quality: f64,
+    /// Quality during bad phases
+    bad_quality: f64,
+    /// Interaction rate
+    interaction_rate: f64,
+}
+
+impl OscillatingAdversary {
+    /// Create a new oscillating adversary
+    pub fn new(period: u64, duty_cycle: f64) -> Self {
+        Self {
+            period: period.max(1),
+            duty_cycle: duty_cycle.clamp(0.0, 1.0),
+            good_quality: 0.9,
+            bad_quality: 0.2,
+            interaction_rate: 0.8,
+        }
+    }
+
+    /// Whether the agent is in its good phase at this tick
+    pub fn is_good_phase(&self, tick: u64) -> bool {
+        let good_ticks = (self.duty_cycle * self.period as f64).round() as u64;
+        tick % self.period < good_ticks
+    }
+
+    /// Quality of work produced at this tick
+    pub fn quality_at(&self, tick: u64) -> f64 {
+        if self.is_good_phase(tick) {
+            self.good_quality
+        } else {
+            self.bad_quality
+        }
+    }
+}
+
+impl Agent for OscillatingAdversary {
+    fn act(
+        &self,
+        node: &Node,
+        _network: &HashMap<NodeId, Node>,
+        tick: u64,
+        rng: &mut StdRng,
+    ) -> Vec<Event> {
+        let mut events = Vec::new();
+
+        if let Some(partner) = maybe_partner(node, self.interaction_rate, rng) {
+            events.push(Event::Interaction {
+                from: node.id,
+                to: partner,
+                volume: 1.0,
+                exchange_in: 1.0,
+                exchange_out: 1.0,
+                quality: Score::new(self.quality_at(tick)),
+                tone: SignedScore::ZERO,
+                capability: primary_capability(node),
+                tone_signals: None,
+            });
+        }
+
+        events
+    }
+
+    fn agent_type(&self) -> &'static str {
+        "oscillating_adversary"
+    }
+}
+
+/// An adversary that blends in by matching local quality while skimming value
+///
+/// Each interaction is pitched just below the mean quality its neighbors see
+/// on their own connections, so quality and variance detectors see nothing
+/// unusual, while every exchange takes more than it gives.
+pub struct CamouflageAdversary {
+    /// Fraction of each exchange withheld (gives 1 - skim for every 1 received)
+    skim: f64,
+    /// How far below the local mean quality to pitch work
+    quality_margin: f64,
+    /// Quality used when no neighbor data is visible
+    fallback_quality: f64,
+    /// Interaction rate
+    interaction_rate: f64,
+}
+
+impl CamouflageAdversary {
+    /// Create a new camouflage adversary
+    pub fn new(skim: f64) -> Self {
+        Self {
+            skim: skim.clamp(0.0, 1.0),
+            quality_margin: 0.05,
+            fallback_quality: 0.7,
+            interaction_rate: 0.8,
+        }
+    }
+
+    /// Mean quality on the connections of this node's neighbors
+    pub fn local_mean_quality(&self, node: &Node, network: &HashMap<NodeId, Node>) -> f64 {
+        let qualities: Vec<f64> = node
+            .connections
+            .keys()
+            .filter_map(|id| network.get(id))
+            .flat_map(|neighbor| {
+                neighbor
+                    .connections
+                    .iter()
+                    .filter(|(id, _)| **id != node.id)
+                    .map(|(_, conn)| conn.q.value())
+            })
+            .collect();
+
+        if qualities.is_empty() {
+            self.fallback_quality
+        } else {
+            qualities.iter().sum::<f64>() / qualities.len() as f64
+        }
+    }
+}
+
+impl Agent for CamouflageAdversary {
+    fn act(
+        &self,
+        node: &Node,
+        network: &HashMap<NodeId, Node>,
+        _tick: u64,
+        rng: &mut StdRng,
+    ) -> Vec<Event> {
+        let mut events = Vec::new();
+
+        if let Some(partner) = maybe_partner(node, self.interaction_rate, rng) {
+            let quality = self.local_mean_quality(node, network) - self.quality_margin;
+
+            events.push(Event::Interaction {
+                from: node.id,
+                to: partner,
+                volume: 1.0,
+                exchange_in: 1.0,
+                exchange_out: 1.0 - self.skim,
+                quality: Score::new(quality),
+                tone: SignedScore::ZERO,
+                capability: primary_capability(node),
+                tone_signals: None,
+            });
+        }
+
+        events
+    }
+
+    fn agent_type(&self) -> &'static str {
+        "camouflage_adversary"
+    }
+}
+
 /// A free rider that takes but doesn't give quality work
 pub struct FreeRider {
     /// Interaction rate
@@ -189,22 +431,27 @@ impl FreeRider {
 }
 
 impl Agent for FreeRider {
-    fn act(&self, node: &Node, _network: &HashMap<NodeId, Node>, _tick: u64) -> Vec<Event> {
-        let mut rng = rand::thread_rng();
+    fn act(
+        &self,
+        node: &Node,
+        _network: &HashMap<NodeId, Node>,
+        _tick: u64,
+        rng: &mut StdRng,
+    ) -> Vec<Event> {
         let mut events = Vec::new();
 
-        if rng.gen::<f64>() < self.interaction_rate && !node.connections.is_empty() {
-            let partners: Vec<_> = node.connections.keys().collect();
-            let partner = partners[rng.gen_range(0..partners.len())];
-
+        if let Some(partner) = maybe_partner(node, self.interaction_rate, rng) {
             // Always low quality
             events.push(Event::Interaction {
                 from: node.id,
-                to: *partner,
+                to: partner,
                 volume: 1.0,
+                exchange_in: 1.0,
+                exchange_out: 1.0,
                 quality: Score::new(rng.gen_range(0.1..0.3)),
                 tone: SignedScore::new(-0.3),
-                capability: node.capabilities.keys().next().cloned(),
+                capability: primary_capability(node),
+                tone_signals: None,
             });
         }
 
@@ -238,8 +485,13 @@ impl SybilCluster {
 }
 
 impl Agent for SybilCluster {
-    fn act(&self, node: &Node, _network: &HashMap<NodeId, Node>, _tick: u64) -> Vec<Event> {
-        let mut rng = rand::thread_rng();
+    fn act(
+        &self,
+        node: &Node,
+        _network: &HashMap<NodeId, Node>,
+        _tick: u64,
+        rng: &mut StdRng,
+    ) -> Vec<Event> {
         let mut events = Vec::new();
 
         // High-quality interactions within cluster
@@ -249,17 +501,19 @@ impl Agent for SybilCluster {
                     from: node.id,
                     to: *member,
                     volume: 1.0,
+                    exchange_in: 1.0,
+                    exchange_out: 1.0,
                     quality: Score::new(0.99), // Suspiciously perfect
                     tone: SignedScore::new(0.9),
-                    capability: node.capabilities.keys().next().cloned(),
+                    capability: primary_capability(node),
+                    tone_signals: None,
                 });
             }
         }
 
         // Low-quality interactions with outsiders
-        let outsiders: Vec<_> = node
-            .connections
-            .keys()
+        let outsiders: Vec<_> = sorted_partners(node)
+            .into_iter()
             .filter(|id| !self.cluster_members.contains(id))
             .collect();
 
@@ -267,11 +521,14 @@ impl Agent for SybilCluster {
             if rng.gen::<f64>() < self.external_rate {
                 events.push(Event::Interaction {
                     from: node.id,
-                    to: *partner,
+                    to: partner,
                     volume: 1.0,
+                    exchange_in: 1.0,
+                    exchange_out: 1.0,
                     quality: Score::new(0.3),
                     tone: SignedScore::new(-0.2),
-                    capability: node.capabilities.keys().next().cloned(),
+                    capability: primary_capability(node),
+                    tone_signals: None,
                 });
             }
         }
@@ -284,6 +541,165 @@ impl Agent for SybilCluster {
     }
 }
 
+/// A Sybil cluster member that gives outsiders genuinely good service
+///
+/// Unlike [`SybilCluster`], outsiders see nothing wrong: external work is
+/// honest and high quality, building external trust. Only the dense,
+/// near-perfect mutual rating inside the cluster gives it away.
+pub struct StealthSybil {
+    /// IDs of other nodes in the cluster
+    cluster_members: Vec<NodeId>,
+    /// Interaction rate with cluster members
+    internal_rate: f64,
+    /// Interaction rate with outsiders
+    external_rate: f64,
+    /// Quality of work given to outsiders
+    external_quality: f64,
+}
+
+impl StealthSybil {
+    /// Create a new stealth Sybil node
+    pub fn new(cluster_members: Vec<NodeId>) -> Self {
+        Self {
+            cluster_members,
+            internal_rate: 0.9,
+            external_rate: 0.3,
+            external_quality: 0.85,
+        }
+    }
+}
+
+impl Agent for StealthSybil {
+    fn act(
+        &self,
+        node: &Node,
+        _network: &HashMap<NodeId, Node>,
+        _tick: u64,
+        rng: &mut StdRng,
+    ) -> Vec<Event> {
+        let mut events = Vec::new();
+
+        // Mutual praise within the cluster
+        for member in &self.cluster_members {
+            if *member != node.id && rng.gen::<f64>() < self.internal_rate {
+                events.push(Event::Interaction {
+                    from: node.id,
+                    to: *member,
+                    volume: 1.0,
+                    exchange_in: 1.0,
+                    exchange_out: 1.0,
+                    quality: Score::new(0.99),
+                    tone: SignedScore::new(0.9),
+                    capability: primary_capability(node),
+                    tone_signals: None,
+                });
+            }
+        }
+
+        // Fair, good-quality work for outsiders
+        let outsiders: Vec<_> = sorted_partners(node)
+            .into_iter()
+            .filter(|id| !self.cluster_members.contains(id))
+            .collect();
+
+        for partner in outsiders {
+            if rng.gen::<f64>() < self.external_rate {
+                events.push(Event::Interaction {
+                    from: node.id,
+                    to: partner,
+                    volume: 1.0,
+                    exchange_in: 1.0,
+                    exchange_out: 1.0,
+                    quality: Score::new(self.external_quality),
+                    tone: SignedScore::new(0.5),
+                    capability: primary_capability(node),
+                    tone_signals: None,
+                });
+            }
+        }
+
+        events
+    }
+
+    fn agent_type(&self) -> &'static str {
+        "stealth_sybil"
+    }
+}
+
+/// A whitewasher that behaves badly and sheds its reputation by rejoining
+/// under a fresh identity once its trust falls too low
+pub struct Whitewasher {
+    /// Trust level below which the agent abandons its identity
+    trust_floor: f64,
+    /// Minimum interactions on an identity before it is abandoned
+    min_history: usize,
+    /// Interaction rate
+    interaction_rate: f64,
+    /// Quality of work produced
+    quality: f64,
+}
+
+impl Whitewasher {
+    /// Create a new whitewasher
+    pub fn new(trust_floor: f64) -> Self {
+        Self {
+            trust_floor,
+            min_history: 10,
+            interaction_rate: 0.8,
+            quality: 0.15,
+        }
+    }
+}
+
+impl Agent for Whitewasher {
+    fn act(
+        &self,
+        node: &Node,
+        _network: &HashMap<NodeId, Node>,
+        _tick: u64,
+        rng: &mut StdRng,
+    ) -> Vec<Event> {
+        let mut events = Vec::new();
+
+        // Reputation burned: leave and come back as someone new
+        if node.history.len() >= self.min_history && node.trust.value() < self.trust_floor {
+            let mut fresh = Node::new(NodeId(rng.gen()));
+            for state in node.capabilities.values() {
+                fresh.add_capability(state.capability.clone());
+            }
+
+            events.push(Event::node_leave(node.id));
+            events.push(Event::node_join_with_agent(
+                fresh,
+                AgentType::Whitewasher {
+                    trust_floor: self.trust_floor,
+                },
+            ));
+            return events;
+        }
+
+        if let Some(partner) = maybe_partner(node, self.interaction_rate, rng) {
+            events.push(Event::Interaction {
+                from: node.id,
+                to: partner,
+                volume: 1.0,
+                exchange_in: 1.0,
+                exchange_out: 1.0,
+                quality: Score::new(self.quality),
+                tone: SignedScore::new(-0.2),
+                capability: primary_capability(node),
+                tone_signals: None,
+            });
+        }
+
+        events
+    }
+
+    fn agent_type(&self) -> &'static str {
+        "whitewasher"
+    }
+}
+
 /// A passive agent that rarely interacts
 pub struct PassiveAgent {
     /// Very low interaction rate
@@ -306,21 +722,26 @@ impl Default for PassiveAgent {
 }
 
 impl Agent for PassiveAgent {
-    fn act(&self, node: &Node, _network: &HashMap<NodeId, Node>, _tick: u64) -> Vec<Event> {
-        let mut rng = rand::thread_rng();
+    fn act(
+        &self,
+        node: &Node,
+        _network: &HashMap<NodeId, Node>,
+        _tick: u64,
+        rng: &mut StdRng,
+    ) -> Vec<Event> {
         let mut events = Vec::new();
 
-        if rng.gen::<f64>() < self.interaction_rate && !node.connections.is_empty() {
-            let partners: Vec<_> = node.connections.keys().collect();
-            let partner = partners[rng.gen_range(0..partners.len())];
-
+        if let Some(partner) = maybe_partner(node, self.interaction_rate, rng) {
             events.push(Event::Interaction {
                 from: node.id,
-                to: *partner,
+                to: partner,
                 volume: 0.5,
+                exchange_in: 1.0,
+                exchange_out: 1.0,
                 quality: Score::new(0.6),
                 tone: SignedScore::ZERO,
-                capability: node.capabilities.keys().next().cloned(),
+                capability: primary_capability(node),
+                tone_signals: None,
             });
         }
 
@@ -335,6 +756,115 @@ impl Agent for PassiveAgent {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::SeedableRng;
+    use symbiont_core::connection::Connection;
+    use symbiont_core::constants::MU;
+
+    #[test]
+    fn test_honest_agent_volume_range() {
+        let agent = HonestAgent::new(1.0).with_volume_range(0.5, 4.0);
+        let mut node = Node::new(NodeId::from_index(1));
+        node.get_or_create_connection(NodeId::from_index(2));
+        let network = HashMap::new();
+        let mut rng = StdRng::seed_from_u64(1);
+
+        let volumes: Vec<f64> = (0..200)
+            .flat_map(|tick| agent.act(&node, &network, tick, &mut rng))
+            .filter_map(|e| match e {
+                Event::Interaction { volume, .. } => Some(volume),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(volumes.len(), 200);
+        assert!(volumes.iter().all(|v| (0.5..=4.0).contains(v)));
+        assert!(volumes.iter().any(|&v| v != volumes[0]));
+
+        // Larger volumes reinforce more
+        let delta = |volume: f64| {
+            let mut conn = Connection::new(NodeId::from_index(2));
+            let before = conn.w.value();
+            conn.process_interaction(volume, 1.0, 1.0, Score::new(0.8), SignedScore::ZERO, 0.0);
+            conn.w.value() - before
+        };
+        let (low, high) = (delta(0.5), delta(4.0));
+        assert!(high > low, "{high} vs {low}");
+
+        // ...but sublinearly: Φ scales with |Q|^μ
+        let mut conn = Connection::new(NodeId::from_index(2));
+        conn.r = 0.5;
+        conn.q = Score::new(0.8);
+        let ratio = conn.compute_reinforcement(4.0) / conn.compute_reinforcement(0.5);
+        assert!((ratio - 8f64.powf(MU)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_oscillating_agent_alternates_phases() {
+        let agent = OscillatingAdversary::new(20, 0.75);
+        assert_eq!(agent.agent_type(), "oscillating_adversary");
+
+        // 15 good ticks then 5 bad ticks, repeating
+        assert_eq!(agent.quality_at(0), 0.9);
+        assert_eq!(agent.quality_at(14), 0.9);
+        assert_eq!(agent.quality_at(15), 0.2);
+        assert_eq!(agent.quality_at(19), 0.2);
+        assert_eq!(agent.quality_at(20), 0.9);
+        assert_eq!(agent.quality_at(35), 0.2);
+
+        let mut node = Node::new(NodeId::from_index(1));
+        node.get_or_create_connection(NodeId::from_index(2));
+        let network = HashMap::new();
+        let mut rng = StdRng::seed_from_u64(1);
+
+        // Emitted interactions carry the phase's quality on each side of a boundary
+        for (tick, expected) in [(14, 0.9), (15, 0.2), (20, 0.9)] {
+            let quality = (0..100)
+                .flat_map(|_| agent.act(&node, &network, tick, &mut rng))
+                .find_map(|e| match e {
+                    Event::Interaction { quality, .. } => Some(quality.value()),
+                    _ => None,
+                })
+                .unwrap();
+            assert_eq!(quality, expected);
+        }
+    }
+
+    #[test]
+    fn test_camouflage_agent_mirrors_local_quality() {
+        let agent = CamouflageAdversary::new(0.4);
+        assert_eq!(agent.agent_type(), "camouflage_adversary");
+
+        let me = NodeId::from_index(1);
+        let neighbor = NodeId::from_index(2);
+        let mut node = Node::new(me);
+        node.get_or_create_connection(neighbor);
+
+        // The neighbor sees 0.8 quality from its other partners
+        let mut network = HashMap::new();
+        let mut neighbor_node = Node::new(neighbor);
+        for i in 10..13 {
+            neighbor_node.get_or_create_connection(NodeId::from_index(i)).q = Score::new(0.8);
+        }
+        neighbor_node.get_or_create_connection(me).q = Score::new(0.1);
+        network.insert(neighbor, neighbor_node);
+
+        assert!((agent.local_mean_quality(&node, &network) - 0.8).abs() < 1e-9);
+
+        let mut rng = StdRng::seed_from_u64(1);
+        let event = (0..100)
+            .flat_map(|_| agent.act(&node, &network, 0, &mut rng))
+            .next()
+            .unwrap();
+        match event {
+            Event::Interaction { quality, exchange_in, exchange_out, .. } => {
+                assert!((quality.value() - 0.75).abs() < 1e-9);
+                assert!(exchange_in > exchange_out);
+            }
+            other => panic!("unexpected event {other:?}"),
+        }
+
+        // No visible neighbors: fall back to a plausible default
+        assert_eq!(agent.local_mean_quality(&node, &HashMap::new()), 0.7);
+    }
 
     #[test]
     fn test_honest_agent() {
@@ -342,9 +872,67 @@ mod tests {
         assert_eq!(agent.agent_type(), "honest");
     }
 
+    #[test]
+    fn test_agents_without_partners_stay_quiet() {
+        let node = Node::new(NodeId::from_index(1));
+        let network: HashMap<_, _> = [(node.id, node.clone())].into_iter().collect();
+        let mut rng = StdRng::seed_from_u64(1);
+
+        let agents: Vec<Box<dyn Agent>> = vec![
+            Box::new(HonestAgent::new(1.0)),
+            Box::new(StrategicAdversary::new(0)),
+            Box::new(OscillatingAdversary::new(4, 0.5)),
+            Box::new(CamouflageAdversary::new(0.3)),
+            Box::new(FreeRider::new(1.0)),
+            Box::new(Whitewasher::new(0.2)),
+        ];
+        for agent in &agents {
+            for tick in 0..20 {
+                let events = agent.act(&node, &network, tick, &mut rng);
+                assert!(
+                    events.iter().all(|e| !matches!(e, Event::Interaction { .. })),
+                    "{} interacted with nobody",
+                    agent.agent_type()
+                );
+            }
+        }
+        assert_eq!(random_partner(&node, &mut rng), None);
+    }
+
     #[test]
     fn test_strategic_adversary() {
         let agent = StrategicAdversary::new(100);
         assert_eq!(agent.agent_type(), "strategic_adversary");
     }
+
+    #[test]
+    fn test_whitewasher_rejoins_when_trust_low() {
+        let agent = Whitewasher::new(0.5);
+        let mut node = Node::new(NodeId::from_index(1));
+        node.trust = Score::new(0.1);
+        let mut rng = StdRng::seed_from_u64(1);
+
+        // Not enough history on this identity yet
+        assert!(agent
+            .act(&node, &HashMap::new(), 1, &mut rng)
+            .iter()
+            .all(|e| !matches!(e, Event::NodeLeave { .. })));
+
+        for _ in 0..10 {
+            node.history.add(symbiont_core::interaction::Interaction::new(
+                node.id,
+                NodeId::from_index(2),
+            ));
+        }
+
+        let events = agent.act(&node, &HashMap::new(), 1, &mut rng);
+        assert!(matches!(events[0], Event::NodeLeave { node_id } if node_id == node.id));
+        match &events[1] {
+            Event::NodeJoin { node: fresh, agent_type } => {
+                assert_ne!(fresh.id, node.id);
+                assert!(matches!(agent_type, Some(AgentType::Whitewasher { .. })));
+            }
+            other => panic!("expected NodeJoin, got {other:?}"),
+        }
+    }
 }
@@ -0,0 +1,165 @@
+//! Simulated decisions that converge over the trust graph.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use symbiont_core::convergence::{
+    execute_atd, should_invoke_atd, AgreeToDisagreeResult, ConvergenceTracker, Position,
+};
+use symbiont_core::node::Node;
+use symbiont_core::types::{NodeId, Score, TaskId, Timestamp};
+
+/// A decision on which nodes hold numeric positions in [0, 1]
+///
+/// Each round, every node moves toward the trust-weighted mean position of
+/// its neighbors:
+///
+/// v_i' = v_i + drift × Σ_j T_j (v_j - v_i) / Σ_j T_j
+///
+/// If the tracker finds the decision stuck and criticality is low enough,
+/// agree-to-disagree settles it and positions stop moving.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Decision {
+    /// Tracker recording every round's positions
+    pub tracker: ConvergenceTracker,
+    /// Current position of each participant
+    positions: HashMap<NodeId, f64>,
+    /// How critical the decision is (high criticality blocks agree-to-disagree)
+    pub criticality: Score,
+    /// Fraction of the gap to the neighbor mean closed each round
+    pub drift_rate: f64,
+    /// Agree-to-disagree outcome, once invoked
+    pub outcome: Option<AgreeToDisagreeResult>,
+}
+
+impl Decision {
+    /// Create a decision over initial positions
+    pub fn new(task_id: TaskId, positions: HashMap<NodeId, f64>, criticality: Score) -> Self {
+        Self {
+            // Positions lie in [0, 1], so variance is at most 0.25
+            tracker: ConvergenceTracker::new(task_id, 0.25),
+            positions,
+            criticality,
+            drift_rate: 0.1,
+            outcome: None,
+        }
+    }
+
+    /// Set how fast positions drift toward neighbors
+    pub fn with_drift_rate(mut self, rate: f64) -> Self {
+        self.drift_rate = rate.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Current position of a node
+    pub fn position(&self, id: &NodeId) -> Option<f64> {
+        self.positions.get(id).copied()
+    }
+
+    /// Whether agree-to-disagree has been invoked
+    pub fn atd_invoked(&self) -> bool {
+        self.outcome.is_some()
+    }
+
+    /// Number of nodes whose dissent was recorded by agree-to-disagree
+    pub fn dissent_count(&self) -> usize {
+        self.outcome
+            .as_ref()
+            .map(|o| o.dissenting_nodes.len())
+            .unwrap_or(0)
+    }
+
+    /// Run one deliberation round against the current trust graph
+    ///
+    /// Nodes no longer in the network keep their last position but stop moving.
+    pub fn step(&mut self, nodes: &HashMap<NodeId, Node>) {
+        if self.outcome.is_some() {
+            return;
+        }
+
+        let mut ids: Vec<_> = self
+            .positions
+            .keys()
+            .filter(|id| nodes.contains_key(id))
+            .cloned()
+            .collect();
+        ids.sort();
+
+        // Update synchronously from last round's positions
+        let updated: Vec<(NodeId, f64)> = ids
+            .iter()
+            .map(|id| {
+                let value = self.positions[id];
+                let (mut pull, mut weight) = (0.0, 0.0);
+                for partner in nodes[id].connections.keys() {
+                    if let (Some(&other), Some(node)) =
+                        (self.positions.get(partner), nodes.get(partner))
+                    {
+                        pull += node.trust.value() * (other - value);
+                        weight += node.trust.value();
+                    }
+                }
+
+                if weight > 0.0 {
+                    (*id, value + self.drift_rate * pull / weight)
+                } else {
+                    (*id, value)
+                }
+            })
+            .collect();
+
+        for (id, value) in updated {
+            self.positions.insert(id, value);
+            self.tracker.record_position(Position {
+                node_id: id,
+                value,
+                confidence: nodes[&id].trust,
+                timestamp: Timestamp::now(),
+            });
+        }
+        self.tracker.advance_round();
+
+        if should_invoke_atd(&self.tracker, self.criticality) {
+            let trusts = ids.iter().map(|id| (*id, nodes[id].trust)).collect();
+            self.outcome = Some(execute_atd(&self.tracker, &trusts));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use symbiont_core::convergence::ConvergenceState;
+
+    fn linked_nodes(count: u64) -> HashMap<NodeId, Node> {
+        let mut nodes = HashMap::new();
+        for i in 0..count {
+            let mut node = Node::new(NodeId::from_index(i));
+            for j in (0..count).filter(|&j| j != i) {
+                node.get_or_create_connection(NodeId::from_index(j));
+            }
+            nodes.insert(node.id, node);
+        }
+        nodes
+    }
+
+    #[test]
+    fn test_positions_drift_toward_neighbors() {
+        let nodes = linked_nodes(4);
+        let positions = (0..4)
+            .map(|i| (NodeId::from_index(i), if i < 2 { 0.4 } else { 0.6 }))
+            .collect();
+        let mut decision =
+            Decision::new(TaskId::new(1), positions, Score::HALF).with_drift_rate(0.5);
+
+        for _ in 0..20 {
+            decision.step(&nodes);
+        }
+
+        let low = decision.position(&NodeId::from_index(0)).unwrap();
+        let high = decision.position(&NodeId::from_index(3)).unwrap();
+        assert!((high - low).abs() < 0.01, "{low} vs {high}");
+        assert_eq!(decision.tracker.state(), ConvergenceState::Converged);
+        assert!(!decision.atd_invoked());
+        assert_eq!(decision.dissent_count(), 0);
+    }
+}
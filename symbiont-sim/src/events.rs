@@ -1,11 +1,19 @@
 //! Discrete event system for simulation.
 
+use serde::{Deserialize, Serialize};
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
 use symbiont_core::defense::DefenseSignal;
+use symbiont_core::interaction::ToneSignals;
 use symbiont_core::node::Node;
 use symbiont_core::types::{CapabilityId, NodeId, Score, SignedScore};
 
 /// Agent behavior type for delayed agent assignment
-#[derive(Debug, Clone)]
+///
+/// Serialized with a snake_case `type` tag, e.g.
+/// `{"type": "strategic", "defection_tick": 200}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum AgentType {
     /// Honest agent with interaction rate
     Honest { interaction_rate: f64, base_quality: f64 },
@@ -14,9 +22,23 @@ pub enum AgentType {
     /// Free rider
     FreeRider { interaction_rate: f64 },
     /// Sybil cluster member
-    Sybil { cluster_members: Vec<NodeId> },
+    Sybil {
+        #[serde(default)]
+        cluster_members: Vec<NodeId>,
+    },
+    /// Sybil cluster member that serves outsiders well
+    StealthSybil {
+        #[serde(default)]
+        cluster_members: Vec<NodeId>,
+    },
     /// Passive agent
     Passive,
+    /// Whitewasher that rejoins under a fresh identity below a trust floor
+    Whitewasher { trust_floor: f64 },
+    /// On-off adversary alternating good and bad phases
+    Oscillating { period: u64, duty_cycle: f64 },
+    /// Adversary matching local quality while skimming exchanges
+    Camouflage { skim: f64 },
 }
 
 /// Events that can occur in the simulation
@@ -27,19 +49,31 @@ pub enum Event {
         from: NodeId,
         to: NodeId,
         volume: f64,
+        /// Value the initiator received in the exchange
+        exchange_in: f64,
+        /// Value the initiator gave in the exchange
+        exchange_out: f64,
+        /// Quality of the initiator's work, as judged by the responder
+        ///
+        /// Moves the initiator's own capability quality and the responder's
+        /// view of the initiator; the responder's own quality is untouched.
         quality: Score,
         tone: SignedScore,
         capability: Option<CapabilityId>,
+        /// Raw tone signals; when present, their computed tone replaces `tone`
+        tone_signals: Option<ToneSignals>,
     },
 
     /// A defense signal was emitted
     DefenseSignal {
         signal: DefenseSignal,
+        /// Tick the signal was emitted at (its age is measured in ticks)
+        issued_tick: u64,
     },
 
     /// A new node joins the network with optional agent behavior
     NodeJoin {
-        node: Node,
+        node: Box<Node>,
         agent_type: Option<AgentType>,
     },
 
@@ -47,6 +81,17 @@ pub enum Event {
     NodeLeave {
         node_id: NodeId,
     },
+
+    /// A member leaves, chosen with probability proportional to (1 - trust)
+    LowTrustLeave,
+
+    /// Split the network: `side` is cut off from every other node
+    Partition {
+        side: Vec<NodeId>,
+    },
+
+    /// Restore the connections severed by the last partition
+    Heal,
 }
 
 impl Event {
@@ -60,26 +105,69 @@ impl Event {
             from,
             to,
             volume: 1.0,
+            exchange_in: 1.0,
+            exchange_out: 1.0,
             quality,
             tone: SignedScore::ZERO,
             capability: None,
+            tone_signals: None,
         }
     }
 
+    /// Create a defense signal event stamped with the emitting tick
+    pub fn defense_signal(signal: DefenseSignal, issued_tick: u64) -> Self {
+        Self::DefenseSignal { signal, issued_tick }
+    }
+
     /// Create a node join event without agent
     pub fn node_join(node: Node) -> Self {
-        Self::NodeJoin { node, agent_type: None }
+        Self::NodeJoin { node: Box::new(node), agent_type: None }
     }
 
     /// Create a node join event with agent behavior
     pub fn node_join_with_agent(node: Node, agent_type: AgentType) -> Self {
-        Self::NodeJoin { node, agent_type: Some(agent_type) }
+        Self::NodeJoin {
+            node: Box::new(node),
+            agent_type: Some(agent_type),
+        }
     }
 
     /// Create a node leave event
     pub fn node_leave(node_id: NodeId) -> Self {
         Self::NodeLeave { node_id }
     }
+
+    /// Create a leave event biased toward low-trust members
+    pub fn low_trust_leave() -> Self {
+        Self::LowTrustLeave
+    }
+
+    /// Create a partition event isolating `side` from the rest
+    pub fn partition(side: Vec<NodeId>) -> Self {
+        Self::Partition { side }
+    }
+
+    /// Create a heal event
+    pub fn heal() -> Self {
+        Self::Heal
+    }
+
+    /// Key fixing the order events are processed in within a tick
+    ///
+    /// Kinds go joins, topology changes, interactions, defense signals, then
+    /// departures, so every interaction sees the same membership and every
+    /// signal sees the tick's interactions. Within a kind, events order by
+    /// (from, to) for interactions and (sender, threat) for signals.
+    pub fn order_key(&self) -> (u8, Option<NodeId>, Option<NodeId>) {
+        match self {
+            Self::NodeJoin { node, .. } => (0, Some(node.id), None),
+            Self::Partition { .. } | Self::Heal => (1, None, None),
+            Self::Interaction { from, to, .. } => (2, Some(*from), Some(*to)),
+            Self::DefenseSignal { signal, .. } => (3, Some(signal.sender), Some(signal.threat)),
+            Self::NodeLeave { node_id } => (4, Some(*node_id), None),
+            Self::LowTrustLeave => (5, None, None),
+        }
+    }
 }
 
 /// A scheduled event with timing
@@ -98,11 +186,82 @@ impl ScheduledEvent {
     }
 }
 
+/// What fires when a heap entry comes due
+#[derive(Debug)]
+enum Payload {
+    /// A one-shot event
+    Once(Box<Event>),
+    /// An occurrence of the recurrence at this index
+    Recurring(usize),
+}
+
+/// Heap entry ordering scheduled events by tick, then by insertion order
+#[derive(Debug)]
+struct QueuedEvent {
+    /// When to fire
+    at_tick: u64,
+    /// Monotonic insertion sequence (FIFO tie-break within a tick)
+    seq: u64,
+    /// What to fire
+    payload: Payload,
+}
+
+impl PartialEq for QueuedEvent {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for QueuedEvent {}
+
+impl PartialOrd for QueuedEvent {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for QueuedEvent {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.at_tick
+            .cmp(&other.at_tick)
+            .then(self.seq.cmp(&other.seq))
+    }
+}
+
+/// Builds a fresh event for each firing of a recurring schedule
+pub type EventFn = Box<dyn Fn(u64) -> Event + Send>;
+
+/// A periodic event source
+struct Recurrence {
+    /// Ticks between firings
+    interval: u64,
+    /// Firings left (None = unbounded)
+    remaining: Option<u32>,
+    /// Event factory, given the firing tick
+    event_fn: EventFn,
+}
+
+impl std::fmt::Debug for Recurrence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Recurrence")
+            .field("interval", &self.interval)
+            .field("remaining", &self.remaining)
+            .finish_non_exhaustive()
+    }
+}
+
 /// Event scheduler for time-based events
+///
+/// Backed by a min-heap so scheduling is O(log n). Events due at the
+/// same tick are returned in the order they were scheduled.
 #[derive(Debug, Default)]
 pub struct EventScheduler {
-    /// Scheduled events (sorted by tick)
-    events: Vec<ScheduledEvent>,
+    /// Scheduled events (min-heap on tick, then insertion order)
+    events: BinaryHeap<Reverse<QueuedEvent>>,
+    /// Next insertion sequence number
+    next_seq: u64,
+    /// Recurring event sources, referenced by heap entries
+    recurring: Vec<Recurrence>,
 }
 
 impl EventScheduler {
@@ -111,21 +270,76 @@ impl EventScheduler {
         Self::default()
     }
 
+    /// Push a heap entry with the next sequence number
+    fn enqueue(&mut self, at_tick: u64, payload: Payload) {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.events.push(Reverse(QueuedEvent {
+            at_tick,
+            seq,
+            payload,
+        }));
+    }
+
     /// Schedule an event
     pub fn schedule(&mut self, at_tick: u64, event: Event) {
-        self.events.push(ScheduledEvent::new(at_tick, event));
-        self.events.sort_by_key(|e| e.at_tick);
+        self.enqueue(at_tick, Payload::Once(Box::new(event)));
+    }
+
+    /// Schedule an event every `interval` ticks from `start_tick`
+    ///
+    /// `event_fn` receives the firing tick and builds a fresh event each time.
+    /// `count` limits the number of firings; `None` repeats forever.
+    pub fn schedule_recurring<F>(
+        &mut self,
+        start_tick: u64,
+        interval: u64,
+        count: Option<u32>,
+        event_fn: F,
+    ) where
+        F: Fn(u64) -> Event + Send + 'static,
+    {
+        if count == Some(0) {
+            return;
+        }
+
+        let index = self.recurring.len();
+        self.recurring.push(Recurrence {
+            interval: interval.max(1),
+            remaining: count,
+            event_fn: Box::new(event_fn),
+        });
+        self.enqueue(start_tick, Payload::Recurring(index));
     }
 
     /// Get events due at the given tick
+    ///
+    /// Firing a recurring event enqueues its next occurrence.
     pub fn due_at(&mut self, tick: u64) -> Vec<Event> {
         let mut due = Vec::new();
-        while let Some(scheduled) = self.events.first() {
-            if scheduled.at_tick <= tick {
-                due.push(self.events.remove(0).event);
-            } else {
+        while let Some(Reverse(queued)) = self.events.peek() {
+            if queued.at_tick > tick {
                 break;
             }
+            let Some(Reverse(queued)) = self.events.pop() else {
+                break;
+            };
+
+            match queued.payload {
+                Payload::Once(event) => due.push(*event),
+                Payload::Recurring(index) => {
+                    let recurrence = &mut self.recurring[index];
+                    due.push((recurrence.event_fn)(queued.at_tick));
+
+                    if let Some(remaining) = recurrence.remaining.as_mut() {
+                        *remaining -= 1;
+                    }
+                    if recurrence.remaining != Some(0) {
+                        let next = queued.at_tick + recurrence.interval;
+                        self.enqueue(next, Payload::Recurring(index));
+                    }
+                }
+            }
         }
         due
     }
@@ -137,7 +351,7 @@ impl EventScheduler {
 
     /// Get the next scheduled tick
     pub fn next_tick(&self) -> Option<u64> {
-        self.events.first().map(|e| e.at_tick)
+        self.events.peek().map(|Reverse(e)| e.at_tick)
     }
 }
 
@@ -167,4 +381,72 @@ mod tests {
         // Tick 15 event still pending
         assert!(scheduler.has_pending());
     }
+
+    #[test]
+    fn test_event_scheduler_large_out_of_order() {
+        let mut scheduler = EventScheduler::new();
+
+        // Schedule 10k events in a scrambled tick order, several per tick
+        let count = 10_000u64;
+        for i in 0..count {
+            let tick = (i * 7919) % 1000;
+            scheduler.schedule(tick, Event::node_leave(NodeId::from_index(i)));
+        }
+        assert_eq!(scheduler.next_tick(), Some(0));
+
+        let mut seen = 0u64;
+        for tick in 0..1000 {
+            let events = scheduler.due_at(tick);
+            let mut last_index = None;
+            for event in events {
+                let Event::NodeLeave { node_id } = event else {
+                    panic!("unexpected event type");
+                };
+                let index = u64::from_le_bytes(node_id.0[..8].try_into().unwrap());
+                // Every event popped belongs to this tick
+                assert_eq!((index * 7919) % 1000, tick);
+                // FIFO within the tick
+                if let Some(last) = last_index {
+                    assert!(index > last);
+                }
+                last_index = Some(index);
+                seen += 1;
+            }
+        }
+
+        assert_eq!(seen, count);
+        assert!(!scheduler.has_pending());
+    }
+
+    #[test]
+    fn test_recurring_event() {
+        let mut scheduler = EventScheduler::new();
+        scheduler.schedule_recurring(10, 10, Some(5), |tick| {
+            Event::node_leave(NodeId::from_index(tick))
+        });
+
+        let mut fired = Vec::new();
+        for tick in 0..200 {
+            for event in scheduler.due_at(tick) {
+                let Event::NodeLeave { node_id } = event else {
+                    panic!("unexpected event type");
+                };
+                assert_eq!(node_id, NodeId::from_index(tick));
+                fired.push(tick);
+            }
+        }
+
+        assert_eq!(fired, vec![10, 20, 30, 40, 50]);
+        assert!(!scheduler.has_pending());
+    }
+
+    #[test]
+    fn test_unbounded_recurring_event() {
+        let mut scheduler = EventScheduler::new();
+        scheduler.schedule_recurring(0, 3, None, |_| Event::node_leave(NodeId::from_index(0)));
+
+        // Catching up over a long gap fires every missed occurrence
+        assert_eq!(scheduler.due_at(30).len(), 11);
+        assert_eq!(scheduler.next_tick(), Some(33));
+    }
 }
@@ -0,0 +1,167 @@
+//! Reproducible interaction streams without agents.
+//!
+//! Drives the trust engine directly: events come from a seeded RNG over a
+//! fixed topology, so benchmarks of [`Network::handle_event`] measure the
+//! engine rather than agent dispatch.
+//!
+//! [`Network::handle_event`]: crate::network::Network::handle_event
+
+use crate::events::Event;
+use crate::topology::Topology;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Serialize;
+use symbiont_core::types::{NodeId, Score};
+
+/// Shape and rates of a generated interaction stream
+#[derive(Debug, Clone, Serialize)]
+pub struct InteractionStreamConfig {
+    /// Number of nodes, ids from `NodeId::from_index(0)` upward
+    pub node_count: usize,
+    /// Who may interact with whom
+    pub topology: Topology,
+    /// Link probability for the Random topology
+    pub connection_probability: f64,
+    /// Chance each node starts an interaction per round
+    pub interaction_rate: f64,
+    /// Range each node's underlying quality is drawn from
+    pub quality_range: (f64, f64),
+    /// Per-interaction quality jitter, ± this much around the initiator's
+    pub quality_noise: f64,
+}
+
+impl Default for InteractionStreamConfig {
+    fn default() -> Self {
+        Self {
+            node_count: 100,
+            topology: Topology::Random,
+            connection_probability: 0.1,
+            interaction_rate: 0.5,
+            quality_range: (0.3, 0.9),
+            quality_noise: 0.1,
+        }
+    }
+}
+
+impl InteractionStreamConfig {
+    /// Set node count
+    pub fn with_nodes(mut self, count: usize) -> Self {
+        self.node_count = count;
+        self
+    }
+
+    /// Set the interaction topology
+    pub fn with_topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    /// Set the chance each node interacts per round
+    pub fn with_interaction_rate(mut self, rate: f64) -> Self {
+        self.interaction_rate = rate.clamp(0.0, 1.0);
+        self
+    }
+}
+
+/// Endless, deterministic stream of interaction events
+///
+/// Each node draws a fixed quality from `quality_range`. Then, round after
+/// round, every node in index order starts an interaction with probability
+/// `interaction_rate`, picking a topology neighbor uniformly; the event's
+/// quality rates the initiator, per [`Event::Interaction`], jittered by
+/// `quality_noise`. The same config and seed always give the same events.
+/// Empty if no node can interact.
+pub fn generate_interactions(
+    config: &InteractionStreamConfig,
+    seed: u64,
+) -> impl Iterator<Item = Event> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let n = config.node_count;
+
+    let mut neighbors = vec![Vec::new(); n];
+    for (i, j) in config.topology.edges(n, config.connection_probability, &mut rng) {
+        neighbors[i].push(j);
+        neighbors[j].push(i);
+    }
+    let (low, high) = config.quality_range;
+    let quality: Vec<f64> = (0..n).map(|_| low + (high - low) * rng.gen::<f64>()).collect();
+
+    let rate = config.interaction_rate;
+    let noise = config.quality_noise;
+    let live = rate > 0.0 && neighbors.iter().any(|partners| !partners.is_empty());
+    let mut initiator = 0;
+
+    std::iter::from_fn(move || {
+        if !live {
+            return None;
+        }
+        loop {
+            let from = initiator;
+            initiator = (initiator + 1) % n;
+            let partners = &neighbors[from];
+            if partners.is_empty() || rng.gen::<f64>() >= rate {
+                continue;
+            }
+
+            let to = partners[rng.gen_range(0..partners.len())];
+            let jitter = noise * (2.0 * rng.gen::<f64>() - 1.0);
+            return Some(Event::interaction(
+                NodeId::from_index(from as u64),
+                NodeId::from_index(to as u64),
+                Score::new(quality[from] + jitter),
+            ));
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::{Network, NetworkConfig};
+
+    /// (from, to, quality) of each interaction event
+    fn summarize(events: impl Iterator<Item = Event>) -> Vec<(NodeId, NodeId, Score)> {
+        events
+            .map(|event| match event {
+                Event::Interaction { from, to, quality, .. } => (from, to, quality),
+                other => panic!("unexpected event {other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_same_seed_same_stream() {
+        let config = InteractionStreamConfig::default()
+            .with_nodes(40)
+            .with_topology(Topology::ScaleFree(2));
+        let first = summarize(generate_interactions(&config, 7).take(500));
+        let second = summarize(generate_interactions(&config, 7).take(500));
+        assert_eq!(first.len(), 500);
+        assert_eq!(first, second);
+
+        let other = summarize(generate_interactions(&config, 8).take(500));
+        assert_ne!(first, other);
+
+        // Nodes only interact with neighbors, never themselves
+        assert!(first.iter().all(|(from, to, _)| from != to));
+
+        // Nothing to generate without links or without activity
+        let isolated = config.clone().with_nodes(1);
+        assert_eq!(generate_interactions(&isolated, 7).count(), 0);
+        let idle = config.with_interaction_rate(0.0);
+        assert_eq!(generate_interactions(&idle, 7).count(), 0);
+    }
+
+    #[test]
+    fn test_stream_drives_network_directly() {
+        let config = InteractionStreamConfig::default().with_nodes(20);
+        let mut network = Network::from_config(NetworkConfig::default().with_nodes(20));
+        for event in generate_interactions(&config, 3).take(200) {
+            network.handle_event(event);
+        }
+        // Both sides record each interaction
+        let interactions: u64 =
+            network.nodes().values().map(|n| n.interaction_count()).sum();
+        assert_eq!(interactions, 2 * 200);
+    }
+}
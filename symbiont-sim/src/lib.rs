@@ -5,18 +5,27 @@
 //! ## Modules
 //!
 //! - [`network`] - Simulated network of nodes
+//! - [`decision`] - Decisions converging over the trust graph
 //! - [`agents`] - Agent behavior models
 //! - [`scenarios`] - Predefined simulation scenarios
 //! - [`metrics`] - Metrics collection and export
+//! - [`replay`] - Replaying recorded interaction logs
 //! - [`events`] - Discrete event system
+//! - [`generator`] - Reproducible interaction streams without agents
 //! - [`runner`] - Simulation executor
+//! - [`topology`] - Initial connection graphs and capability assignment
 
 pub mod agents;
+pub mod decision;
 pub mod events;
+pub mod generator;
 pub mod metrics;
 pub mod network;
+pub mod replay;
 pub mod runner;
 pub mod scenarios;
+pub mod topology;
 
-pub use network::Network;
+pub use generator::{generate_interactions, InteractionStreamConfig};
+pub use network::{Network, NetworkSnapshot, SnapshotError};
 pub use runner::SimulationRunner;
@@ -1,24 +1,47 @@
 //! Metrics collection for simulation analysis.
 
-use std::collections::HashMap;
+use crate::decision::Decision;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::io::Write;
-use symbiont_core::types::{NodeId, Score};
+use symbiont_core::convergence::ConvergenceState;
+use symbiont_core::math::percentile;
+use symbiont_core::node::{NodeStatus, StatusChange};
+use symbiont_core::trust::TrustLevel;
+use symbiont_core::types::{Hash, NodeId, Score};
 
 /// Collected metrics from simulation
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct MetricsCollector {
     /// Trust distribution over time
     pub trust_history: Vec<TrustSnapshot>,
+    /// Trust of each node at every tick it was present
+    pub node_trust_history: HashMap<NodeId, Vec<(u64, Score)>>,
     /// Interaction counts per node pair
+    #[serde(with = "pair_counts")]
     pub interaction_counts: HashMap<(NodeId, NodeId), u64>,
     /// Quality history per node
     pub quality_history: HashMap<NodeId, Vec<Score>>,
     /// Detection events
     pub detection_events: Vec<DetectionEvent>,
+    /// Convergence of the decision under deliberation, per tick
+    pub decision_history: Vec<DecisionSnapshot>,
+    /// Defense signals dropped for being older than the network's max age
+    #[serde(default)]
+    pub stale_signals_dropped: u64,
+    /// Seed of the run these metrics came from
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// Hash of the network configuration of the run
+    #[serde(default)]
+    pub config_hash: Option<Hash>,
+    /// Number of transitions into each node status
+    #[serde(default)]
+    pub status_transitions: HashMap<NodeStatus, u64>,
 }
 
 /// Snapshot of trust distribution at a tick
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TrustSnapshot {
     /// Tick number
     pub tick: u64,
@@ -30,14 +53,38 @@ pub struct TrustSnapshot {
     pub min: f64,
     /// Maximum trust
     pub max: f64,
+    /// Median trust
+    #[serde(default)]
+    pub median: f64,
     /// Number of high-trust nodes (> 0.7)
     pub high_trust_count: usize,
     /// Number of low-trust nodes (< 0.3)
     pub low_trust_count: usize,
+    /// Gini coefficient of trust (0 = equal, → 1 = concentrated)
+    #[serde(default)]
+    pub gini: f64,
+    /// Number of nodes at each trust level, ordered as [`TrustLevel::ALL`]
+    #[serde(default)]
+    pub level_counts: [usize; 5],
+}
+
+/// Convergence state of a decision at a tick
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecisionSnapshot {
+    /// Tick number
+    pub tick: u64,
+    /// Convergence score
+    pub score: Score,
+    /// Convergence state
+    pub state: ConvergenceState,
+    /// Whether agree-to-disagree has been invoked
+    pub atd_invoked: bool,
+    /// Number of dissenting nodes recorded by agree-to-disagree
+    pub dissent_count: usize,
 }
 
 /// A detection event (adversary detected)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DetectionEvent {
     /// When detected
     pub tick: u64,
@@ -56,22 +103,36 @@ impl MetricsCollector {
     }
 
     /// Record trust distribution at a tick
+    ///
+    /// NaN or infinite trust values are left out of the snapshot.
     pub fn record_trust_distribution(&mut self, tick: u64, trusts: &[Score]) {
-        if trusts.is_empty() {
+        let mut values: Vec<f64> = trusts.iter().map(|s| s.value()).collect();
+        self.record_trust_values(tick, &mut values);
+    }
+
+    /// Record trust distribution from raw values, sorting them in place
+    ///
+    /// Lets a caller reuse one buffer every tick; NaN or infinite values are
+    /// dropped from it.
+    pub fn record_trust_values(&mut self, tick: u64, values: &mut Vec<f64>) {
+        values.retain(|v| v.is_finite());
+        if values.is_empty() {
             return;
         }
-
-        let values: Vec<f64> = trusts.iter().map(|s| s.value()).collect();
+        values.sort_by(|a, b| a.total_cmp(b));
         let n = values.len() as f64;
 
         let mean = values.iter().sum::<f64>() / n;
         let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
         let std_dev = variance.sqrt();
-        let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
-        let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let min = values[0];
+        let max = values[values.len() - 1];
+        let median = percentile(values, 0.5);
 
         let high_trust_count = values.iter().filter(|&&v| v > 0.7).count();
         let low_trust_count = values.iter().filter(|&&v| v < 0.3).count();
+        let gini = gini_coefficient(values);
+        let level_counts = trust_level_histogram(values);
 
         self.trust_history.push(TrustSnapshot {
             tick,
@@ -79,11 +140,48 @@ impl MetricsCollector {
             std_dev,
             min,
             max,
+            median,
             high_trust_count,
             low_trust_count,
+            gini,
+            level_counts,
         });
     }
 
+    /// Record one node's trust at a tick
+    pub fn record_node_trust(&mut self, tick: u64, node_id: NodeId, trust: Score) {
+        self.node_trust_history
+            .entry(node_id)
+            .or_default()
+            .push((tick, trust));
+    }
+
+    /// Trust trajectory of a single node, from the tick it first appeared
+    pub fn node_trajectory(&self, id: &NodeId) -> Option<&[(u64, Score)]> {
+        self.node_trust_history.get(id).map(|t| t.as_slice())
+    }
+
+    /// Gini coefficient of trust at each recorded tick
+    pub fn trust_gini_history(&self) -> Vec<(u64, f64)> {
+        self.trust_history.iter().map(|s| (s.tick, s.gini)).collect()
+    }
+
+    /// Whether trust has settled over the last `window` snapshots
+    ///
+    /// Fits a least-squares line to mean trust and to its std_dev across the
+    /// window; trust has converged when each fitted line drifts by less than
+    /// `epsilon` from the first snapshot of the window to the last.
+    pub fn has_converged(&self, window: usize, epsilon: f64) -> bool {
+        if window < 2 || self.trust_history.len() < window {
+            return false;
+        }
+
+        let recent = &self.trust_history[self.trust_history.len() - window..];
+        let drift = |values: Vec<f64>| (trend_slope(&values) * (window - 1) as f64).abs();
+        drift(recent.iter().map(|s| s.mean).collect()) < epsilon
+            && drift(recent.iter().map(|s| s.std_dev).collect()) < epsilon
+    }
+
     /// Record an interaction
     pub fn record_interaction(&mut self, from: NodeId, to: NodeId, quality: Score) {
         let key = if from < to { (from, to) } else { (to, from) };
@@ -105,6 +203,70 @@ impl MetricsCollector {
         });
     }
 
+    /// Count a node's change of status
+    pub fn record_status_change(&mut self, change: &StatusChange) {
+        *self.status_transitions.entry(change.to).or_default() += 1;
+    }
+
+    /// Record a defense signal dropped as stale
+    pub fn record_stale_signal(&mut self) {
+        self.stale_signals_dropped += 1;
+    }
+
+    /// Record which seed and configuration produced these metrics
+    pub fn set_run_identity(&mut self, seed: u64, config_hash: Hash) {
+        self.seed = Some(seed);
+        self.config_hash = Some(config_hash);
+    }
+
+    /// Blake3 digest of the run identity, trust history and detections
+    ///
+    /// The canonical form writes floats to 9 decimals and sorts detections,
+    /// so summation-order noise from hash map iteration cannot change it.
+    pub fn digest(&self) -> Hash {
+        use std::fmt::Write as _;
+
+        let mut canonical = String::new();
+        let config = self.config_hash.map(|h| h.to_hex()).unwrap_or_default();
+        let _ = writeln!(canonical, "seed={:?} config={config}", self.seed);
+
+        for s in &self.trust_history {
+            let _ = writeln!(
+                canonical,
+                "trust {} {:.9} {:.9} {:.9} {:.9} {:.9} {:?}",
+                s.tick, s.mean, s.std_dev, s.min, s.max, s.gini, s.level_counts
+            );
+        }
+
+        let mut detections: Vec<_> = self.detection_events.iter().collect();
+        detections.sort_by(|a, b| {
+            (a.tick, a.node_id, &a.threat_type).cmp(&(b.tick, b.node_id, &b.threat_type))
+        });
+        for d in detections {
+            let _ = writeln!(
+                canonical,
+                "detection {} {} {} {:.9}",
+                d.tick,
+                d.node_id.to_hex(),
+                d.threat_type,
+                d.confidence.value()
+            );
+        }
+
+        Hash::compute(canonical.as_bytes())
+    }
+
+    /// Record the convergence state of a decision
+    pub fn record_decision(&mut self, tick: u64, decision: &Decision) {
+        self.decision_history.push(DecisionSnapshot {
+            tick,
+            score: decision.tracker.score(),
+            state: decision.tracker.state(),
+            atd_invoked: decision.atd_invoked(),
+            dissent_count: decision.dissent_count(),
+        });
+    }
+
     /// Get summary statistics
     pub fn summary(&self) -> MetricsSummary {
         let total_interactions: u64 = self.interaction_counts.values().sum();
@@ -129,17 +291,29 @@ impl MetricsCollector {
             final_mean_trust: final_trust,
             trust_convergence,
             detection_count: self.detection_events.len(),
+            decision_state: self.decision_history.last().map(|d| d.state),
+            dissent_count: self.decision_history.last().map_or(0, |d| d.dissent_count),
+            stale_signals_dropped: self.stale_signals_dropped,
+            final_level_counts: self
+                .trust_history
+                .last()
+                .map_or([0; 5], |s| s.level_counts),
         }
     }
 
     /// Export trust history to CSV
     pub fn export_trust_csv<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        writeln!(writer, "tick,mean,std_dev,min,max,high_trust,low_trust")?;
+        writeln!(
+            writer,
+            "tick,mean,std_dev,min,max,high_trust,low_trust,gini,median,\
+             very_low,low,medium,high,very_high"
+        )?;
 
         for snapshot in &self.trust_history {
+            let [very_low, low, medium, high, very_high] = snapshot.level_counts;
             writeln!(
                 writer,
-                "{},{:.4},{:.4},{:.4},{:.4},{},{}",
+                "{},{:.4},{:.4},{:.4},{:.4},{},{},{:.4},{:.4},{},{},{},{},{}",
                 snapshot.tick,
                 snapshot.mean,
                 snapshot.std_dev,
@@ -147,12 +321,39 @@ impl MetricsCollector {
                 snapshot.max,
                 snapshot.high_trust_count,
                 snapshot.low_trust_count,
+                snapshot.gini,
+                snapshot.median,
+                very_low,
+                low,
+                medium,
+                high,
+                very_high,
             )?;
         }
 
         Ok(())
     }
 
+    /// Export per-node trust to CSV, one row per (tick, node)
+    pub fn export_node_trust_csv<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writeln!(writer, "tick,node_id,trust")?;
+
+        let mut rows: Vec<_> = self
+            .node_trust_history
+            .iter()
+            .flat_map(|(id, trajectory)| {
+                trajectory.iter().map(move |(tick, trust)| (*tick, *id, *trust))
+            })
+            .collect();
+        rows.sort_by_key(|(tick, id, _)| (*tick, *id));
+
+        for (tick, id, trust) in rows {
+            writeln!(writer, "{},{},{:.4}", tick, id, trust.value())?;
+        }
+
+        Ok(())
+    }
+
     /// Export detection events to CSV
     pub fn export_detections_csv<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
         writeln!(writer, "tick,node_id,threat_type,confidence")?;
@@ -170,10 +371,82 @@ impl MetricsCollector {
 
         Ok(())
     }
+
+    /// Export the latest tick's metrics in Prometheus text exposition format
+    ///
+    /// Trust gauges are omitted until a trust snapshot has been recorded.
+    pub fn export_prometheus<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        let mut metric = |name: &str, kind: &str, help: &str, value: String| {
+            writeln!(writer, "# HELP {name} {help}")?;
+            writeln!(writer, "# TYPE {name} {kind}")?;
+            writeln!(writer, "{name} {value}")
+        };
+
+        if let Some(latest) = self.trust_history.last() {
+            metric("symbiont_tick", "gauge", "Latest simulated tick", latest.tick.to_string())?;
+            metric("symbiont_mean_trust", "gauge", "Mean node trust", latest.mean.to_string())?;
+            metric(
+                "symbiont_trust_stddev",
+                "gauge",
+                "Standard deviation of node trust",
+                latest.std_dev.to_string(),
+            )?;
+            metric(
+                "symbiont_high_trust_nodes",
+                "gauge",
+                "Nodes with trust above 0.7",
+                latest.high_trust_count.to_string(),
+            )?;
+        }
+
+        metric(
+            "symbiont_detections_total",
+            "counter",
+            "Adversary detections",
+            self.detection_events.len().to_string(),
+        )?;
+        metric(
+            "symbiont_interactions_total",
+            "counter",
+            "Interactions between nodes",
+            self.interaction_counts.values().sum::<u64>().to_string(),
+        )
+    }
+
+    /// Export all collected metrics as a single JSON document
+    ///
+    /// Interaction counts are keyed by `"<node_a>-<node_b>"` since JSON
+    /// object keys must be strings.
+    pub fn export_json<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        let interaction_counts: BTreeMap<String, u64> = self
+            .interaction_counts
+            .iter()
+            .map(|((a, b), count)| (format!("{a}-{b}"), *count))
+            .collect();
+
+        let export = MetricsExport {
+            summary: self.summary(),
+            trust_history: &self.trust_history,
+            interaction_counts,
+            detection_events: &self.detection_events,
+        };
+
+        serde_json::to_writer_pretty(&mut *writer, &export)?;
+        writeln!(writer)
+    }
+}
+
+/// JSON document layout for [`MetricsCollector::export_json`]
+#[derive(Serialize)]
+struct MetricsExport<'a> {
+    summary: MetricsSummary,
+    trust_history: &'a [TrustSnapshot],
+    interaction_counts: BTreeMap<String, u64>,
+    detection_events: &'a [DetectionEvent],
 }
 
 /// Summary of simulation metrics
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MetricsSummary {
     /// Total simulation ticks
     pub total_ticks: u64,
@@ -185,6 +458,16 @@ pub struct MetricsSummary {
     pub trust_convergence: f64,
     /// Number of adversaries detected
     pub detection_count: usize,
+    /// Final convergence state of the decision, if one was deliberated
+    pub decision_state: Option<ConvergenceState>,
+    /// Dissenting nodes recorded by agree-to-disagree
+    pub dissent_count: usize,
+    /// Defense signals dropped as stale
+    #[serde(default)]
+    pub stale_signals_dropped: u64,
+    /// Nodes at each trust level in the last snapshot, ordered as [`TrustLevel::ALL`]
+    #[serde(default)]
+    pub final_level_counts: [usize; 5],
 }
 
 impl std::fmt::Display for MetricsSummary {
@@ -194,7 +477,92 @@ impl std::fmt::Display for MetricsSummary {
         writeln!(f, "  Interactions: {}", self.total_interactions)?;
         writeln!(f, "  Final Mean Trust: {:.3}", self.final_mean_trust)?;
         writeln!(f, "  Trust Convergence: {:.3}", self.trust_convergence)?;
-        writeln!(f, "  Detections: {}", self.detection_count)
+        writeln!(f, "  Detections: {}", self.detection_count)?;
+        if self.stale_signals_dropped > 0 {
+            writeln!(f, "  Stale Signals Dropped: {}", self.stale_signals_dropped)?;
+        }
+        let [very_low, low, medium, high, very_high] = self.final_level_counts;
+        writeln!(
+            f,
+            "  Trust Levels: {very_low} very low, {low} low, {medium} medium, \
+             {high} high, {very_high} very high"
+        )?;
+        if let Some(state) = self.decision_state {
+            writeln!(f, "  Decision: {state:?} ({} dissenting)", self.dissent_count)?;
+        }
+        Ok(())
+    }
+}
+
+/// Count trust values in each trust level, ordered as [`TrustLevel::ALL`]
+fn trust_level_histogram(trusts: &[f64]) -> [usize; 5] {
+    let mut counts = [0; 5];
+    for &trust in trusts {
+        let level = TrustLevel::from_score(Score::new(trust));
+        if let Some(i) = TrustLevel::ALL.iter().position(|&l| l == level) {
+            counts[i] += 1;
+        }
+    }
+    counts
+}
+
+/// Gini coefficient of non-negative values sorted ascending
+///
+/// G = 2·Σ(i·x_i) / (n·Σx) − (n + 1) / n, with i from 1
+fn gini_coefficient(sorted: &[f64]) -> f64 {
+    let n = sorted.len() as f64;
+    let total: f64 = sorted.iter().sum();
+    if sorted.is_empty() || total <= 0.0 {
+        return 0.0;
+    }
+
+    let weighted: f64 = sorted
+        .iter()
+        .enumerate()
+        .map(|(i, x)| (i + 1) as f64 * x)
+        .sum();
+
+    2.0 * weighted / (n * total) - (n + 1.0) / n
+}
+
+/// Least-squares slope of evenly spaced values, per step
+///
+/// β = Σ(i - ī)(y_i - ȳ) / Σ(i - ī)²
+fn trend_slope(values: &[f64]) -> f64 {
+    let n = values.len() as f64;
+    if values.len() < 2 {
+        return 0.0;
+    }
+
+    let mean_i = (n - 1.0) / 2.0;
+    let mean_y = values.iter().sum::<f64>() / n;
+    let (covariance, spread) = values.iter().enumerate().fold((0.0, 0.0), |(c, s), (i, y)| {
+        let di = i as f64 - mean_i;
+        (c + di * (y - mean_y), s + di * di)
+    });
+    covariance / spread
+}
+
+/// Serialize tuple-keyed counts as a sorted list, since JSON keys must be strings
+mod pair_counts {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::HashMap;
+    use symbiont_core::types::NodeId;
+
+    pub fn serialize<S: Serializer>(
+        counts: &HashMap<(NodeId, NodeId), u64>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut entries: Vec<_> = counts.iter().map(|(&(a, b), &n)| (a, b, n)).collect();
+        entries.sort();
+        entries.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<HashMap<(NodeId, NodeId), u64>, D::Error> {
+        let entries = Vec::<(NodeId, NodeId, u64)>::deserialize(deserializer)?;
+        Ok(entries.into_iter().map(|(a, b, n)| ((a, b), n)).collect())
     }
 }
 
@@ -213,6 +581,102 @@ mod tests {
         assert!((collector.trust_history[0].mean - 0.6).abs() < 0.01);
     }
 
+    #[test]
+    fn test_has_converged() {
+        let mut collector = MetricsCollector::new();
+        assert!(!collector.has_converged(10, 0.01));
+
+        // Trust climbing steadily toward agreement
+        for tick in 0..20 {
+            let low = 0.2 + tick as f64 * 0.02;
+            collector.record_trust_distribution(tick, &[Score::new(low), Score::new(0.8)]);
+        }
+        assert!(!collector.has_converged(10, 0.01));
+
+        // Then holding, with a little jitter
+        for tick in 20..40 {
+            let jitter = if tick % 2 == 0 { 0.002 } else { -0.002 };
+            let trusts = [Score::new(0.6 + jitter), Score::new(0.8)];
+            collector.record_trust_distribution(tick, &trusts);
+        }
+        assert!(collector.has_converged(10, 0.01));
+        assert!(!collector.has_converged(30, 0.01));
+        assert!(!collector.has_converged(41, 0.01));
+    }
+
+    #[test]
+    fn test_trust_gini() {
+        let mut collector = MetricsCollector::new();
+
+        let equal = vec![Score::new(0.5); 100];
+        collector.record_trust_distribution(1, &equal);
+
+        let mut winner_take_all = vec![Score::ZERO; 99];
+        winner_take_all.push(Score::ONE);
+        collector.record_trust_distribution(2, &winner_take_all);
+
+        let history = collector.trust_gini_history();
+        assert_eq!(history[0].0, 1);
+        assert!(history[0].1.abs() < 1e-9);
+        assert_eq!(history[1].0, 2);
+        assert!(history[1].1 > 0.98);
+
+        let mut csv = Vec::new();
+        collector.export_trust_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert!(csv.starts_with("tick,mean,std_dev,min,max,high_trust,low_trust,gini,"));
+        assert_eq!(csv.lines().nth(2).unwrap().split(',').nth(7), Some("0.9900"));
+    }
+
+    #[test]
+    fn test_trust_level_histogram() {
+        let mut collector = MetricsCollector::new();
+        let trusts: Vec<Score> = [0.05, 0.1, 0.3, 0.5, 0.55, 0.59, 0.7, 0.8, 0.95, 1.0]
+            .into_iter()
+            .map(Score::new)
+            .collect();
+        collector.record_trust_distribution(1, &trusts);
+
+        assert_eq!(collector.trust_history[0].level_counts, [2, 1, 3, 1, 3]);
+        assert!((collector.trust_history[0].median - 0.57).abs() < 1e-9);
+
+        let mut csv = Vec::new();
+        collector.export_trust_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let mut lines = csv.lines();
+        assert!(lines.next().unwrap().ends_with(",very_low,low,medium,high,very_high"));
+        assert!(lines.next().unwrap().ends_with(",0.5700,2,1,3,1,3"));
+
+        let summary = collector.summary();
+        assert_eq!(summary.final_level_counts, [2, 1, 3, 1, 3]);
+        assert!(summary
+            .to_string()
+            .contains("Trust Levels: 2 very low, 1 low, 3 medium, 1 high, 3 very high"));
+    }
+
+    #[test]
+    fn test_node_trust_csv() {
+        let mut collector = MetricsCollector::new();
+        let a = NodeId::from_index(1);
+        let b = NodeId::from_index(2);
+
+        collector.record_node_trust(1, b, Score::new(0.4));
+        collector.record_node_trust(1, a, Score::new(0.5));
+        collector.record_node_trust(2, a, Score::new(0.6));
+
+        assert_eq!(collector.node_trajectory(&a).unwrap().len(), 2);
+        assert!(collector.node_trajectory(&NodeId::from_index(3)).is_none());
+
+        let mut csv = Vec::new();
+        collector.export_node_trust_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines[0], "tick,node_id,trust");
+        assert_eq!(lines[1], format!("1,{a},0.5000"));
+        assert_eq!(lines[2], format!("1,{b},0.4000"));
+        assert_eq!(lines[3], format!("2,{a},0.6000"));
+    }
+
     #[test]
     fn test_interaction_recording() {
         let mut collector = MetricsCollector::new();
@@ -225,4 +689,58 @@ mod tests {
 
         assert_eq!(*collector.interaction_counts.get(&(n1, n2)).unwrap(), 2);
     }
+
+    #[test]
+    fn test_prometheus_export() {
+        let mut collector = MetricsCollector::new();
+        collector.record_trust_distribution(7, &[Score::new(0.4), Score::new(0.8)]);
+        collector.record_interaction(NodeId::from_index(1), NodeId::from_index(2), Score::HALF);
+        collector.record_interaction(NodeId::from_index(2), NodeId::from_index(1), Score::HALF);
+        collector.record_detection(5, NodeId::from_index(2), "sybil", Score::new(0.9));
+
+        let mut buf = Vec::new();
+        collector.export_prometheus(&mut buf).unwrap();
+        let text = String::from_utf8(buf).unwrap();
+
+        assert!(text.contains("# TYPE symbiont_mean_trust gauge\n"));
+        assert!(text.contains("# TYPE symbiont_interactions_total counter\n"));
+        let value = |name: &str| -> f64 {
+            text.lines()
+                .find_map(|l| l.strip_prefix(name)?.strip_prefix(' '))
+                .unwrap()
+                .parse()
+                .unwrap()
+        };
+        assert_eq!(value("symbiont_tick"), 7.0);
+        assert!((value("symbiont_mean_trust") - 0.6).abs() < 1e-9);
+        assert!((value("symbiont_trust_stddev") - 0.2).abs() < 1e-9);
+        assert_eq!(value("symbiont_high_trust_nodes"), 1.0);
+        assert_eq!(value("symbiont_detections_total"), 1.0);
+        assert_eq!(value("symbiont_interactions_total"), 2.0);
+    }
+
+    #[test]
+    fn test_json_export_round_trip() {
+        let mut collector = MetricsCollector::new();
+
+        for tick in 1..=5 {
+            collector.record_trust_distribution(tick, &[Score::new(0.4), Score::new(0.6)]);
+        }
+        collector.record_interaction(NodeId::from_index(1), NodeId::from_index(2), Score::new(0.8));
+        collector.record_detection(3, NodeId::from_index(2), "strategic", Score::new(0.7));
+
+        let mut buf = Vec::new();
+        collector.export_json(&mut buf).unwrap();
+
+        let value: serde_json::Value = serde_json::from_slice(&buf).unwrap();
+        let history: Vec<TrustSnapshot> =
+            serde_json::from_value(value["trust_history"].clone()).unwrap();
+        let summary: MetricsSummary = serde_json::from_value(value["summary"].clone()).unwrap();
+
+        assert_eq!(history.len(), 5);
+        assert_eq!(summary.total_ticks, 5);
+        assert_eq!(history.last().unwrap().tick, 5);
+        assert_eq!(value["interaction_counts"].as_object().unwrap().len(), 1);
+        assert_eq!(value["detection_events"].as_array().unwrap().len(), 1);
+    }
 }
@@ -1,31 +1,121 @@
 //! Simulated network of Symbiont nodes.
 
-use crate::agents::{Agent, FreeRider, HonestAgent, PassiveAgent, StrategicAdversary, SybilCluster};
+use crate::agents::{
+    Agent, CamouflageAdversary, FreeRider, HonestAgent, OscillatingAdversary, PassiveAgent,
+    StealthSybil, StrategicAdversary, SybilCluster, Whitewasher,
+};
+use crate::decision::Decision;
 use crate::events::{AgentType, Event};
+use rand::rngs::StdRng;
 use rand::SeedableRng;
-use symbiont_core::constants::ADVERSARY_INTERVAL;
-use symbiont_core::defense::DefenseSignal;
-use symbiont_core::detection::{detect_all_threats, detect_collusion};
+use symbiont_core::constants::{
+    ADVERSARY_INTERVAL, ADVERSARY_INTERVAL_MAX, ADVERSARY_INTERVAL_MIN, INTERACTION_HISTORY_SIZE,
+    PRIMING_SENSITIVITY, SCAN_VARIANCE_FLOOR, SCAN_VARIANCE_SPIKE, SCAN_VARIANCE_STABLE,
+    SCAN_VARIANCE_WINDOW, SIGNAL_MAX_AGE_TICKS, STATUS_INTERVAL,
+};
+use symbiont_core::defense::{Affirmation, DefenseHandler, DefenseSignal};
+use symbiont_core::detection::{
+    detect_collusion_with, detect_rapid_trust_gain, median_trust_velocity, DetectionConfig,
+    DetectorRegistry,
+};
 use symbiont_core::types::Hash;
-use crate::metrics::MetricsCollector;
+use crate::metrics::{MetricsCollector, TrustSnapshot};
+use crate::replay::InteractionRecord;
+use crate::topology::{CapabilityAssignment, Topology};
 use rand::Rng;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::{debug, debug_span};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::io::Write;
 use symbiont_core::capability::Capability;
-use symbiont_core::node::Node;
-use symbiont_core::trust::compute_trust;
-use symbiont_core::types::NodeId;
+use symbiont_core::clock::{Clock, SystemClock, VirtualClock};
+use symbiont_core::connection::Connection;
+use symbiont_core::node::{DefenseState, Node, NodeFlag, NodeStatus, ThreatType};
+use symbiont_core::params::ProtocolParams;
+use symbiont_core::trust::{compute_global_trust, compute_trust, trust_ceiling, AffirmationStore};
+use symbiont_core::types::{CapabilityId, NodeId, Score, TaskId, Timestamp};
+use symbiont_core::workflow::Workflow;
+
+/// How node trust is computed each tick
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TrustMode {
+    /// Each node's own evidence only
+    #[default]
+    Local,
+    /// Local trust propagated over the connection graph
+    Global { iterations: usize },
+    /// Blend of the two: T = α × T_local + (1 - α) × T_global, capped at the
+    /// node's trust ceiling
+    Hybrid { alpha: BlendWeight, iterations: usize },
+}
+
+/// Blend weight in [0, 1], held in thousandths so trust modes compare exactly
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "u16")]
+pub struct BlendWeight(u16);
+
+impl TryFrom<u16> for BlendWeight {
+    type Error = String;
+
+    fn try_from(thousandths: u16) -> Result<Self, Self::Error> {
+        if thousandths > 1000 {
+            return Err(format!("blend weight {thousandths} exceeds 1000 thousandths"));
+        }
+        Ok(Self(thousandths))
+    }
+}
+
+impl BlendWeight {
+    /// Weight nearest `alpha`, clamped to [0, 1]
+    pub fn new(alpha: f64) -> Self {
+        Self((alpha.clamp(0.0, 1.0) * 1000.0).round() as u16)
+    }
+
+    /// Weight as a fraction
+    pub fn value(self) -> f64 {
+        f64::from(self.0) / 1000.0
+    }
+}
 
 /// Configuration for network creation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct NetworkConfig {
     /// Number of initial nodes
     pub node_count: usize,
     /// Capabilities to assign to nodes
     pub capabilities: Vec<Capability>,
-    /// Probability of connection between any two nodes
+    /// Probability of connection between any two nodes (Random topology)
     pub connection_probability: f64,
+    /// Shape of the initial connection graph
+    pub topology: Topology,
+    /// Which capabilities each node receives
+    pub capability_assignment: CapabilityAssignment,
     /// Random seed for reproducibility
     pub seed: Option<u64>,
+    /// Trust computation mode
+    pub trust_mode: TrustMode,
+    /// Protocol settings given to every node the network creates
+    pub params: ProtocolParams,
+    /// Defense signals more than this many ticks old are dropped unapplied
+    pub signal_max_age_ticks: u64,
+    /// Interactions retained in the history of each node the network creates
+    pub history_size: usize,
+    /// Settings for collusion detection in adversary scans
+    pub detection: DetectionConfig,
+    /// Milliseconds of protocol time per tick, driving a virtual clock
+    ///
+    /// Unset, nodes read the wall clock, so a fast simulation spans only a
+    /// few real seconds of idle and expiry time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ms_per_tick: Option<u64>,
+    /// Converged state to warm-start from instead of fresh nodes
+    ///
+    /// When set, nodes come from the snapshot and the node count,
+    /// capabilities and connection probability are ignored. The nodes take
+    /// this config's protocol settings and history size.
+    #[serde(skip)]
+    pub prior: Option<NetworkSnapshot>,
 }
 
 impl Default for NetworkConfig {
@@ -34,7 +124,16 @@ impl Default for NetworkConfig {
             node_count: 10,
             capabilities: Vec::new(),
             connection_probability: 0.3,
+            topology: Topology::Random,
+            capability_assignment: CapabilityAssignment::AllNodes,
             seed: None,
+            trust_mode: TrustMode::Local,
+            params: ProtocolParams::default(),
+            signal_max_age_ticks: SIGNAL_MAX_AGE_TICKS,
+            history_size: INTERACTION_HISTORY_SIZE,
+            detection: DetectionConfig::default(),
+            ms_per_tick: None,
+            prior: None,
         }
     }
 }
@@ -58,11 +157,317 @@ impl NetworkConfig {
         self
     }
 
+    /// Set the shape of the initial connection graph
+    pub fn with_topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    /// Set how capabilities are handed out to nodes
+    pub fn with_capability_assignment(mut self, assignment: CapabilityAssignment) -> Self {
+        self.capability_assignment = assignment;
+        self
+    }
+
     /// Set random seed
     pub fn with_seed(mut self, seed: u64) -> Self {
         self.seed = Some(seed);
         self
     }
+
+    /// Set trust computation mode
+    pub fn with_trust_mode(mut self, mode: TrustMode) -> Self {
+        self.trust_mode = mode;
+        self
+    }
+
+    /// Set protocol settings for the network's nodes
+    pub fn with_params(mut self, params: ProtocolParams) -> Self {
+        self.params = params;
+        self
+    }
+
+    /// Set the age in ticks past which defense signals are dropped
+    pub fn with_signal_max_age_ticks(mut self, max_age_ticks: u64) -> Self {
+        self.signal_max_age_ticks = max_age_ticks;
+        self
+    }
+
+    /// Set interaction history size for created nodes
+    pub fn with_history_size(mut self, size: usize) -> Self {
+        self.history_size = size;
+        self
+    }
+
+    /// Set collusion detection settings for adversary scans
+    pub fn with_detection(mut self, detection: DetectionConfig) -> Self {
+        self.detection = detection;
+        self
+    }
+
+    /// Drive nodes with a virtual clock advancing `ms` per tick
+    pub fn with_virtual_clock(mut self, ms: u64) -> Self {
+        self.ms_per_tick = Some(ms);
+        self
+    }
+
+    /// Start from the nodes of a previous run instead of fresh ones
+    pub fn with_prior(mut self, snapshot: NetworkSnapshot) -> Self {
+        self.prior = Some(snapshot);
+        self
+    }
+
+    /// Blake3 hash of the configuration's canonical JSON
+    ///
+    /// A prior snapshot enters through its [`NetworkSnapshot::digest`].
+    pub fn config_hash(&self) -> Hash {
+        let mut bytes = canonical_json(self);
+        if let Some(prior) = &self.prior {
+            bytes.extend_from_slice(prior.digest().as_bytes());
+        }
+        Hash::compute(&bytes)
+    }
+}
+
+/// JSON with object keys sorted, so hash map order doesn't change the bytes
+fn canonical_json<T: Serialize>(value: &T) -> Vec<u8> {
+    let value = serde_json::to_value(value).expect("value serializes to JSON");
+    serde_json::to_vec(&value).expect("JSON value serializes")
+}
+
+/// Tag at the start of every snapshot
+pub const SNAPSHOT_MAGIC: [u8; 4] = *b"SYMB";
+
+/// Snapshot layout version, bumped whenever the serialized layout changes
+///
+/// Independent of [`symbiont_core::VERSION`], which snapshots record
+/// separately in [`NetworkSnapshot::written_by`]. Format 0 is the layout
+/// from before snapshots carried a header.
+pub const SNAPSHOT_FORMAT: u16 = FORMAT_AFFIRMATION_STORE;
+
+/// First format with the magic and version header
+const FORMAT_HEADER: u16 = 1;
+
+/// First format with nodes' recent affirmations in place of a single proof score
+const FORMAT_AFFIRMATION_STORE: u16 = 2;
+
+/// Failure to load a snapshot
+#[derive(Debug, Error)]
+pub enum SnapshotError {
+    /// The data does not start with [`SNAPSHOT_MAGIC`]
+    #[error("not a Symbiont snapshot (magic {found:?}, expected {SNAPSHOT_MAGIC:?})")]
+    BadMagic {
+        /// Tag found in place of the magic
+        found: [u8; 4],
+    },
+    /// The snapshot was written by a newer format than this build reads
+    #[error("snapshot format {found} is newer than supported format {SNAPSHOT_FORMAT}")]
+    UnsupportedVersion {
+        /// Format recorded in the snapshot
+        found: u16,
+    },
+    /// A binary snapshot was written by an older format
+    ///
+    /// Only JSON snapshots are upgraded; bincode cannot skip or default fields.
+    #[cfg(feature = "persistence")]
+    #[error("binary snapshot format {found} is older than {SNAPSHOT_FORMAT}; re-export as JSON")]
+    StaleBinary {
+        /// Format recorded in the snapshot
+        found: u16,
+    },
+    /// The snapshot is not valid JSON for a [`NetworkSnapshot`]
+    #[error("invalid snapshot JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    /// The snapshot bytes could not be decoded
+    #[cfg(feature = "persistence")]
+    #[error("invalid snapshot encoding: {0}")]
+    Decode(#[from] bincode::Error),
+}
+
+/// Serializable network state
+///
+/// Agents attached from an [`AgentType`] are recorded by kind and rebuilt on
+/// [`Network::restore`]. Custom agents given to [`Network::set_agent`] are
+/// trait objects and cannot be serialized, so they must be re-attached after
+/// restoring. The same goes for a custom clock given to
+/// [`Network::set_clock`]; a virtual clock from the config is kept.
+///
+/// Snapshots open with [`SNAPSHOT_MAGIC`] and the format version, so loads
+/// can reject foreign data and upgrade older layouts via
+/// [`NetworkSnapshot::migrate`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkSnapshot {
+    /// Always [`SNAPSHOT_MAGIC`]
+    pub magic: [u8; 4],
+    /// Layout format the snapshot was written with, 0 if absent
+    #[serde(default)]
+    pub version: u16,
+    /// [`symbiont_core::VERSION`] of the build that wrote the snapshot, empty if absent
+    #[serde(default)]
+    pub written_by: String,
+    /// Simulation tick at capture time
+    pub tick: u64,
+    /// All nodes, sorted by id
+    pub nodes: Vec<Node>,
+    /// Metrics collected so far
+    pub metrics: MetricsCollector,
+    /// Master seed agent randomness is derived from
+    ///
+    /// Each tick draws from a generator seeded by (seed, stream, tick), so the
+    /// seed and [`NetworkSnapshot::tick`] fix every later draw.
+    pub seed: u64,
+    /// Protocol settings given to nodes that join later
+    #[serde(default)]
+    pub params: ProtocolParams,
+    /// Tick of the last adversary scan
+    #[serde(default)]
+    pub last_scan_tick: u64,
+    /// Nodes that joined since the last adversary scan
+    #[serde(default)]
+    pub joins_since_scan: usize,
+    /// Archived expelled nodes, sorted by id
+    #[serde(default)]
+    pub archived: Vec<Node>,
+    /// Kinds of the agents attached from an [`AgentType`]
+    #[serde(default)]
+    pub agents: BTreeMap<NodeId, AgentType>,
+    /// How node trust is computed each tick
+    #[serde(default)]
+    pub trust_mode: TrustMode,
+    /// Virtual clock state, None when nodes read the wall clock
+    #[serde(default)]
+    pub clock: Option<VirtualTime>,
+    /// Active partition, with the links to reconnect on heal
+    #[serde(default)]
+    pub partition: Option<Partition>,
+    /// Workflows whose steps follow membership changes
+    #[serde(default)]
+    pub workflows: Vec<Workflow>,
+    /// Decision under deliberation, if any
+    #[serde(default)]
+    pub decision: Option<Decision>,
+}
+
+/// A virtual clock as captured in a snapshot
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VirtualTime {
+    /// Clock time at the snapshot's tick
+    pub now: Timestamp,
+    /// Milliseconds that pass per tick
+    pub ms_per_tick: u64,
+}
+
+impl NetworkSnapshot {
+    /// Blake3 hash of the snapshot's canonical JSON
+    pub fn digest(&self) -> Hash {
+        Hash::compute(&canonical_json(self))
+    }
+
+    /// Check the magic and stamp an older snapshot as [`SNAPSHOT_FORMAT`]
+    ///
+    /// Snapshots from a newer format are refused rather than misread. Layout
+    /// changes are upgraded on the raw JSON by [`NetworkSnapshot::from_json`],
+    /// since removed fields no longer deserialize.
+    pub fn migrate(mut self) -> Result<Self, SnapshotError> {
+        if self.magic != SNAPSHOT_MAGIC {
+            return Err(SnapshotError::BadMagic { found: self.magic });
+        }
+        if self.version > SNAPSHOT_FORMAT {
+            return Err(SnapshotError::UnsupportedVersion {
+                found: self.version,
+            });
+        }
+        self.version = SNAPSHOT_FORMAT;
+        Ok(self)
+    }
+
+    /// Decode a JSON snapshot, checking and migrating it
+    pub fn from_json(bytes: &[u8]) -> Result<Self, SnapshotError> {
+        let mut value: serde_json::Value = serde_json::from_slice(bytes)?;
+        let version = value.get("version").and_then(serde_json::Value::as_u64).unwrap_or(0);
+        if version < u64::from(SNAPSHOT_FORMAT) {
+            upgrade_layout(&mut value, version);
+        }
+        serde_json::from_value::<Self>(value)?.migrate()
+    }
+}
+
+/// Rewrite an older JSON snapshot into the current layout
+///
+/// Each step upgrades snapshots older than the format that introduced it.
+/// Snapshots before [`FORMAT_HEADER`] are given the magic. Nodes before
+/// [`FORMAT_AFFIRMATION_STORE`] held a single `affirmation_proof` score; it
+/// seeds the affirmation store as one fully trusted affirmation of that
+/// strength.
+fn upgrade_layout(snapshot: &mut serde_json::Value, version: u64) {
+    let Some(snapshot) = snapshot.as_object_mut() else {
+        return;
+    };
+    if version < u64::from(FORMAT_HEADER) {
+        snapshot.entry("magic").or_insert_with(|| SNAPSHOT_MAGIC.to_vec().into());
+    }
+    if version >= u64::from(FORMAT_AFFIRMATION_STORE) {
+        return;
+    }
+    for key in ["nodes", "archived"] {
+        let Some(nodes) = snapshot.get_mut(key).and_then(serde_json::Value::as_array_mut) else {
+            continue;
+        };
+        for node in nodes.iter_mut().filter_map(serde_json::Value::as_object_mut) {
+            let proof = node.remove("affirmation_proof").and_then(|p| p.as_f64());
+            let affirmed = node.get("affirmation_count").and_then(serde_json::Value::as_u64);
+            if let (Some(proof), Some(1..)) = (proof, affirmed) {
+                let store = AffirmationStore {
+                    weight: 1.0,
+                    weighted_strength: proof.clamp(0.0, 1.0),
+                };
+                let store = serde_json::to_value(store).expect("store serializes to JSON");
+                node.insert("recent_affirmations".into(), store);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "persistence")]
+impl NetworkSnapshot {
+    /// Encode the snapshot with bincode, far smaller than JSON for large networks
+    pub fn to_bytes(&self) -> Result<Vec<u8>, bincode::Error> {
+        bincode::serialize(self)
+    }
+
+    /// Decode a snapshot written by [`NetworkSnapshot::to_bytes`]
+    ///
+    /// The magic is checked before decoding, so foreign data gives
+    /// [`SnapshotError::BadMagic`] instead of a misleading decode error.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SnapshotError> {
+        let mut found = [0; 4];
+        let len = bytes.len().min(4);
+        found[..len].copy_from_slice(&bytes[..len]);
+        if found != SNAPSHOT_MAGIC {
+            return Err(SnapshotError::BadMagic { found });
+        }
+        let (_, version) = bincode::deserialize::<([u8; 4], u16)>(bytes)?;
+        if version < SNAPSHOT_FORMAT {
+            return Err(SnapshotError::StaleBinary { found: version });
+        }
+        bincode::deserialize::<Self>(bytes)?.migrate()
+    }
+}
+
+/// An active split of the network into two sides
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Partition {
+    /// Nodes on the isolated side
+    side: BTreeSet<NodeId>,
+    /// Cross-partition links removed by the split, restored on heal
+    severed: Vec<(NodeId, NodeId)>,
+}
+
+impl Partition {
+    /// Whether a link between `a` and `b` would cross the split
+    fn separates(&self, a: &NodeId, b: &NodeId) -> bool {
+        self.side.contains(a) != self.side.contains(b)
+    }
 }
 
 /// A simulated network of Symbiont nodes
@@ -71,12 +476,52 @@ pub struct Network {
     nodes: HashMap<NodeId, Node>,
     /// Agent behaviors for each node
     agents: HashMap<NodeId, Box<dyn Agent>>,
+    /// Kinds of the agents attached from an [`AgentType`], kept for snapshots
+    agent_kinds: HashMap<NodeId, AgentType>,
     /// Current simulation tick
     pub tick: u64,
     /// Pending events
     event_queue: Vec<Event>,
     /// Metrics collector
     pub metrics: MetricsCollector,
+    /// Outgoing affirmations awaiting delivery
+    defense: DefenseHandler,
+    /// Current partition, if the network is split
+    partition: Option<Partition>,
+    /// Workflows whose steps may be assigned to members
+    workflows: Vec<Workflow>,
+    /// Trust computation mode
+    trust_mode: TrustMode,
+    /// Decision under deliberation, if any
+    decision: Option<Decision>,
+    /// Master seed for agent and network randomness
+    seed: u64,
+    /// Protocol settings given to nodes the network creates
+    params: ProtocolParams,
+    /// Age (in ticks) past which defense signals are dropped
+    signal_max_age_ticks: u64,
+    /// Interaction history size for nodes the network creates
+    history_size: usize,
+    /// Collusion detection settings for adversary scans
+    detection: DetectionConfig,
+    /// Tick of the last adversary scan
+    last_scan_tick: u64,
+    /// Nodes that joined since the last adversary scan
+    joins_since_scan: usize,
+    /// Time source given to nodes each tick
+    clock: Box<dyn Clock>,
+    /// Tick length of the virtual clock, None for a wall or custom clock
+    virtual_ms_per_tick: Option<u64>,
+    /// Expelled nodes moved out of the live network, kept for forensics
+    archived: HashMap<NodeId, Node>,
+    /// Per-node detectors run on each adversary scan
+    detectors: DetectorRegistry,
+    /// Running totals behind [`Network::stats`]
+    running: RunningStats,
+    /// Whether nodes were handed out mutably since the totals were rebuilt
+    stats_dirty: bool,
+    /// Trust values from the last trust update, a buffer reused every tick
+    trust_scratch: Vec<f64>,
 }
 
 impl Network {
@@ -85,57 +530,215 @@ impl Network {
         Self {
             nodes: HashMap::new(),
             agents: HashMap::new(),
+            agent_kinds: HashMap::new(),
             tick: 0,
             event_queue: Vec::new(),
             metrics: MetricsCollector::new(),
+            defense: DefenseHandler::new(),
+            partition: None,
+            workflows: Vec::new(),
+            trust_mode: TrustMode::Local,
+            decision: None,
+            seed: rand::random(),
+            params: ProtocolParams::default(),
+            signal_max_age_ticks: SIGNAL_MAX_AGE_TICKS,
+            history_size: INTERACTION_HISTORY_SIZE,
+            detection: DetectionConfig::default(),
+            last_scan_tick: 0,
+            joins_since_scan: 0,
+            clock: Box::new(SystemClock),
+            virtual_ms_per_tick: None,
+            archived: HashMap::new(),
+            detectors: DetectorRegistry::default(),
+            running: RunningStats::default(),
+            stats_dirty: false,
+            trust_scratch: Vec::new(),
+        }
+    }
+
+    /// Replace the detectors run on each adversary scan
+    pub fn set_detectors(&mut self, detectors: DetectorRegistry) {
+        self.detectors = detectors;
+    }
+
+    /// Detectors run on each adversary scan, for registering more
+    pub fn detectors_mut(&mut self) -> &mut DetectorRegistry {
+        &mut self.detectors
+    }
+
+    /// Replace the network's time source
+    ///
+    /// The clock is moved to the current tick and every node adopts its time.
+    pub fn set_clock(&mut self, mut clock: Box<dyn Clock>) {
+        clock.on_tick(self.tick);
+        let now = clock.now();
+        for node in self.nodes.values_mut() {
+            node.clock_time = Some(now);
         }
+        self.clock = clock;
+        self.virtual_ms_per_tick = None;
+    }
+
+    /// Drive nodes with a virtual clock, remembered so snapshots keep it
+    fn set_virtual_clock(&mut self, clock: VirtualClock) {
+        let ms_per_tick = clock.ms_per_tick();
+        self.set_clock(Box::new(clock));
+        self.virtual_ms_per_tick = Some(ms_per_tick);
+    }
+
+    /// Current time on the network's clock
+    pub fn now(&self) -> Timestamp {
+        self.clock.now()
+    }
+
+    /// Capture nodes, tick, and metrics for later resumption
+    pub fn snapshot(&self) -> NetworkSnapshot {
+        NetworkSnapshot {
+            magic: SNAPSHOT_MAGIC,
+            version: SNAPSHOT_FORMAT,
+            written_by: symbiont_core::VERSION.to_string(),
+            tick: self.tick,
+            nodes: self
+                .sorted_node_ids()
+                .iter()
+                .filter_map(|id| self.nodes.get(id).cloned())
+                .collect(),
+            metrics: self.metrics.clone(),
+            seed: self.seed,
+            params: self.params.clone(),
+            last_scan_tick: self.last_scan_tick,
+            joins_since_scan: self.joins_since_scan,
+            archived: {
+                let mut archived: Vec<Node> = self.archived.values().cloned().collect();
+                archived.sort_by_key(|n| n.id);
+                archived
+            },
+            agents: self.agent_kinds.iter().map(|(id, kind)| (*id, kind.clone())).collect(),
+            trust_mode: self.trust_mode,
+            clock: self.virtual_ms_per_tick.map(|ms_per_tick| VirtualTime {
+                now: self.clock.now(),
+                ms_per_tick,
+            }),
+            partition: self.partition.clone(),
+            workflows: self.workflows.clone(),
+            decision: self.decision.clone(),
+        }
+    }
+
+    /// Rebuild a network from a snapshot, re-creating agents recorded by kind
+    pub fn restore(snapshot: NetworkSnapshot) -> Self {
+        let mut network = Self::new();
+        network.tick = snapshot.tick;
+        network.metrics = snapshot.metrics;
+        network.seed = snapshot.seed;
+        network.params = snapshot.params;
+        network.trust_mode = snapshot.trust_mode;
+        network.partition = snapshot.partition;
+        network.workflows = snapshot.workflows;
+        network.decision = snapshot.decision;
+        network.last_scan_tick = snapshot.last_scan_tick;
+        network.joins_since_scan = snapshot.joins_since_scan;
+        for node in snapshot.nodes {
+            network.add_node(node);
+        }
+        network.archived = snapshot.archived.into_iter().map(|n| (n.id, n)).collect();
+        for (id, kind) in snapshot.agents {
+            network.set_agent_type(id, kind);
+        }
+        if let Some(clock) = snapshot.clock {
+            let elapsed = network.tick.saturating_mul(clock.ms_per_tick);
+            let start = Timestamp::new(clock.now.millis().saturating_sub(elapsed));
+            network.set_virtual_clock(VirtualClock::starting_at(start, clock.ms_per_tick));
+        }
+        network
     }
 
     /// Create a network from configuration
     pub fn from_config(config: NetworkConfig) -> Self {
+        let config_hash = config.config_hash();
         let mut network = Self::new();
-        let mut rng = match config.seed {
-            Some(seed) => rand::rngs::StdRng::seed_from_u64(seed),
-            None => rand::rngs::StdRng::from_entropy(),
-        };
+        network.trust_mode = config.trust_mode;
+        network.params = config.params;
+        network.signal_max_age_ticks = config.signal_max_age_ticks;
+        network.history_size = config.history_size;
+        network.detection = config.detection;
+        if let Some(ms) = config.ms_per_tick {
+            network.set_virtual_clock(VirtualClock::new(ms));
+        }
+        if let Some(seed) = config.seed {
+            network.seed = seed;
+        }
+        network.metrics.set_run_identity(network.seed, config_hash);
+        let mut rng = StdRng::seed_from_u64(network.seed);
+
+        // Warm start: nodes keep their trust, links and history, but the
+        // tick restarts at zero so scenarios schedule relative to the start
+        if let Some(prior) = config.prior {
+            for mut node in prior.nodes {
+                node.params = network.params.clone();
+                network.add_node(node.with_history_size(network.history_size));
+            }
+            network.archived = prior.archived.into_iter().map(|n| (n.id, n)).collect();
+            return network;
+        }
 
         // Create nodes
         for i in 0..config.node_count {
-            let id = NodeId::from_index(i as u64);
-            let mut node = Node::new(id);
+            let mut node = network.new_node(NodeId::from_index(i as u64));
 
             // Assign capabilities
-            for cap in &config.capabilities {
-                node.add_capability(cap.clone());
+            let caps = config.capability_assignment.assign(i, &config.capabilities, &mut rng);
+            for cap in caps {
+                node.add_capability(cap);
             }
 
             network.add_node(node);
         }
 
-        // Create random connections
-        let node_ids: Vec<_> = network.nodes.keys().cloned().collect();
-        for i in 0..node_ids.len() {
-            for j in (i + 1)..node_ids.len() {
-                if rng.gen::<f64>() < config.connection_probability {
-                    network.connect(node_ids[i], node_ids[j]);
-                }
-            }
+        // Create connections
+        let node_ids = network.sorted_node_ids();
+        let edges = config.topology.edges(node_ids.len(), config.connection_probability, &mut rng);
+        for (i, j) in edges {
+            network.connect(node_ids[i], node_ids[j]);
         }
 
         network
     }
 
+    /// Fresh node with the network's protocol and history settings
+    fn new_node(&self, id: NodeId) -> Node {
+        let mut node = Node::new(id).with_history_size(self.history_size);
+        node.params = self.params.clone();
+        node
+    }
+
     /// Add a node to the network
-    pub fn add_node(&mut self, node: Node) {
-        let id = node.id;
-        self.nodes.insert(id, node);
+    pub fn add_node(&mut self, mut node: Node) {
+        node.current_tick = self.tick;
+        node.clock_time = Some(self.clock.now());
+        self.running.add(&node);
+        if let Some(replaced) = self.nodes.insert(node.id, node) {
+            self.running.remove(&replaced);
+        }
     }
 
     /// Add a node with a specific agent behavior
     pub fn add_node_with_agent(&mut self, node: Node, agent: Box<dyn Agent>) {
         let id = node.id;
-        self.nodes.insert(id, node);
-        self.agents.insert(id, agent);
+        self.add_node(node);
+        self.set_agent(id, agent);
+    }
+
+    /// Add a node sponsored by an existing member
+    ///
+    /// The newcomer starts with trust derived from the voucher's trust, and the
+    /// voucher's trust cap is penalized if the newcomer later defects.
+    pub fn add_vouched_node(&mut self, mut node: Node, voucher: NodeId) {
+        if let Some(voucher_node) = self.nodes.get_mut(&voucher) {
+            voucher_node.vouch_for(node.id);
+            node.accept_vouch(voucher, voucher_node.trust);
+        }
+        self.add_node(node);
     }
 
     /// Get a node by ID
@@ -144,7 +747,11 @@ impl Network {
     }
 
     /// Get a mutable node by ID
+    ///
+    /// Until the next tick or [`Network::recompute_stats`], [`Network::stats`]
+    /// walks every node, since changes made through this bypass its totals.
     pub fn get_node_mut(&mut self, id: &NodeId) -> Option<&mut Node> {
+        self.stats_dirty = true;
         self.nodes.get_mut(id)
     }
 
@@ -154,7 +761,11 @@ impl Network {
     }
 
     /// Get mutable reference to all nodes
+    ///
+    /// Until the next tick or [`Network::recompute_stats`], [`Network::stats`]
+    /// walks every node, since changes made through this bypass its totals.
     pub fn nodes_mut(&mut self) -> &mut HashMap<NodeId, Node> {
+        self.stats_dirty = true;
         &mut self.nodes
     }
 
@@ -164,18 +775,180 @@ impl Network {
     }
 
     /// Create a connection between two nodes
+    ///
+    /// Does nothing if the nodes are on opposite sides of a partition.
     pub fn connect(&mut self, a: NodeId, b: NodeId) {
-        if let Some(node_a) = self.nodes.get_mut(&a) {
-            node_a.get_or_create_connection(b);
+        if self.is_separated(&a, &b) {
+            return;
+        }
+        for (from, to) in [(a, b), (b, a)] {
+            if let Some(node) = self.nodes.get_mut(&from) {
+                let before = node.connections.len();
+                node.get_or_create_connection(to);
+                self.running.connection_ends += node.connections.len() - before;
+            }
+        }
+    }
+
+    /// Track a workflow so its steps follow membership changes
+    pub fn add_workflow(&mut self, workflow: Workflow) {
+        self.workflows.push(workflow);
+    }
+
+    /// Tracked workflows
+    pub fn workflows(&self) -> &[Workflow] {
+        &self.workflows
+    }
+
+    /// Start deliberating on a decision, replacing any current one
+    pub fn start_decision(&mut self, decision: Decision) {
+        self.decision = Some(decision);
+    }
+
+    /// Decision under deliberation
+    pub fn decision(&self) -> Option<&Decision> {
+        self.decision.as_ref()
+    }
+
+    /// Remove a node, its agent, and every neighbor's connection to it
+    ///
+    /// Workflow steps assigned to the node are re-routed (or failed if no
+    /// other candidate exists). Returns the tasks that were reassigned.
+    pub fn remove_node_gracefully(&mut self, id: NodeId) -> HashSet<TaskId> {
+        self.detach_node(id).map(|(_, reassigned)| reassigned).unwrap_or_default()
+    }
+
+    /// Take a node out of the network, returning it and the re-routed tasks
+    fn detach_node(&mut self, id: NodeId) -> Option<(Node, HashSet<TaskId>)> {
+        let mut removed = self.nodes.remove(&id)?;
+        for change in removed.take_status_changes() {
+            self.metrics.record_status_change(&change);
+        }
+        self.agents.remove(&id);
+        self.agent_kinds.remove(&id);
+        self.running.remove(&removed);
+
+        for node in self.nodes.values_mut() {
+            if node.connections.remove(&id).is_some() {
+                self.running.connection_ends = self.running.connection_ends.saturating_sub(1);
+            }
+        }
+
+        let mut reassigned = HashSet::new();
+        for workflow in self.workflows.iter_mut() {
+            reassigned.extend(workflow.reassign_from(id, &self.nodes));
+        }
+        Some((removed, reassigned))
+    }
+
+    /// Move every expelled node into the archive, returning how many moved
+    ///
+    /// Archived nodes leave trust computation, routing and stats but stay
+    /// queryable via [`Network::archived_node`]. Their ids are remembered, so
+    /// a later join reusing one is refused.
+    pub fn archive_expelled(&mut self) -> usize {
+        let expelled: Vec<NodeId> = self
+            .sorted_node_ids()
+            .into_iter()
+            .filter(|id| self.nodes[id].status == NodeStatus::Expelled)
+            .collect();
+        for &id in &expelled {
+            if let Some((node, _)) = self.detach_node(id) {
+                self.archived.insert(id, node);
+            }
+        }
+        expelled.len()
+    }
+
+    /// Get an archived node by ID
+    pub fn archived_node(&self, id: &NodeId) -> Option<&Node> {
+        self.archived.get(id)
+    }
+
+    /// All archived nodes
+    pub fn archived(&self) -> &HashMap<NodeId, Node> {
+        &self.archived
+    }
+
+    /// Split the network, removing every connection between `side` and the rest
+    ///
+    /// Any existing partition is healed first.
+    pub fn partition(&mut self, side: impl IntoIterator<Item = NodeId>) {
+        self.heal();
+
+        let mut partition = Partition {
+            side: side.into_iter().collect(),
+            severed: Vec::new(),
+        };
+        for node in self.nodes.values_mut() {
+            let node_id = node.id;
+            let cut: Vec<_> = node
+                .connections
+                .keys()
+                .filter(|partner| partition.separates(&node_id, partner))
+                .cloned()
+                .collect();
+            for partner in cut {
+                node.connections.remove(&partner);
+                self.running.connection_ends = self.running.connection_ends.saturating_sub(1);
+                if node_id < partner {
+                    partition.severed.push((node_id, partner));
+                }
+            }
         }
-        if let Some(node_b) = self.nodes.get_mut(&b) {
-            node_b.get_or_create_connection(a);
+        partition.severed.sort();
+        self.partition = Some(partition);
+    }
+
+    /// End the current partition and reconnect the severed links
+    pub fn heal(&mut self) {
+        if let Some(partition) = self.partition.take() {
+            for (a, b) in partition.severed {
+                self.connect(a, b);
+            }
         }
     }
 
+    /// Whether the network is currently split
+    pub fn is_partitioned(&self) -> bool {
+        self.partition.is_some()
+    }
+
+    /// Whether a partition currently separates two nodes
+    fn is_separated(&self, a: &NodeId, b: &NodeId) -> bool {
+        self.partition.as_ref().is_some_and(|p| p.separates(a, b))
+    }
+
     /// Set agent behavior for a node
+    ///
+    /// A custom agent is not captured by [`Network::snapshot`]; use
+    /// [`Network::set_agent_type`] for one that is.
     pub fn set_agent(&mut self, id: NodeId, agent: Box<dyn Agent>) {
         self.agents.insert(id, agent);
+        self.agent_kinds.remove(&id);
+    }
+
+    /// Attach an agent built from `agent_type`, recorded in snapshots
+    pub fn set_agent_type(&mut self, id: NodeId, agent_type: AgentType) {
+        let agent = self.create_agent_from_type(agent_type.clone());
+        self.agents.insert(id, agent);
+        self.agent_kinds.insert(id, agent_type);
+    }
+
+    /// Get the behavior type name of the agent driving a node
+    pub fn agent_type_of(&self, id: &NodeId) -> Option<&'static str> {
+        self.agents.get(id).map(|a| a.agent_type())
+    }
+
+    /// Attach an agent of `agent_type` to every node without one
+    ///
+    /// Used after [`Network::restore`] to cover nodes whose agents were custom.
+    pub fn attach_agents(&mut self, agent_type: AgentType) {
+        for id in self.sorted_node_ids() {
+            if !self.agents.contains_key(&id) {
+                self.set_agent_type(id, agent_type.clone());
+            }
+        }
     }
 
     /// Create an agent from an AgentType specification
@@ -193,9 +966,19 @@ impl Network {
             AgentType::Sybil { cluster_members } => {
                 Box::new(SybilCluster::new(cluster_members))
             }
+            AgentType::StealthSybil { cluster_members } => {
+                Box::new(StealthSybil::new(cluster_members))
+            }
             AgentType::Passive => {
                 Box::new(PassiveAgent::new())
             }
+            AgentType::Whitewasher { trust_floor } => {
+                Box::new(Whitewasher::new(trust_floor))
+            }
+            AgentType::Oscillating { period, duty_cycle } => {
+                Box::new(OscillatingAdversary::new(period, duty_cycle))
+            }
+            AgentType::Camouflage { skim } => Box::new(CamouflageAdversary::new(skim)),
         }
     }
 
@@ -204,58 +987,221 @@ impl Network {
         self.event_queue.push(event);
     }
 
+    /// Replay a recorded interaction log through the trust engine
+    ///
+    /// Nodes are created on first mention and connections on first
+    /// interaction. Records should arrive in timestamp order: each run of
+    /// records sharing a timestamp is queued and processed as one tick.
+    /// Self-interactions are skipped.
+    pub fn apply_interaction_log(&mut self, records: impl Iterator<Item = InteractionRecord>) {
+        let mut current: Option<u64> = None;
+
+        for record in records {
+            if current.is_some_and(|t| t != record.timestamp) {
+                self.tick();
+            }
+            current = Some(record.timestamp);
+
+            let (from, to) = (record.from_id(), record.to_id());
+            if from == to {
+                continue;
+            }
+            for id in [from, to] {
+                if !self.nodes.contains_key(&id) {
+                    let node = self.new_node(id);
+                    self.add_node(node);
+                }
+            }
+            self.queue_event(Event::interaction(from, to, record.quality_score()));
+        }
+
+        if current.is_some() {
+            self.tick();
+        }
+    }
+
+    /// Agents sorted by node id, so batch order is independent of map order
+    fn sorted_agent_ids(&self) -> Vec<NodeId> {
+        let mut ids: Vec<_> = self.agents.keys().cloned().collect();
+        ids.sort();
+        ids
+    }
+
+    /// Protocol settings given to nodes the network creates
+    pub fn params(&self) -> &ProtocolParams {
+        &self.params
+    }
+
+    /// Master seed all randomness in this network derives from
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Derive randomness from a new master seed from now on
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.metrics.seed = Some(seed);
+    }
+
+    /// RNG for one stream of randomness at the current tick
+    ///
+    /// Derived from (master seed, stream, tick), so it is the same on every
+    /// run with the same seed, regardless of thread scheduling.
+    fn tick_rng(&self, stream: &[u8]) -> StdRng {
+        let mut data = Vec::with_capacity(stream.len() + 16);
+        data.extend_from_slice(&self.seed.to_le_bytes());
+        data.extend_from_slice(stream);
+        data.extend_from_slice(&self.tick.to_le_bytes());
+
+        let hash = Hash::compute(&data);
+        let mut seed = [0u8; 32];
+        seed.copy_from_slice(hash.as_bytes());
+        StdRng::from_seed(seed)
+    }
+
+    /// Events generated by a single agent this tick
+    fn agent_action(&self, id: &NodeId) -> Vec<Event> {
+        match (self.agents.get(id), self.nodes.get(id)) {
+            (Some(agent), Some(node)) => {
+                let mut rng = self.tick_rng(id.as_bytes());
+                agent.act(node, &self.nodes, self.tick, &mut rng)
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Generate agent events one agent at a time
+    #[cfg_attr(all(feature = "parallel", not(test)), allow(dead_code))]
+    fn agent_actions_serial(&self) -> Vec<Vec<Event>> {
+        self.sorted_agent_ids()
+            .iter()
+            .map(|id| self.agent_action(id))
+            .collect()
+    }
+
+    /// Generate agent events across the rayon thread pool
+    #[cfg(feature = "parallel")]
+    fn agent_actions_parallel(&self) -> Vec<Vec<Event>> {
+        use rayon::prelude::*;
+
+        self.sorted_agent_ids()
+            .par_iter()
+            .map(|id| self.agent_action(id))
+            .collect()
+    }
+
     /// Process all queued events
+    ///
+    /// Events are sorted by `Event::order_key` first, so the outcome depends
+    /// only on which events were queued, not the order they were queued in.
+    /// The sort is stable: events with equal keys (such as repeated
+    /// interactions between the same pair) keep their queue order.
     pub fn process_events(&mut self) {
-        let events = std::mem::take(&mut self.event_queue);
+        let mut events = std::mem::take(&mut self.event_queue);
+        events.sort_by_key(Event::order_key);
         for event in events {
             self.handle_event(event);
         }
     }
 
-    /// Handle a single event
-    fn handle_event(&mut self, event: Event) {
+    /// Handle a single event immediately, bypassing the queue
+    pub fn handle_event(&mut self, event: Event) {
         match event {
             Event::Interaction {
                 from,
                 to,
                 volume,
+                exchange_in,
+                exchange_out,
                 quality,
                 tone,
                 capability,
+                tone_signals,
             } => {
+                // Nothing crosses a partition
+                if self.is_separated(&from, &to) {
+                    return;
+                }
+
+                let tone = tone_signals.map(|s| s.compute_tone()).unwrap_or(tone);
+
                 // Update both nodes
                 if let Some(from_node) = self.nodes.get_mut(&from) {
+                    let before = from_node.status;
+                    self.running.remove(from_node);
                     from_node.handle_outgoing_interaction(
-                        to, volume, 1.0, 1.0, quality, tone, capability,
+                        to, volume, exchange_in, exchange_out, quality, tone, capability,
                     );
+                    self.running.add(from_node);
+                    log_status_change(from_node, before);
                 }
                 if let Some(to_node) = self.nodes.get_mut(&to) {
+                    let before = to_node.status;
+                    self.running.remove(to_node);
                     to_node.handle_incoming_interaction(
-                        from, volume, 1.0, 1.0, quality, tone, capability,
+                        from, volume, exchange_in, exchange_out, quality, tone, capability,
                     );
+                    self.running.add(to_node);
+                    log_status_change(to_node, before);
+
+                    // The responder affirms good work from the initiator
+                    self.defense.maybe_affirm(to_node, from, quality, tone);
                 }
 
                 self.metrics.record_interaction(from, to, quality);
             }
-            Event::DefenseSignal { signal } => {
+            Event::DefenseSignal { signal, issued_tick } => {
+                // Stale evidence no longer says anything about the target
+                let age = self.tick.saturating_sub(issued_tick);
+                if age > self.signal_max_age_ticks {
+                    debug!(threat = %signal.threat, age, "dropped stale defense signal");
+                    self.metrics.record_stale_signal();
+                    return;
+                }
+
+                // Nodes linked to the target grow wary, of it and in general
+                let boost = signal.confidence.value() * PRIMING_SENSITIVITY;
+                for node in self.nodes.values_mut() {
+                    if !node.connections.contains_key(&signal.threat) {
+                        continue;
+                    }
+                    self.running.remove(node);
+                    if let Some(conn) = node.get_connection_mut(&signal.threat) {
+                        conn.increase_priming(boost);
+                    }
+                    node.increase_priming(boost);
+                    self.running.add(node);
+                }
+
                 // Propagate signal to target's connections
                 if let Some(target) = self.nodes.get_mut(&signal.threat) {
+                    self.running.remove(target);
                     target.update_threat_belief(
                         signal.threat,
                         signal.threat_type,
                         signal.confidence.value(),
                         Some(signal.evidence),
                     );
+                    self.running.add(target);
                 }
             }
-            Event::NodeJoin { node, agent_type } => {
+            Event::NodeJoin { mut node, agent_type } => {
                 let node_id = node.id;
-                self.add_node(node);
+                // An archived id is burned: rejoining under it is refused
+                if self.archived.contains_key(&node_id) {
+                    debug!(node = %node_id, "refused rejoin of an archived node");
+                    return;
+                }
+                self.joins_since_scan += 1;
+                node.params = self.params.clone();
+                match node.vouched_by {
+                    Some(voucher) => self.add_vouched_node(*node, voucher),
+                    None => self.add_node(*node),
+                }
 
                 // Create and attach agent if specified
                 if let Some(at) = agent_type {
-                    let agent: Box<dyn Agent> = self.create_agent_from_type(at);
-                    self.agents.insert(node_id, agent);
+                    self.set_agent_type(node_id, at);
 
                     // Connect to existing nodes
                     let existing_ids: Vec<_> = self.nodes.keys()
@@ -268,77 +1214,324 @@ impl Network {
                 }
             }
             Event::NodeLeave { node_id } => {
-                self.nodes.remove(&node_id);
-                self.agents.remove(&node_id);
+                self.remove_node_gracefully(node_id);
             }
+            Event::LowTrustLeave => {
+                if let Some(node_id) = self.pick_low_trust_node() {
+                    self.remove_node_gracefully(node_id);
+                }
+            }
+            Event::Partition { side } => self.partition(side),
+            Event::Heal => self.heal(),
         }
     }
 
-    /// Advance simulation by one tick
-    pub fn tick(&mut self) {
-        self.tick += 1;
+    /// Queue an affirmation for delivery on the next tick
+    pub fn queue_affirmation(&mut self, affirmation: Affirmation) {
+        self.defense.send_affirmation(affirmation);
+    }
 
-        // Have each agent act
-        let node_ids: Vec<_> = self.agents.keys().cloned().collect();
-        for id in node_ids {
-            if let Some(agent) = self.agents.get(&id) {
-                // Get node state
-                if let Some(node) = self.nodes.get(&id) {
-                    // Generate events based on agent behavior
-                    let events = agent.act(node, &self.nodes, self.tick);
-                    for event in events {
-                        self.event_queue.push(event);
-                    }
-                }
+    /// Deliver pending affirmations, weighting each by its affirmer's trust
+    fn deliver_affirmations(&mut self) {
+        let mut received: BTreeMap<NodeId, Vec<(Score, Score)>> = BTreeMap::new();
+        for affirmation in self.defense.take_pending_affirmations() {
+            if affirmation.from == affirmation.to || !self.nodes.contains_key(&affirmation.to) {
+                continue;
+            }
+            if let Some(affirmer) = self.nodes.get(&affirmation.from) {
+                received
+                    .entry(affirmation.to)
+                    .or_default()
+                    .push((affirmer.trust, affirmation.strength));
             }
         }
 
-        // Process all events
-        self.process_events();
-
-        // Apply periodic maintenance
-        for node in self.nodes.values_mut() {
-            node.decay_priming();
-            node.decay_idle_connections();
-            node.check_diversity();
+        for (id, batch) in received {
+            if let Some(node) = self.nodes.get_mut(&id) {
+                node.receive_affirmations(&batch);
+            }
         }
+    }
 
-        // JOB 6: Scan for adversaries (periodic)
-        if self.tick % ADVERSARY_INTERVAL == 0 {
-            self.scan_for_adversaries();
-        }
+    /// Pick an active node with probability proportional to (1 - trust)
+    fn pick_low_trust_node(&self) -> Option<NodeId> {
+        let candidates: Vec<(NodeId, f64)> = self
+            .sorted_node_ids()
+            .into_iter()
+            .filter_map(|id| {
+                let node = self.nodes.get(&id)?;
+                node.status
+                    .is_active()
+                    .then(|| (id, 1.0 - node.trust.value()))
+            })
+            .collect();
 
-        // Update trust scores
-        self.update_trust_scores();
+        let total: f64 = candidates.iter().map(|(_, w)| w).sum();
+        if total <= 0.0 {
+            return None;
+        }
 
-        // Collect metrics
-        self.collect_metrics();
+        let mut pick = self.tick_rng(b"departure").gen::<f64>() * total;
+        for (id, weight) in &candidates {
+            pick -= weight;
+            if pick <= 0.0 && *weight > 0.0 {
+                return Some(*id);
+            }
+        }
+        candidates.iter().rev().find(|(_, w)| *w > 0.0).map(|(id, _)| *id)
     }
 
-    /// Update trust scores for all nodes
-    fn update_trust_scores(&mut self) {
+    /// Advance simulation by one tick
+    ///
+    /// Each phase runs in its own `debug` span under a `tick` span, so
+    /// profiling costs nothing unless a subscriber enables them.
+    pub fn tick(&mut self) {
+        self.tick += 1;
+        let _tick = debug_span!("tick", tick = self.tick).entered();
+        self.clock.on_tick(self.tick);
+        let now = self.clock.now();
         for node in self.nodes.values_mut() {
-            node.trust = compute_trust(node);
+            node.current_tick = self.tick;
+            node.clock_time = Some(now);
         }
+
+        // Have each agent act, merging batches in node-id order
+        {
+            let _span = debug_span!("agent_actions").entered();
+            #[cfg(feature = "parallel")]
+            let batches = self.agent_actions_parallel();
+            #[cfg(not(feature = "parallel"))]
+            let batches = self.agent_actions_serial();
+
+            for events in batches {
+                self.event_queue.extend(events);
+            }
+        }
+
+        // Process all events
+        {
+            let _span = debug_span!("events", queued = self.event_queue.len()).entered();
+            self.process_events();
+            self.deliver_affirmations();
+        }
+
+        // Penalize vouchers whose vouchees were expelled; a failed probation
+        // window the vouchee recovers from doesn't count
+        let failed: Vec<NodeId> = self
+            .nodes
+            .values()
+            .filter(|n| n.vouched_by.is_some())
+            .filter(|n| n.status == NodeStatus::Expelled)
+            .map(|n| n.id)
+            .collect();
+        self.penalize_vouchers(&failed);
+
+        // Apply periodic maintenance
+        {
+            let _span = debug_span!("maintenance").entered();
+            let active = self.nodes.values().filter(|n| n.status.is_active()).count();
+            for node in self.nodes.values_mut() {
+                node.reachable_partners = Some(active.saturating_sub(1));
+                node.decay_priming();
+                node.decay_connection_priming();
+                node.decay_load();
+                // Queued tasks only model load here: starting one charges the
+                // node, and the simulation has no task outcomes to record
+                node.drain_tasks();
+                node.decay_capability_quality();
+                node.decay_idle_connections();
+                node.decay_swift_trust();
+                node.decay_affirmations();
+                node.check_quality_anomaly();
+                node.check_diversity();
+            }
+        }
+
+        // Promote or demote members along the status ladder
+        if self.tick % STATUS_INTERVAL == 0 {
+            for node in self.nodes.values_mut() {
+                let before = node.status;
+                node.check_promotion();
+                log_status_change(node, before);
+            }
+        }
+
+        // JOB 6: Scan for adversaries (adaptive interval)
+        if self.tick - self.last_scan_tick >= self.next_scan_interval() {
+            let _span = debug_span!("detection").entered();
+            self.scan_for_adversaries();
+            self.last_scan_tick = self.tick;
+            self.joins_since_scan = 0;
+        }
+
+        // Update trust scores and flag nodes whose trust swings or climbs
+        // far faster than their peers'
+        {
+            let _span = debug_span!("trust_update").entered();
+            self.update_trust_scores();
+            let median_velocity = median_trust_velocity(self.nodes.values());
+            for node in self.nodes.values_mut() {
+                node.check_trust_volatility();
+                let gain = detect_rapid_trust_gain(node, median_velocity);
+                if gain.is_threat() {
+                    debug!(node = %node.id, reason = %gain.reason, "rapid trust gain");
+                    node.flags.insert(NodeFlag::TrustVolatility);
+                }
+            }
+        }
+
+        // Positions drift toward trusted neighbors
+        if let Some(decision) = self.decision.as_mut() {
+            decision.step(&self.nodes);
+        }
+
+        // Collect metrics
+        self.collect_metrics();
+    }
+
+    /// Ticks between adversary scans under current conditions
+    ///
+    /// Compares mean trust variance over the last SCAN_VARIANCE_WINDOW
+    /// snapshots with the window before it:
+    ///
+    /// ratio = (σ²_recent + floor) / (σ²_baseline + floor)
+    ///
+    /// Joins since the last scan or a ratio of at least SCAN_VARIANCE_SPIKE
+    /// give ADVERSARY_INTERVAL_MIN; a ratio at most SCAN_VARIANCE_STABLE gives
+    /// ADVERSARY_INTERVAL_MAX. Otherwise, or with too little history, the
+    /// fixed ADVERSARY_INTERVAL applies.
+    pub fn next_scan_interval(&self) -> u64 {
+        if self.joins_since_scan > 0 {
+            return ADVERSARY_INTERVAL_MIN;
+        }
+
+        let history = &self.metrics.trust_history;
+        if history.len() < 2 * SCAN_VARIANCE_WINDOW {
+            return ADVERSARY_INTERVAL;
+        }
+
+        let mean_variance = |snapshots: &[TrustSnapshot]| {
+            snapshots.iter().map(|s| s.std_dev.powi(2)).sum::<f64>() / snapshots.len() as f64
+        };
+        let split = history.len() - SCAN_VARIANCE_WINDOW;
+        let recent = mean_variance(&history[split..]);
+        let baseline = mean_variance(&history[split - SCAN_VARIANCE_WINDOW..split]);
+        let ratio = (recent + SCAN_VARIANCE_FLOOR) / (baseline + SCAN_VARIANCE_FLOOR);
+
+        if ratio >= SCAN_VARIANCE_SPIKE {
+            ADVERSARY_INTERVAL_MIN
+        } else if ratio <= SCAN_VARIANCE_STABLE {
+            ADVERSARY_INTERVAL_MAX
+        } else {
+            ADVERSARY_INTERVAL
+        }
+    }
+
+    /// Update trust scores for all nodes
+    ///
+    /// The running stats are rebuilt in the same pass, picking up every
+    /// change made to nodes during the tick, and per-node trust metrics are
+    /// recorded there too, so collecting metrics doesn't walk the nodes again.
+    fn update_trust_scores(&mut self) {
+        let mut running = RunningStats::default();
+        let mut trusts = std::mem::take(&mut self.trust_scratch);
+        trusts.clear();
+        let (tick, metrics) = (self.tick, &mut self.metrics);
+        let mut settle = |node: &Node| {
+            running.add(node);
+            trusts.push(node.trust.value());
+            metrics.record_node_trust(tick, node.id, node.trust);
+        };
+        match self.trust_mode {
+            TrustMode::Local => {
+                for node in self.nodes.values_mut() {
+                    let trust = compute_trust(node);
+                    node.record_trust(trust);
+                    settle(node);
+                }
+            }
+            TrustMode::Global { iterations } => {
+                let global = compute_global_trust(&self.nodes, iterations);
+                for (id, trust) in global {
+                    if let Some(node) = self.nodes.get_mut(&id) {
+                        node.record_trust(trust);
+                        settle(node);
+                    }
+                }
+            }
+            TrustMode::Hybrid { alpha, iterations } => {
+                let alpha = alpha.value();
+                let global = compute_global_trust(&self.nodes, iterations);
+                for (id, global_trust) in global {
+                    if let Some(node) = self.nodes.get_mut(&id) {
+                        let local = compute_trust(node).value();
+                        let blended = alpha * local + (1.0 - alpha) * global_trust.value();
+                        let ceiling = trust_ceiling(node).value();
+                        node.record_trust(Score::new(blended.min(ceiling)));
+                        settle(node);
+                    }
+                }
+            }
+        }
+        self.running = running;
+        self.stats_dirty = false;
+        self.trust_scratch = trusts;
     }
 
     /// Collect metrics at this tick
     fn collect_metrics(&mut self) {
-        let trust_scores: Vec<_> = self.nodes.values().map(|n| n.trust).collect();
-        self.metrics.record_trust_distribution(self.tick, &trust_scores);
+        self.metrics.record_trust_values(self.tick, &mut self.trust_scratch);
+
+        for node in self.nodes.values_mut() {
+            for change in node.take_status_changes() {
+                self.metrics.record_status_change(&change);
+            }
+        }
+
+        if let Some(decision) = &self.decision {
+            self.metrics.record_decision(self.tick, decision);
+        }
+    }
+
+    /// Apply VOUCH_PENALTY to whoever vouched for each defecting node
+    fn penalize_vouchers(&mut self, defectors: &[NodeId]) {
+        for defector in defectors {
+            let voucher = self.nodes.get(defector).and_then(|n| n.vouched_by);
+            if let Some(voucher_node) = voucher.and_then(|v| self.nodes.get_mut(&v)) {
+                voucher_node.apply_vouch_penalty(*defector);
+            }
+        }
     }
 
     /// Scan for adversaries across all nodes (JOB 6)
     fn scan_for_adversaries(&mut self) {
         // Collect detection results without mutating nodes yet
         let mut signals_to_emit: Vec<DefenseSignal> = Vec::new();
+        // Nodes detected as adversaries (low diversity alone doesn't count)
+        let mut defectors: Vec<NodeId> = Vec::new();
 
         // Check each node for individual threats
         for node in self.nodes.values() {
-            let threats = detect_all_threats(node, &node.history);
+            let threats = self.detectors.run_all(node, &node.history);
             for detection in threats {
                 if detection.is_threat() {
                     if let Some(threat_type) = detection.threat_type {
+                        if threat_type != ThreatType::Sybil {
+                            defectors.push(detection.node_id);
+                        }
+                        debug!(
+                            node = %detection.node_id,
+                            threat = ?threat_type,
+                            confidence = detection.confidence.value(),
+                            reason = %detection.reason,
+                            "threat detected"
+                        );
+                        self.metrics.record_detection(
+                            self.tick,
+                            detection.node_id,
+                            threat_type.name(),
+                            detection.confidence,
+                        );
                         // Hash the reason as evidence
                         let evidence = Hash::compute(detection.reason.as_bytes());
                         let signal = DefenseSignal::new(
@@ -355,11 +1548,17 @@ impl Network {
         }
 
         // Check for collusion clusters
-        let collusion_clusters = detect_collusion(&self.nodes);
+        let collusion_clusters = detect_collusion_with(&self.nodes, &self.detection);
         for cluster in collusion_clusters {
-            if cluster.is_suspicious() {
+            if cluster.is_suspicious_with(&self.detection.collusion) {
+                debug!(
+                    members = cluster.members.len(),
+                    confidence = cluster.confidence.value(),
+                    "collusion cluster detected"
+                );
                 // Emit signals for each member of the cluster
                 for member_id in &cluster.members {
+                    defectors.push(*member_id);
                     let evidence_str = format!(
                         "Collusion cluster detected: density={:.2}, mutual_rating={:.2}",
                         cluster.internal_density, cluster.mutual_rating
@@ -368,7 +1567,7 @@ impl Network {
                     let signal = DefenseSignal::new(
                         *member_id, // emitter (could be any node that detected it)
                         *member_id,
-                        symbiont_core::node::ThreatType::Sybil,
+                        ThreatType::Sybil,
                         cluster.confidence,
                         evidence,
                     );
@@ -377,40 +1576,251 @@ impl Network {
             }
         }
 
+        self.penalize_vouchers(&defectors);
+
         // Process all emitted signals
         for signal in signals_to_emit {
-            self.queue_event(Event::DefenseSignal { signal });
+            self.queue_event(Event::defense_signal(signal, self.tick));
         }
     }
 
-    /// Get network statistics
-    pub fn stats(&self) -> NetworkStats {
-        let trust_values: Vec<f64> = self.nodes.values().map(|n| n.trust.value()).collect();
-        let connection_counts: Vec<usize> =
-            self.nodes.values().map(|n| n.connections.len()).collect();
-
-        let mean_trust = if trust_values.is_empty() {
-            0.0
-        } else {
-            trust_values.iter().sum::<f64>() / trust_values.len() as f64
-        };
+    /// Approximate bytes held by nodes, their histories and connection maps
+    ///
+    /// Counts allocated capacity, so it is an estimate for capacity planning
+    /// rather than an exact heap measurement.
+    pub fn approx_memory_bytes(&self) -> usize {
+        let connection_entry = std::mem::size_of::<(NodeId, Connection)>();
+        self.nodes
+            .values()
+            .map(|node| {
+                std::mem::size_of::<Node>()
+                    + node.history.approx_memory_bytes()
+                    + node.connections.capacity() * connection_entry
+            })
+            .sum()
+    }
 
-        let mean_connections = if connection_counts.is_empty() {
-            0.0
+    /// Get network statistics from the running totals, without walking nodes
+    pub fn stats(&self) -> NetworkStats {
+        let running = if self.stats_dirty {
+            RunningStats::from_nodes(self.nodes.values())
         } else {
-            connection_counts.iter().sum::<usize>() as f64 / connection_counts.len() as f64
+            self.running
         };
+        let n = self.nodes.len();
+        let per_node = |total: f64| if n == 0 { 0.0 } else { total / n as f64 };
 
-        let total_connections: usize = connection_counts.iter().sum::<usize>() / 2;
+        // σ² = E[T²] - E[T]², which can dip just below zero from rounding
+        let mean_trust = per_node(running.trust_sum);
+        let variance = per_node(running.trust_sq_sum) - mean_trust * mean_trust;
 
         NetworkStats {
-            node_count: self.nodes.len(),
-            connection_count: total_connections,
+            node_count: n,
+            connection_count: running.connection_ends / 2,
             mean_trust,
-            mean_connections,
+            trust_std_dev: variance.max(0.0).sqrt(),
+            mean_connections: per_node(running.connection_ends as f64),
+            alert_level: Score::new(per_node(running.alert_sum)).value(),
+            defense: running.defense,
             tick: self.tick,
         }
     }
+
+    /// Rebuild the running totals from every node and return fresh stats
+    ///
+    /// Use after editing nodes through [`Network::nodes_mut`] between ticks,
+    /// or to check the running totals against a full recomputation.
+    pub fn recompute_stats(&mut self) -> NetworkStats {
+        self.running = RunningStats::from_nodes(self.nodes.values());
+        self.stats_dirty = false;
+        self.stats()
+    }
+
+    /// How on edge the network is, in [0, 1]
+    ///
+    /// A = mean over nodes of (π + s) / 2, where π is node priming and s is
+    /// 0 for Normal, 0.5 for Primed and 1 for Defending. It falls back toward
+    /// zero as priming decays and nodes stand down.
+    pub fn alert_level(&self) -> Score {
+        if self.nodes.is_empty() {
+            return Score::ZERO;
+        }
+        let total: f64 = self.nodes.values().map(node_alert).sum();
+        Score::new(total / self.nodes.len() as f64)
+    }
+
+    /// Number of nodes in each defense state
+    pub fn defense_census(&self) -> DefenseCensus {
+        let mut census = DefenseCensus::default();
+        for node in self.nodes.values() {
+            census.count(node.defense_state, 1);
+        }
+        census
+    }
+
+    /// Supply of each capability across active nodes
+    ///
+    /// A provider's available capacity is 1 - load on that capability, or 0
+    /// if it cannot accept more work for it.
+    pub fn capability_supply(&self) -> HashMap<CapabilityId, CapabilitySupply> {
+        let mut supply: HashMap<CapabilityId, CapabilitySupply> = HashMap::new();
+
+        for node in self.nodes.values().filter(|n| n.status.is_active()) {
+            for (&cap, state) in &node.capabilities {
+                let capacity = if node.can_accept_capability_work(cap) {
+                    1.0 - state.load.value()
+                } else {
+                    0.0
+                };
+                let entry = supply.entry(cap).or_default();
+                entry.providers += 1;
+                entry.mean_quality += node.capability_quality(cap).value();
+                entry.mean_capacity += capacity;
+            }
+        }
+
+        for entry in supply.values_mut() {
+            let n = entry.providers as f64;
+            entry.mean_quality /= n;
+            entry.mean_capacity /= n;
+        }
+
+        supply
+    }
+
+    /// Highest-quality active node that can take work for a capability
+    ///
+    /// Ties go to the more trusted node, then the lower id.
+    pub fn best_provider(&self, cap: CapabilityId) -> Option<NodeId> {
+        self.nodes
+            .values()
+            .filter(|n| n.status.is_active() && n.can_accept_capability_work(cap))
+            .max_by(|a, b| {
+                a.capability_quality(cap)
+                    .value()
+                    .total_cmp(&b.capability_quality(cap).value())
+                    .then(a.trust.value().total_cmp(&b.trust.value()))
+                    .then(b.id.cmp(&a.id))
+            })
+            .map(|n| n.id)
+    }
+}
+
+impl Network {
+    /// Collect undirected edges, deduplicating bidirectional connections
+    ///
+    /// Returns `(a, b) -> (weight, threat)` with `a < b`, where weight is the
+    /// mean of both directions and threat is the higher mutual threat belief.
+    fn undirected_edges(&self) -> BTreeMap<(NodeId, NodeId), (f64, f64)> {
+        let mut edges: BTreeMap<(NodeId, NodeId), (f64, f64)> = BTreeMap::new();
+
+        for (id, node) in &self.nodes {
+            for (partner, conn) in &node.connections {
+                if !self.nodes.contains_key(partner) {
+                    continue;
+                }
+                let key = if id < partner { (*id, *partner) } else { (*partner, *id) };
+                let threat = node.get_threat_level(partner);
+                edges
+                    .entry(key)
+                    .and_modify(|(w, t)| {
+                        *w = (*w + conn.w.value()) / 2.0;
+                        *t = t.max(threat);
+                    })
+                    .or_insert((conn.w.value(), threat));
+            }
+        }
+
+        edges
+    }
+
+    /// Node IDs in a stable order for export
+    fn sorted_node_ids(&self) -> Vec<NodeId> {
+        let mut ids: Vec<_> = self.nodes.keys().cloned().collect();
+        ids.sort();
+        ids
+    }
+
+    /// Export the topology as a Graphviz DOT graph
+    ///
+    /// Nodes are labeled with trust and status; edges carry the connection
+    /// weight and are colored by threat belief.
+    pub fn export_dot<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writeln!(writer, "digraph symbiont {{")?;
+        writeln!(writer, "  edge [dir=none];")?;
+
+        for id in self.sorted_node_ids() {
+            let node = &self.nodes[&id];
+            writeln!(
+                writer,
+                "  \"{}\" [label=\"{}\\ntrust={:.3}\\n{:?}\"];",
+                id, id, node.trust.value(), node.status
+            )?;
+        }
+
+        for ((a, b), (weight, threat)) in self.undirected_edges() {
+            writeln!(
+                writer,
+                "  \"{}\" -> \"{}\" [weight={:.3}, penwidth={:.2}, color=\"{}\"];",
+                a,
+                b,
+                weight,
+                1.0 + 4.0 * weight,
+                threat_color(threat)
+            )?;
+        }
+
+        writeln!(writer, "}}")
+    }
+
+    /// Export the topology as GraphML
+    pub fn export_graphml<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(writer, r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#)?;
+        writeln!(writer, r#"  <key id="trust" for="node" attr.name="trust" attr.type="double"/>"#)?;
+        writeln!(writer, r#"  <key id="status" for="node" attr.name="status" attr.type="string"/>"#)?;
+        writeln!(writer, r#"  <key id="weight" for="edge" attr.name="weight" attr.type="double"/>"#)?;
+        writeln!(writer, r#"  <key id="threat" for="edge" attr.name="threat" attr.type="double"/>"#)?;
+        writeln!(writer, r#"  <key id="color" for="edge" attr.name="color" attr.type="string"/>"#)?;
+        writeln!(writer, r#"  <graph id="symbiont" edgedefault="undirected">"#)?;
+
+        for id in self.sorted_node_ids() {
+            let node = &self.nodes[&id];
+            writeln!(writer, r#"    <node id="{id}">"#)?;
+            writeln!(writer, r#"      <data key="trust">{:.4}</data>"#, node.trust.value())?;
+            writeln!(writer, r#"      <data key="status">{:?}</data>"#, node.status)?;
+            writeln!(writer, "    </node>")?;
+        }
+
+        for ((a, b), (weight, threat)) in self.undirected_edges() {
+            writeln!(writer, r#"    <edge source="{a}" target="{b}">"#)?;
+            writeln!(writer, r#"      <data key="weight">{weight:.4}</data>"#)?;
+            writeln!(writer, r#"      <data key="threat">{threat:.4}</data>"#)?;
+            writeln!(writer, r#"      <data key="color">{}</data>"#, threat_color(threat))?;
+            writeln!(writer, "    </edge>")?;
+        }
+
+        writeln!(writer, "  </graph>")?;
+        writeln!(writer, "</graphml>")
+    }
+}
+
+/// Emit a debug event if a node's status changed
+fn log_status_change(node: &Node, before: NodeStatus) {
+    if node.status != before {
+        debug!(node = %node.id, from = ?before, to = ?node.status, "node status changed");
+    }
+}
+
+/// Edge color for a threat belief level
+fn threat_color(threat: f64) -> &'static str {
+    if threat >= 0.7 {
+        "red"
+    } else if threat >= 0.3 {
+        "orange"
+    } else {
+        "black"
+    }
 }
 
 impl Default for Network {
@@ -428,49 +1838,1716 @@ pub struct NetworkStats {
     pub connection_count: usize,
     /// Mean trust score
     pub mean_trust: f64,
+    /// Standard deviation of trust scores
+    pub trust_std_dev: f64,
     /// Mean connections per node
     pub mean_connections: f64,
+    /// Network-wide alertness (see [`Network::alert_level`])
+    pub alert_level: f64,
+    /// Number of nodes in each defense state
+    pub defense: DefenseCensus,
     /// Current tick
     pub tick: u64,
 }
 
+/// Count of nodes in each defense state
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DefenseCensus {
+    /// Nodes operating normally
+    pub normal: usize,
+    /// Nodes on heightened alert
+    pub primed: usize,
+    /// Nodes actively defending
+    pub defending: usize,
+}
+
+impl DefenseCensus {
+    /// Add `delta` nodes (negative to remove) to the count for `state`
+    fn count(&mut self, state: DefenseState, delta: isize) {
+        let slot = match state {
+            DefenseState::Normal => &mut self.normal,
+            DefenseState::Primed => &mut self.primed,
+            DefenseState::Defending => &mut self.defending,
+        };
+        *slot = slot.saturating_add_signed(delta);
+    }
+}
+
+/// A node's share of the alert level: (π + s) / 2
+fn node_alert(node: &Node) -> f64 {
+    let state = match node.defense_state {
+        DefenseState::Normal => 0.0,
+        DefenseState::Primed => 0.5,
+        DefenseState::Defending => 1.0,
+    };
+    (node.priming.value() + state) / 2.0
+}
+
+/// Running totals behind [`Network::stats`]
+///
+/// Joins, departures and link changes adjust the totals as they happen, and
+/// each tick's trust update rebuilds them in the pass it already makes, so
+/// reading stats never walks the network.
+#[derive(Debug, Clone, Copy, Default)]
+struct RunningStats {
+    /// Σ T
+    trust_sum: f64,
+    /// Σ T²
+    trust_sq_sum: f64,
+    /// Σ connections per node, each link counted from both ends
+    connection_ends: usize,
+    /// Σ (π + s) / 2
+    alert_sum: f64,
+    /// Nodes in each defense state
+    defense: DefenseCensus,
+}
+
+impl RunningStats {
+    /// Totals over the given nodes
+    fn from_nodes<'a>(nodes: impl IntoIterator<Item = &'a Node>) -> Self {
+        let mut running = Self::default();
+        for node in nodes {
+            running.add(node);
+        }
+        running
+    }
+
+    /// Count a node joining
+    fn add(&mut self, node: &Node) {
+        let trust = node.trust.value();
+        self.trust_sum += trust;
+        self.trust_sq_sum += trust * trust;
+        self.connection_ends += node.connections.len();
+        self.alert_sum += node_alert(node);
+        self.defense.count(node.defense_state, 1);
+    }
+
+    /// Count a node leaving
+    fn remove(&mut self, node: &Node) {
+        let trust = node.trust.value();
+        self.trust_sum -= trust;
+        self.trust_sq_sum -= trust * trust;
+        self.connection_ends = self.connection_ends.saturating_sub(node.connections.len());
+        self.alert_sum -= node_alert(node);
+        self.defense.count(node.defense_state, -1);
+    }
+}
+
+/// How well a capability is supplied across the network
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CapabilitySupply {
+    /// Number of active nodes offering the capability
+    pub providers: usize,
+    /// Mean capability quality among providers
+    pub mean_quality: f64,
+    /// Mean available capacity among providers
+    pub mean_capacity: f64,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::agents::HonestAgent;
     use symbiont_core::capability::common;
+    use symbiont_core::constants::{
+        MAX_PROBATION_FAILURES, PROBATION_COUNT, SWIFT_TRUST_BASE, SWIFT_TRUST_FLOOR,
+        SWIFT_TRUST_MIN_INTERACTIONS, TRUST_VOLATILITY_THRESHOLD, TRUST_VOLATILITY_WINDOW,
+        VOUCH_PENALTY, W_INIT,
+    };
+    use symbiont_core::detection::{CollusionConfig, DetectionResult};
+    use symbiont_core::interaction::{Interaction, InteractionHistory};
+    use symbiont_core::node::NodeFlag;
+    use symbiont_core::types::{Score, Weight};
+    use symbiont_core::workflow::{execute_sequential_workflow, patterns, StepStatus};
 
+    #[cfg(feature = "parallel")]
     #[test]
-    fn test_network_creation() {
+    fn test_parallel_actions_match_serial() {
         let config = NetworkConfig::default()
-            .with_nodes(5)
+            .with_nodes(200)
             .with_capability(common::analysis())
-            .with_connection_prob(0.5)
+            .with_connection_prob(0.1)
             .with_seed(42);
+        let mut network = Network::from_config(config);
 
-        let network = Network::from_config(config);
+        let ids: Vec<_> = network.nodes().keys().cloned().collect();
+        for id in ids {
+            network.set_agent(id, Box::new(HonestAgent::new(1.0)));
+        }
 
-        assert_eq!(network.node_count(), 5);
-        assert!(network.stats().connection_count > 0);
+        let serial = network.agent_actions_serial();
+        let parallel = network.agent_actions_parallel();
+
+        let count = |batches: &[Vec<Event>]| batches.iter().map(Vec::len).sum::<usize>();
+        assert_eq!(count(&serial), 200);
+        assert_eq!(count(&serial), count(&parallel));
+
+        // Batches merge in the same node order either way
+        let initiators = |batches: &[Vec<Event>]| -> Vec<NodeId> {
+            batches
+                .iter()
+                .flatten()
+                .filter_map(|e| match e {
+                    Event::Interaction { from, .. } => Some(*from),
+                    _ => None,
+                })
+                .collect()
+        };
+        assert_eq!(initiators(&serial), initiators(&parallel));
+    }
+
+    /// Advance with a fixed interaction pattern so runs are reproducible
+    fn run_ring(network: &mut Network, ticks: u64) {
+        let ids = network.sorted_node_ids();
+        for _ in 0..ticks {
+            for (i, from) in ids.iter().enumerate() {
+                let to = ids[(i + 1) % ids.len()];
+                let quality = Score::new(0.5 + 0.05 * (i % 8) as f64);
+                network.queue_event(Event::interaction(*from, to, quality));
+            }
+            network.tick();
+        }
     }
 
     #[test]
-    fn test_network_tick() {
+    fn test_snapshot_restore_matches_continuous_run() {
+        let config = NetworkConfig::default()
+            .with_nodes(12)
+            .with_capability(common::analysis())
+            .with_connection_prob(0.3)
+            .with_trust_mode(TrustMode::Hybrid {
+                alpha: BlendWeight::new(0.6),
+                iterations: 10,
+            })
+            .with_seed(42);
+
+        let mut continuous = Network::from_config(config);
+        let defector = NodeId::from_index(0);
+        continuous.set_agent_type(defector, AgentType::Strategic { defection_tick: 70 });
+        continuous.attach_agents(AgentType::Honest { interaction_rate: 0.5, base_quality: 0.7 });
+        run_ring(&mut continuous, 50);
+
+        let json = serde_json::to_string(&continuous.snapshot()).unwrap();
+        let mut restored = Network::restore(serde_json::from_str(&json).unwrap());
+        assert_eq!(restored.tick, 50);
+        assert_eq!(restored.trust_mode, continuous.trust_mode);
+        assert_eq!(restored.agent_type_of(&defector), Some("strategic_adversary"));
+        assert_eq!(restored.agent_type_of(&NodeId::from_index(1)), Some("honest"));
+
+        run_ring(&mut continuous, 50);
+        run_ring(&mut restored, 50);
+
+        assert_eq!(restored.tick, continuous.tick);
+        assert_eq!(
+            restored.metrics.trust_history.len(),
+            continuous.metrics.trust_history.len()
+        );
+        assert_eq!(
+            restored.metrics.interaction_counts,
+            continuous.metrics.interaction_counts
+        );
+        for (id, node) in continuous.nodes() {
+            let other = &restored.nodes()[id];
+            assert!((node.trust.value() - other.trust.value()).abs() < 1e-9);
+            assert_eq!(node.connections.len(), other.connections.len());
+            for (peer, conn) in &node.connections {
+                assert!((conn.w.value() - other.connections[peer].w.value()).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[cfg(feature = "persistence")]
+    #[test]
+    fn test_snapshot_bincode_round_trip() {
+        let config = NetworkConfig::default()
+            .with_nodes(12)
+            .with_capability(common::analysis())
+            .with_connection_prob(0.3)
+            .with_seed(42);
+        let mut network = Network::from_config(config);
+        run_ring(&mut network, 50);
+
+        let snapshot = network.snapshot();
+        let bytes = snapshot.to_bytes().unwrap();
+        assert!(bytes.len() < serde_json::to_vec(&snapshot).unwrap().len());
+
+        let decoded = NetworkSnapshot::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            serde_json::to_value(&decoded).unwrap(),
+            serde_json::to_value(&snapshot).unwrap()
+        );
+        let restored = Network::restore(decoded);
+        assert_eq!(restored.tick, network.tick);
+        assert_eq!(restored.metrics.digest(), network.metrics.digest());
+    }
+
+    #[test]
+    fn test_snapshot_magic_and_version_checked() {
         let mut network = Network::from_config(
             NetworkConfig::default()
-                .with_nodes(3)
-                .with_seed(42),
+                .with_nodes(4)
+                .with_capability(common::analysis())
+                .with_seed(5),
         );
+        run_ring(&mut network, 5);
+        let snapshot = network.snapshot();
+        assert_eq!(snapshot.version, SNAPSHOT_FORMAT);
+        assert_eq!(snapshot.written_by, symbiont_core::VERSION);
 
-        // Add honest agents
-        let node_ids: Vec<_> = network.nodes.keys().cloned().collect();
-        for id in node_ids {
-            network.set_agent(id, Box::new(HonestAgent::new(0.5)));
+        let json = serde_json::to_vec(&snapshot).unwrap();
+        assert_eq!(NetworkSnapshot::from_json(&json).unwrap().tick, network.tick);
+
+        let mut foreign = snapshot.clone();
+        foreign.magic = *b"NOPE";
+        let err = NetworkSnapshot::from_json(&serde_json::to_vec(&foreign).unwrap()).unwrap_err();
+        assert!(matches!(err, SnapshotError::BadMagic { found } if &found == b"NOPE"));
+        assert!(err.to_string().contains("not a Symbiont snapshot"));
+
+        let mut newer = snapshot;
+        newer.version = SNAPSHOT_FORMAT + 1;
+        assert!(matches!(
+            newer.migrate(),
+            Err(SnapshotError::UnsupportedVersion { found }) if found == SNAPSHOT_FORMAT + 1
+        ));
+    }
+
+    #[test]
+    fn test_legacy_snapshot_migrates() {
+        // Written before snapshots had a header: three nodes, three ticks
+        let legacy = include_str!("../tests/fixtures/snapshot-v0.json");
+        let restored = NetworkSnapshot::from_json(legacy.as_bytes()).unwrap();
+        assert_eq!(restored.magic, SNAPSHOT_MAGIC);
+        assert_eq!(restored.version, SNAPSHOT_FORMAT);
+        assert!(restored.written_by.is_empty());
+        assert_eq!(restored.tick, 3);
+        assert_eq!(restored.nodes.len(), 3);
+        for node in &restored.nodes {
+            assert_eq!(node.affirmation_count, 3);
+            assert_eq!(node.recent_affirmations.mean(), Some(0.8500000000000002));
+        }
+        let mut network = Network::restore(restored);
+        run_ring(&mut network, 3);
+        assert_eq!(network.tick, 6);
+    }
+
+    #[test]
+    fn test_header_snapshot_upgrades_affirmation_proof() {
+        // Written with the header but before the affirmation store
+        let legacy = include_str!("../tests/fixtures/snapshot-v1.json");
+        assert!(legacy.contains("\"affirmation_proof\""));
+        let restored = NetworkSnapshot::from_json(legacy.as_bytes()).unwrap();
+        assert_eq!(restored.version, SNAPSHOT_FORMAT);
+        assert_eq!(restored.tick, 3);
+        for node in &restored.nodes {
+            assert_eq!(node.recent_affirmations.mean(), Some(0.8500000000000002));
         }
+        Network::restore(restored);
+    }
 
-        let initial_tick = network.tick;
+    #[test]
+    fn test_warm_start_takes_config_and_hashes_prior() {
+        let mut network = Network::from_config(
+            NetworkConfig::default()
+                .with_nodes(4)
+                .with_capability(common::analysis())
+                .with_seed(5),
+        );
+        run_ring(&mut network, 40);
+        let ids = network.sorted_node_ids();
+        let node = network.get_node_mut(&ids[0]).unwrap();
+        node.flags.extend([NodeFlag::LowDiversity, NodeFlag::Isolated, NodeFlag::QualityAnomaly]);
+        node.vouchees.extend(ids[1..].iter().copied());
+        let prior = network.snapshot();
+
+        // Reloading rebuilds every map and set, but the hash stays put
+        let reloaded = NetworkSnapshot::from_json(&serde_json::to_vec(&prior).unwrap()).unwrap();
+        let config = NetworkConfig::default().with_seed(5);
+        let warm_hash = config.clone().with_prior(prior.clone()).config_hash();
+        assert_eq!(warm_hash, config.clone().with_prior(reloaded).config_hash());
+        assert_ne!(warm_hash, config.config_hash());
+
+        let mut later = prior.clone();
+        later.nodes[1].trust = Score::new(later.nodes[1].trust.value() / 2.0);
+        assert_ne!(warm_hash, config.clone().with_prior(later).config_hash());
+
+        // Warm nodes take this config's settings rather than the prior run's
+        let params = ProtocolParams::default().with_diversity_window(20);
+        let warm = Network::from_config(
+            config.with_params(params.clone()).with_history_size(30).with_prior(prior),
+        );
+        assert_eq!(warm.node_count(), ids.len());
+        for node in warm.nodes().values() {
+            assert_eq!(node.params, params);
+            assert_eq!(node.history.max_size(), 30);
+            assert!(node.history.len() <= 30);
+        }
+    }
+
+    #[cfg(feature = "persistence")]
+    #[test]
+    fn test_snapshot_bytes_reject_wrong_magic() {
+        let mut network = Network::from_config(
+            NetworkConfig::default()
+                .with_nodes(4)
+                .with_capability(common::analysis())
+                .with_seed(5),
+        );
+        run_ring(&mut network, 5);
+        let mut bytes = network.snapshot().to_bytes().unwrap();
+        assert!(NetworkSnapshot::from_bytes(&bytes).is_ok());
+
+        // Older binary layouts cannot be upgraded
+        let mut stale = bytes.clone();
+        stale[4..6].copy_from_slice(&0u16.to_le_bytes());
+        assert!(matches!(
+            NetworkSnapshot::from_bytes(&stale),
+            Err(SnapshotError::StaleBinary { found: 0 })
+        ));
+
+        bytes[..4].copy_from_slice(b"JUNK");
+        let err = NetworkSnapshot::from_bytes(&bytes).unwrap_err();
+        assert!(matches!(err, SnapshotError::BadMagic { found } if &found == b"JUNK"));
+
+        // Too short to hold a magic at all
+        assert!(matches!(
+            NetworkSnapshot::from_bytes(b"SY"),
+            Err(SnapshotError::BadMagic { .. })
+        ));
+    }
+
+    #[test]
+    fn test_status_transitions_recorded() {
+        let mut network = Network::from_config(
+            NetworkConfig::default()
+                .with_nodes(6)
+                .with_capability(common::analysis())
+                .with_seed(2),
+        );
+
+        // One good interaction per node per tick finishes probation at tick 50
+        let ids = network.sorted_node_ids();
+        for _ in 0..60 {
+            for (i, from) in ids.iter().enumerate() {
+                let to = ids[(i + 1) % ids.len()];
+                network.queue_event(Event::interaction(*from, to, Score::new(0.8)));
+            }
+            network.tick();
+        }
+
+        for node in network.nodes().values() {
+            let change = node.status_history.first().unwrap();
+            assert_eq!(change.tick, PROBATION_COUNT as u64);
+            assert_eq!(change.to, NodeStatus::Member);
+        }
+        assert_eq!(network.metrics.status_transitions[&NodeStatus::Member], 6);
+
+        // Changes made between ticks count once, even for archived nodes
+        network.get_node_mut(&ids[0]).unwrap().expel();
+        network.get_node_mut(&ids[1]).unwrap().expel();
+        network.archive_expelled();
+        network.tick();
         network.tick();
+        assert_eq!(network.metrics.status_transitions[&NodeStatus::Expelled], 2);
+    }
 
-        assert_eq!(network.tick, initial_tick + 1);
+    #[test]
+    fn test_running_stats_match_full_recomputation() {
+        let mut network = Network::from_config(
+            NetworkConfig::default()
+                .with_nodes(12)
+                .with_capability(common::analysis())
+                .with_seed(21),
+        );
+        let ids = network.sorted_node_ids();
+        for &id in &ids {
+            network.set_agent(id, Box::new(HonestAgent::new(0.8)));
+        }
+
+        let check = |network: &mut Network| {
+            let running = network.stats();
+            let full = network.recompute_stats();
+            assert_eq!(running.node_count, full.node_count);
+            assert_eq!(running.connection_count, full.connection_count);
+            assert_eq!(running.defense, full.defense);
+            assert!((running.mean_trust - full.mean_trust).abs() < 1e-9);
+            assert!((running.trust_std_dev - full.trust_std_dev).abs() < 1e-6);
+            assert!((running.mean_connections - full.mean_connections).abs() < 1e-9);
+            assert!((running.alert_level - full.alert_level).abs() < 1e-9);
+        };
+        check(&mut network);
+
+        for round in 0..5u64 {
+            for _ in 0..10 {
+                network.tick();
+            }
+            check(&mut network);
+
+            // A newcomer joins and links up, and a member leaves
+            let newcomer = NodeId::from_index(100 + round);
+            network.add_node(Node::with_capabilities(newcomer, vec![common::analysis()]));
+            network.connect(newcomer, ids[0]);
+            network.connect(newcomer, ids[1]);
+            network.connect(newcomer, ids[1]);
+            check(&mut network);
+            network.remove_node_gracefully(ids[2 + round as usize]);
+            check(&mut network);
+        }
+
+        network.partition(ids[..4].iter().cloned());
+        check(&mut network);
+        network.heal();
+        check(&mut network);
+        assert!(network.stats().trust_std_dev > 0.0);
+
+        // Edits made outside the network's own paths still show up
+        for node in network.nodes_mut().values_mut() {
+            node.trust = Score::new(0.25);
+        }
+        assert!((network.stats().mean_trust - 0.25).abs() < 1e-9);
+        assert_eq!(network.stats().trust_std_dev, 0.0);
+        network.get_node_mut(&ids[0]).unwrap().trust = Score::ONE;
+        assert!(network.stats().mean_trust > 0.25);
+        check(&mut network);
+    }
+
+    #[test]
+    fn test_empty_and_single_node_networks_tick() {
+        let mut empty = Network::new();
+        for _ in 0..ADVERSARY_INTERVAL {
+            empty.tick();
+        }
+        let stats = empty.stats();
+        assert_eq!(stats.node_count, 0);
+        assert_eq!(stats.mean_trust, 0.0);
+        assert_eq!(stats.alert_level, 0.0);
+        assert!(empty.metrics.trust_history.is_empty());
+
+        let mut single = Network::from_config(
+            NetworkConfig::default()
+                .with_nodes(1)
+                .with_capability(common::analysis())
+                .with_seed(1),
+        );
+        let id = single.sorted_node_ids()[0];
+        single.set_agent(id, Box::new(HonestAgent::new(1.0)));
+        for _ in 0..ADVERSARY_INTERVAL {
+            single.tick();
+        }
+        assert_eq!(single.stats().connection_count, 0);
+        assert_eq!(single.metrics.trust_history.len(), ADVERSARY_INTERVAL as usize);
+        assert_eq!(single.get_node(&id).unwrap().interaction_count(), 0);
+    }
+
+    #[test]
+    fn test_rapid_climber_flagged() {
+        let mut network = Network::from_config(
+            NetworkConfig::default()
+                .with_nodes(12)
+                .with_capability(common::analysis())
+                .with_connection_prob(1.0)
+                .with_seed(3),
+        );
+        let ids = network.sorted_node_ids();
+        let climber = ids[0];
+        for id in &ids {
+            network.get_node_mut(id).unwrap().status = NodeStatus::Member;
+        }
+
+        // Only the climber works, so everyone else's trust stays flat
+        let run = |network: &mut Network, ticks: usize| {
+            for _ in 0..ticks {
+                for partner in &ids[1..] {
+                    network.queue_event(Event::interaction(climber, *partner, Score::new(0.95)));
+                }
+                network.tick();
+            }
+        };
+        let volatile = |network: &Network, id: &NodeId| {
+            network.get_node(id).unwrap().flags.contains(&NodeFlag::TrustVolatility)
+        };
+
+        run(&mut network, TRUST_VOLATILITY_WINDOW);
+        assert!(volatile(&network, &climber));
+        assert!(ids[1..].iter().all(|id| !volatile(&network, id)));
+
+        // Once its trust levels off the flag clears
+        run(&mut network, 2 * TRUST_VOLATILITY_WINDOW);
+        assert!(!volatile(&network, &climber));
+    }
+
+    #[test]
+    fn test_oscillating_quality_flags_trust_volatility() {
+        let mut network = Network::from_config(
+            NetworkConfig::default()
+                .with_nodes(60)
+                .with_capability(common::analysis())
+                .with_connection_prob(1.0)
+                .with_seed(3),
+        );
+        let mut ids: Vec<_> = network.nodes().keys().cloned().collect();
+        ids.sort();
+        let node = ids[0];
+        network.get_node_mut(&node).unwrap().status = NodeStatus::Member;
+
+        let run = |network: &mut Network, ticks: u64, period: u64| {
+            for t in 0..ticks {
+                let quality = if (t / period) % 2 == 0 { 0.95 } else { 0.05 };
+                for partner in &ids[1..] {
+                    network.queue_event(Event::interaction(node, *partner, Score::new(quality)));
+                }
+                network.tick();
+            }
+        };
+
+        run(&mut network, 100, 20);
+        let flagged = network.get_node(&node).unwrap();
+        assert!(flagged.flags.contains(&NodeFlag::TrustVolatility));
+        assert!(!flagged.is_trust_stable(TRUST_VOLATILITY_WINDOW, TRUST_VOLATILITY_THRESHOLD));
+
+        // Steady quality lets trust settle and clears the flag
+        run(&mut network, 100, u64::MAX);
+        let settled = network.get_node(&node).unwrap();
+        assert!(!settled.flags.contains(&NodeFlag::TrustVolatility));
+        assert!(settled.is_trust_stable(TRUST_VOLATILITY_WINDOW, TRUST_VOLATILITY_THRESHOLD));
+    }
+
+    #[test]
+    fn test_same_seed_reproduces_trust_history() {
+        let run = |seed: u64| {
+            let mut network = Network::from_config(
+                NetworkConfig::default()
+                    .with_nodes(15)
+                    .with_capability(common::analysis())
+                    .with_capability(common::generation())
+                    .with_connection_prob(0.3)
+                    .with_seed(seed),
+            );
+            for id in network.sorted_node_ids() {
+                network.set_agent(id, Box::new(HonestAgent::new(0.7)));
+            }
+            for _ in 0..100 {
+                network.tick();
+            }
+            network
+        };
+
+        let trajectories = |network: &Network| -> Vec<Vec<f64>> {
+            network
+                .sorted_node_ids()
+                .iter()
+                .map(|id| {
+                    let history = network.metrics.node_trajectory(id).unwrap();
+                    history.iter().map(|(_, t)| t.value()).collect()
+                })
+                .collect()
+        };
+
+        // Map iteration order can reorder float sums, so allow rounding error
+        let (a, b) = (run(17), run(17));
+        let (ta, tb) = (trajectories(&a), trajectories(&b));
+        assert_eq!(ta.len(), tb.len());
+        for (x, y) in ta.iter().zip(&tb) {
+            assert_eq!(x.len(), y.len());
+            assert!(x.iter().zip(y).all(|(p, q)| (p - q).abs() < 1e-12));
+        }
+        assert_eq!(a.stats().connection_count, b.stats().connection_count);
+
+        // A different seed takes a different path
+        assert_ne!(trajectories(&a), trajectories(&run(18)));
+    }
+
+    #[test]
+    fn test_network_creation() {
+        let config = NetworkConfig::default()
+            .with_nodes(5)
+            .with_capability(common::analysis())
+            .with_connection_prob(0.5)
+            .with_seed(42);
+
+        let network = Network::from_config(config);
+
+        assert_eq!(network.node_count(), 5);
+        assert!(network.stats().connection_count > 0);
+    }
+
+    #[test]
+    fn test_capability_supply() {
+        let mut network = Network::from_config(
+            NetworkConfig::default()
+                .with_nodes(10)
+                .with_capability(common::analysis())
+                .with_seed(42),
+        );
+        let analysis = common::analysis().id;
+        let validation = common::validation().id;
+
+        // Only two nodes validate; the better one is half busy
+        let (strong, weak) = (NodeId::from_index(1), NodeId::from_index(2));
+        for (id, quality) in [(strong, 0.9), (weak, 0.6)] {
+            let node = network.get_node_mut(&id).unwrap();
+            node.add_capability(common::validation());
+            node.capabilities.get_mut(&validation).unwrap().quality = Score::new(quality);
+        }
+        network.get_node_mut(&strong).unwrap().capabilities.get_mut(&validation).unwrap().load =
+            Score::HALF;
+
+        // Expelled nodes don't count as supply
+        network.get_node_mut(&NodeId::from_index(9)).unwrap().expel();
+
+        let supply = network.capability_supply();
+        assert_eq!(supply[&analysis].providers, 9);
+        assert!((supply[&analysis].mean_capacity - 1.0).abs() < 1e-9);
+
+        let scarce = &supply[&validation];
+        assert_eq!(scarce.providers, 2);
+        assert!((scarce.mean_quality - 0.75).abs() < 1e-9);
+        assert!((scarce.mean_capacity - 0.75).abs() < 1e-9);
+
+        assert_eq!(network.best_provider(validation), Some(strong));
+        assert_eq!(network.best_provider(common::generation().id), None);
+    }
+
+    #[test]
+    fn test_idle_node_loses_swift_trust() {
+        let mut network = Network::from_config(
+            NetworkConfig::default()
+                .with_nodes(20)
+                .with_capability(common::analysis())
+                .with_connection_prob(0.3)
+                .with_seed(5),
+        );
+
+        // Node 0 has no agent and only ever responds; everyone else is honest
+        let responder = NodeId::from_index(0);
+        let active = NodeId::from_index(1);
+        for id in network.sorted_node_ids().into_iter().filter(|id| *id != responder) {
+            network.set_agent(id, Box::new(HonestAgent::new(0.8)));
+        }
+
+        // A newcomer cut off from everyone is never engaged at all
+        let idle = NodeId::from_index(100);
+        network.add_node(Node::with_capabilities(idle, vec![common::analysis()]));
+        network.partition([idle]);
+
+        for _ in 0..20 {
+            network.tick();
+        }
+        let active_early = network.nodes[&active].trust.value();
+
+        for _ in 0..300 {
+            network.tick();
+        }
+        let idle_node = &network.nodes[&idle];
+        let responder_node = &network.nodes[&responder];
+        let active_node = &network.nodes[&active];
+
+        assert_eq!(idle_node.initiated_count + idle_node.received_count, 0);
+        assert_eq!(idle_node.swift_trust_multiplier, SWIFT_TRUST_FLOOR);
+        assert!(idle_node.trust_cap.value() <= SWIFT_TRUST_FLOOR);
+
+        // Being well served counts as engagement
+        assert_eq!(responder_node.initiated_count, 0);
+        assert!(responder_node.received_count >= SWIFT_TRUST_MIN_INTERACTIONS);
+        assert_eq!(responder_node.swift_trust_multiplier, 1.0);
+
+        assert!(active_node.initiated_count >= SWIFT_TRUST_MIN_INTERACTIONS);
+        assert_eq!(active_node.swift_trust_multiplier, 1.0);
+        assert!(active_node.trust.value() >= active_early);
+    }
+
+    #[test]
+    #[tracing_test::traced_test]
+    fn test_detection_emits_trace_event() {
+        let mut network = Network::from_config(
+            NetworkConfig::default()
+                .with_nodes(12)
+                .with_capability(common::analysis())
+                .with_connection_prob(0.4)
+                .with_seed(3),
+        );
+        for id in network.sorted_node_ids() {
+            network.set_agent(id, Box::new(HonestAgent::new(0.5)));
+        }
+
+        // Inject an adversary that skims most of every exchange
+        let adversary = NodeId::from_index(100);
+        network.queue_event(Event::NodeJoin {
+            node: Box::new(Node::with_capabilities(adversary, vec![common::analysis()])),
+            agent_type: Some(AgentType::Camouflage { skim: 0.8 }),
+        });
+
+        for _ in 0..ADVERSARY_INTERVAL {
+            network.tick();
+        }
+
+        assert!(logs_contain("threat detected"));
+        assert!(logs_contain(&format!("node={adversary}")));
+    }
+
+    #[test]
+    fn test_scan_records_detections() {
+        let mut network = Network::from_config(
+            NetworkConfig::default()
+                .with_nodes(12)
+                .with_capability(common::analysis())
+                .with_connection_prob(0.4)
+                .with_seed(3),
+        );
+        for id in network.sorted_node_ids() {
+            network.set_agent(id, Box::new(HonestAgent::new(0.5)));
+        }
+
+        let adversary = NodeId::from_index(100);
+        network.queue_event(Event::NodeJoin {
+            node: Box::new(Node::with_capabilities(adversary, vec![common::analysis()])),
+            agent_type: Some(AgentType::Strategic { defection_tick: 50 }),
+        });
+
+        for _ in 0..3 * ADVERSARY_INTERVAL {
+            network.tick();
+        }
+
+        let events = &network.metrics.detection_events;
+        assert!(!events.is_empty());
+        assert_eq!(network.metrics.summary().detection_count, events.len());
+        assert!(events.iter().any(|e| e.node_id == adversary), "{events:?}");
+    }
+
+    #[test]
+    fn test_custom_detector_fires_during_scan() {
+        let mut network = Network::from_config(
+            NetworkConfig::default()
+                .with_nodes(6)
+                .with_capability(common::analysis())
+                .with_seed(3),
+        );
+        let target = NodeId::from_index(2);
+        network.detectors_mut().register(move |node, _| {
+            if node.id == target {
+                DetectionResult::threat(
+                    node.id,
+                    ThreatType::Cheating,
+                    Score::new(0.9),
+                    String::from("Flagged by custom detector"),
+                )
+            } else {
+                DetectionResult::clean(node.id)
+            }
+        });
+
+        // An idle network is stable, so scans run at the longest interval
+        for _ in 0..ADVERSARY_INTERVAL_MAX {
+            network.tick();
+        }
+
+        let events = &network.metrics.detection_events;
+        assert!(events.iter().any(|e| e.node_id == target), "{events:?}");
+        assert!(events.iter().all(|e| e.node_id == target));
+    }
+
+    /// Honest network with a stealth Sybil ring of four, run for three scans
+    fn stealth_sybil_ring(detection: DetectionConfig) -> (Network, Vec<NodeId>) {
+        let mut network = Network::from_config(
+            NetworkConfig::default()
+                .with_nodes(12)
+                .with_capability(common::analysis())
+                .with_connection_prob(0.3)
+                .with_seed(8)
+                .with_detection(detection),
+        );
+        for id in network.sorted_node_ids() {
+            network.set_agent(id, Box::new(HonestAgent::new(0.5)));
+        }
+
+        // A ring of four, bridged to the honest network by one member; honest
+        // agents then find the ring's members on their own
+        let ring: Vec<_> = (100..104).map(NodeId::from_index).collect();
+        for id in &ring {
+            network.add_node_with_agent(
+                Node::with_capabilities(*id, vec![common::analysis()]),
+                Box::new(StealthSybil::new(ring.clone())),
+            );
+        }
+        for (i, a) in ring.iter().enumerate() {
+            for b in &ring[i + 1..] {
+                network.connect(*a, *b);
+            }
+        }
+        network.connect(ring[0], NodeId::from_index(0));
+
+        for _ in 0..3 * ADVERSARY_INTERVAL {
+            network.tick();
+        }
+        (network, ring)
+    }
+
+    #[test]
+    fn test_stealth_sybil_caught_by_internal_structure() {
+        let (network, ring) = stealth_sybil_ring(DetectionConfig::default());
+        let outsider = NodeId::from_index(0);
+
+        // Outsiders see good work, and the ring links as widely as anyone...
+        let external = network.get_node(&outsider).unwrap().get_connection(&ring[0]).unwrap();
+        assert!(external.q.value() > 0.8, "external quality {}", external.q.value());
+        for id in &ring {
+            assert!(network.get_node(id).unwrap().connections.len() > ring.len() + 2);
+        }
+
+        // ...but internal density and mutual rating give the ring away
+        let clusters = detect_collusion_with(network.nodes(), &DetectionConfig::default());
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters[0].members, ring.iter().cloned().collect());
+        assert!(clusters[0].mutual_rating > 0.9);
+
+        // Scans use the network's detection settings, so a lenient density
+        // threshold leaves out the collusion evidence
+        let threat = |network: &Network, id: &NodeId| {
+            network.get_node(id).unwrap().threat_beliefs[id].level.value()
+        };
+        let lenient = CollusionConfig { density_threshold: 1.0, ..CollusionConfig::default() };
+        let (unflagged, _) =
+            stealth_sybil_ring(DetectionConfig::default().with_collusion(lenient));
+        for id in &ring {
+            assert!(threat(&network, id) > threat(&unflagged, id));
+        }
+    }
+
+    #[test]
+    fn test_scan_interval_shortens_after_injection() {
+        let mut network = Network::from_config(
+            NetworkConfig::default()
+                .with_nodes(16)
+                .with_capability(common::analysis())
+                .with_connection_prob(0.4)
+                .with_seed(5),
+        );
+        for id in network.sorted_node_ids() {
+            network.set_agent(id, Box::new(HonestAgent::new(0.5)));
+        }
+        for _ in 0..290 {
+            network.tick();
+        }
+        assert!(network.next_scan_interval() >= ADVERSARY_INTERVAL);
+
+        let injected_at = network.tick;
+        for i in 0..4 {
+            let id = NodeId::from_index(100 + i);
+            network.queue_event(Event::NodeJoin {
+                node: Box::new(Node::with_capabilities(id, vec![common::analysis()])),
+                agent_type: Some(AgentType::FreeRider { interaction_rate: 0.8 }),
+            });
+        }
+
+        // Joins bring the next scan forward
+        while network.last_scan_tick <= injected_at {
+            network.tick();
+        }
+        assert!(network.last_scan_tick <= injected_at + ADVERSARY_INTERVAL_MIN);
+        assert_eq!(network.joins_since_scan, 0);
+
+        // With joins cleared, the trust variance spike keeps it short
+        for _ in 0..5 {
+            network.tick();
+        }
+        assert_eq!(network.next_scan_interval(), ADVERSARY_INTERVAL_MIN);
+    }
+
+    #[test]
+    fn test_metrics_digest_reproducible() {
+        let run = |connection_prob: f64| {
+            let mut network = Network::from_config(
+                NetworkConfig::default()
+                    .with_nodes(10)
+                    .with_capability(common::analysis())
+                    .with_connection_prob(connection_prob)
+                    .with_seed(9),
+            );
+            for id in network.sorted_node_ids() {
+                network.set_agent(id, Box::new(HonestAgent::new(0.5)));
+            }
+            network.queue_event(Event::NodeJoin {
+                node: Box::new(Node::with_capabilities(
+                    NodeId::from_index(50),
+                    vec![common::analysis()],
+                )),
+                agent_type: Some(AgentType::FreeRider { interaction_rate: 0.8 }),
+            });
+            for _ in 0..150 {
+                network.tick();
+            }
+            network.metrics
+        };
+
+        let first = run(0.4);
+        let second = run(0.4);
+        assert_eq!(first.seed, Some(9));
+        assert!(first.config_hash.is_some());
+        assert!(!first.detection_events.is_empty());
+        assert_eq!(first.digest(), second.digest());
+
+        assert_ne!(first.digest(), run(0.5).digest());
+
+        // Tampering with recorded trust or detections changes the digest
+        let mut tampered = first.clone();
+        tampered.trust_history[100].mean += 1e-6;
+        assert_ne!(first.digest(), tampered.digest());
+
+        let mut tampered = first.clone();
+        tampered.detection_events.pop();
+        assert_ne!(first.digest(), tampered.digest());
+    }
+
+    #[test]
+    fn test_nan_reciprocity_keeps_mean_trust_finite() {
+        let mut network = Network::from_config(
+            NetworkConfig::default()
+                .with_nodes(8)
+                .with_capability(common::analysis())
+                .with_connection_prob(1.0)
+                .with_seed(6),
+        );
+        for id in network.sorted_node_ids() {
+            network.set_agent(id, Box::new(HonestAgent::new(0.5)));
+        }
+
+        let (id, partner) = (NodeId::from_index(0), NodeId::from_index(1));
+        network.nodes.get_mut(&id).unwrap().connections.get_mut(&partner).unwrap().r = f64::NAN;
+        for _ in 0..20 {
+            network.tick();
+        }
+
+        assert!(network.nodes.values().all(|n| n.trust.value().is_finite()));
+        let latest = network.metrics.trust_history.last().unwrap();
+        assert!(latest.mean.is_finite() && latest.std_dev.is_finite());
+        assert!(network.stats().mean_trust.is_finite());
+    }
+
+    #[test]
+    fn test_network_tick() {
+        let mut network = Network::from_config(
+            NetworkConfig::default()
+                .with_nodes(3)
+                .with_seed(42),
+        );
+
+        // Add honest agents
+        let node_ids: Vec<_> = network.nodes.keys().cloned().collect();
+        for id in node_ids {
+            network.set_agent(id, Box::new(HonestAgent::new(0.5)));
+        }
+
+        let initial_tick = network.tick;
+        network.tick();
+
+        assert_eq!(network.tick, initial_tick + 1);
+    }
+
+    #[test]
+    fn test_event_order_does_not_affect_outcome() {
+        let id = NodeId::from_index;
+        let mut events: Vec<Event> = (0..4u64)
+            .flat_map(|i| (0..4u64).filter(move |&j| j != i).map(move |j| (i, j)))
+            .map(|(i, j)| Event::interaction(id(i), id(j), Score::new(0.2 + 0.2 * j as f64)))
+            .collect();
+        events.push(Event::node_leave(id(3)));
+        events.push(Event::defense_signal(
+            DefenseSignal::new(
+                id(0),
+                id(1),
+                ThreatType::Cheating,
+                Score::new(0.8),
+                Hash::compute(b"test"),
+            ),
+            0,
+        ));
+
+        // Connections and history, minus wall-clock timestamps
+        let fingerprint = |network: &Network| {
+            let mut ids: Vec<_> = network.nodes().keys().cloned().collect();
+            ids.sort();
+            ids.iter()
+                .map(|node_id| {
+                    let node = &network.nodes()[node_id];
+                    let mut conns: Vec<_> = node
+                        .connections
+                        .values()
+                        .map(|c| (c.partner_id, c.w.value(), c.r, c.q.value(), c.count))
+                        .collect();
+                    conns.sort_by_key(|c| c.0);
+                    let history: Vec<_> = node
+                        .history
+                        .all()
+                        .iter()
+                        .map(|i| (i.initiator, i.responder, i.quality.value()))
+                        .collect();
+                    (*node_id, conns, history, node.get_threat_level(node_id))
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let run = |events: Vec<Event>| {
+            let mut network =
+                Network::from_config(NetworkConfig::default().with_nodes(4).with_seed(5));
+            for event in events {
+                network.queue_event(event);
+            }
+            network.process_events();
+            fingerprint(&network)
+        };
+
+        let forward = run(events.clone());
+        events.reverse();
+        let reversed = run(events);
+
+        assert_eq!(forward, reversed);
+        assert_eq!(forward.len(), 3);
+        // Interactions with the leaving node still land before it leaves
+        assert!(forward[0].2.iter().any(|i| i.1 == id(3)));
+        assert!(forward[1].3 > 0.0);
+    }
+
+    #[test]
+    fn test_replay_interaction_log_orders_trust() {
+        use crate::replay::node_id;
+
+        // "good" and "poor" use the same 20 servers but rate them differently;
+        // "narrow" is as satisfied as "good" but only ever uses 3 servers
+        let mut log = String::from("from,to,quality,timestamp\n");
+        for t in 0..30 {
+            for s in 0..20 {
+                log.push_str(&format!("good,s{s},0.9,{t}\n"));
+                log.push_str(&format!("poor,s{s},0.05,{t}\n"));
+                if s < 3 {
+                    log.push_str(&format!("narrow,s{s},0.9,{t}\n"));
+                }
+            }
+        }
+        let records = InteractionRecord::from_csv_reader(log.as_bytes()).unwrap();
+
+        let mut network = Network::new();
+        network.apply_interaction_log(records.into_iter());
+
+        assert_eq!(network.tick, 30);
+        assert_eq!(network.nodes().len(), 23);
+        let server = network.get_node(&node_id("s0")).unwrap();
+        assert_eq!(server.connections.len(), 3);
+        assert_eq!(server.connections.values().map(|c| c.count).sum::<u32>(), 90);
+
+        let trust = |name: &str| network.get_node(&node_id(name)).unwrap().trust.value();
+        assert!(trust("good") > trust("poor"), "{} vs {}", trust("good"), trust("poor"));
+        assert!(trust("good") > trust("narrow"), "{} vs {}", trust("good"), trust("narrow"));
+    }
+
+    #[test]
+    fn test_history_size_config_and_memory() {
+        let build = |size: usize| {
+            let mut network = Network::from_config(
+                NetworkConfig::default()
+                    .with_nodes(8)
+                    .with_capability(common::analysis())
+                    .with_history_size(size)
+                    .with_seed(4),
+            );
+            for id in network.sorted_node_ids() {
+                network.set_agent(id, Box::new(HonestAgent::new(0.5)));
+            }
+            for _ in 0..50 {
+                network.tick();
+            }
+            network
+        };
+
+        let small = build(INTERACTION_HISTORY_SIZE);
+        let large = build(500);
+        assert!(large.nodes.values().all(|n| n.history.max_size() == 500));
+        assert!(small.approx_memory_bytes() > 0);
+        assert!(large.approx_memory_bytes() > small.approx_memory_bytes());
+    }
+
+    #[test]
+    fn test_stale_defense_signal_dropped() {
+        let mut network = Network::from_config(
+            NetworkConfig::default()
+                .with_nodes(3)
+                .with_seed(4)
+                .with_signal_max_age_ticks(100),
+        );
+        let target = NodeId::from_index(1);
+        let signal = DefenseSignal::new(
+            NodeId::from_index(0),
+            target,
+            ThreatType::Cheating,
+            Score::new(0.8),
+            Hash::compute(b"old evidence"),
+        );
+        let belief =
+            |network: &Network| network.get_node(&target).unwrap().get_threat_level(&target);
+
+        network.tick = 500;
+        network.queue_event(Event::defense_signal(signal.clone(), 0));
+        network.process_events();
+        assert_eq!(belief(&network), 0.0);
+        assert_eq!(network.metrics.stale_signals_dropped, 1);
+        assert_eq!(network.metrics.summary().stale_signals_dropped, 1);
+
+        // The same signal within its lifetime is applied
+        network.queue_event(Event::defense_signal(signal, 450));
+        network.process_events();
+        assert!(belief(&network) > 0.0);
+        assert_eq!(network.metrics.stale_signals_dropped, 1);
+    }
+
+    #[test]
+    fn test_defense_signal_primes_connections() {
+        let mut network = Network::from_config(
+            NetworkConfig::default()
+                .with_nodes(4)
+                .with_connection_prob(1.0)
+                .with_seed(4),
+        );
+        let threat = NodeId::from_index(1);
+        let watcher = NodeId::from_index(0);
+        let signal = DefenseSignal::new(
+            NodeId::from_index(2),
+            threat,
+            ThreatType::Cheating,
+            Score::new(0.8),
+            Hash::compute(b"evidence"),
+        );
+        let pi = |network: &Network| {
+            network.get_node(&watcher).unwrap().get_connection(&threat).unwrap().pi.value()
+        };
+        assert_eq!(pi(&network), 0.0);
+
+        network.queue_event(Event::defense_signal(signal, 0));
+        network.process_events();
+        let primed = pi(&network);
+        assert!((primed - 0.8 * PRIMING_SENSITIVITY).abs() < 1e-9);
+
+        for _ in 0..20 {
+            network.tick();
+        }
+        assert!(pi(&network) < primed);
+        assert!(pi(&network) > 0.0);
+    }
+
+    #[test]
+    fn test_signal_raises_alert_level_until_it_decays() {
+        let mut network = Network::from_config(
+            NetworkConfig::default()
+                .with_nodes(6)
+                .with_connection_prob(1.0)
+                .with_seed(6),
+        );
+        assert_eq!(network.alert_level(), Score::ZERO);
+        assert_eq!(network.defense_census().normal, 6);
+
+        // Repeated high-confidence reports about one node put its neighbors on alert
+        let threat = NodeId::from_index(5);
+        for i in 0..4u8 {
+            let signal = DefenseSignal::new(
+                NodeId::from_index(0),
+                threat,
+                ThreatType::Cheating,
+                Score::ONE,
+                Hash::compute(&[i]),
+            );
+            network.queue_event(Event::defense_signal(signal, 0));
+        }
+        network.process_events();
+
+        let raised = network.alert_level().value();
+        let census = network.defense_census();
+        assert_eq!(census.primed, 5);
+        assert_eq!(census.normal, 1);
+        assert!(raised > 0.3, "alert {raised}");
+        let stats = network.stats();
+        // Running totals agree up to rounding
+        assert!((stats.alert_level - raised).abs() < 1e-12);
+        assert_eq!(stats.defense, census);
+
+        for _ in 0..400 {
+            network.tick();
+        }
+        assert!(network.alert_level().value() < 0.05 * raised);
+        assert_eq!(network.defense_census().normal, 6);
+    }
+
+    #[test]
+    fn test_virtual_clock_decays_idle_connection() {
+        // Two linked nodes without agents, so the link never sees traffic
+        let build = |config: NetworkConfig| {
+            Network::from_config(config.with_nodes(2).with_connection_prob(1.0).with_seed(1))
+        };
+        let (a, b) = (NodeId::from_index(0), NodeId::from_index(1));
+
+        let mut wall = build(NetworkConfig::default());
+        let mut virtual_time = build(NetworkConfig::default().with_virtual_clock(1_000));
+        let start = virtual_time.now();
+
+        for _ in 0..500 {
+            wall.tick();
+            virtual_time.tick();
+        }
+
+        // 500 ticks of one second each far exceed IDLE_THRESHOLD
+        assert_eq!(virtual_time.now() - start, 500_000);
+        assert!(virtual_time.get_node(&a).unwrap().get_connection(&b).is_none());
+        assert!(virtual_time.get_node(&b).unwrap().get_connection(&a).is_none());
+
+        // On the wall clock the same run lasts milliseconds, so nothing idles
+        let conn = wall.get_node(&a).unwrap().get_connection(&b).unwrap();
+        assert_eq!(conn.w.value(), W_INIT);
+    }
+
+    #[test]
+    fn test_snapshot_keeps_virtual_clock() {
+        let mut network = Network::from_config(
+            NetworkConfig::default()
+                .with_nodes(4)
+                .with_capability(common::analysis())
+                .with_virtual_clock(1_000)
+                .with_seed(5),
+        );
+        run_ring(&mut network, 5);
+
+        let snapshot = network.snapshot();
+        let clock = snapshot.clock.unwrap();
+        assert_eq!(clock.now, network.now());
+        assert_eq!(clock.ms_per_tick, 1_000);
+
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let mut restored = Network::restore(serde_json::from_str(&json).unwrap());
+        assert_eq!(restored.now(), network.now());
+        run_ring(&mut network, 5);
+        run_ring(&mut restored, 5);
+        assert_eq!(restored.now(), network.now());
+        assert_eq!(restored.now() - clock.now, 5_000);
+
+        // Wall-clock networks record no clock
+        let wall = Network::from_config(NetworkConfig::default().with_nodes(2));
+        assert!(wall.snapshot().clock.is_none());
+    }
+
+    #[test]
+    fn test_snapshot_keeps_partition() {
+        let mut network = Network::from_config(
+            NetworkConfig::default()
+                .with_nodes(6)
+                .with_connection_prob(1.0)
+                .with_seed(5),
+        );
+        let side: Vec<_> = (0..3).map(NodeId::from_index).collect();
+        network.partition(side.iter().cloned());
+
+        let json = serde_json::to_string(&network.snapshot()).unwrap();
+        let mut restored = Network::restore(serde_json::from_str(&json).unwrap());
+        assert!(restored.is_partitioned());
+        assert!(restored.is_separated(&side[0], &NodeId::from_index(5)));
+        assert!(!restored.is_separated(&side[0], &side[1]));
+
+        // Healing the restored network reconnects the severed links
+        restored.heal();
+        for node in restored.nodes().values() {
+            assert_eq!(node.connections.len(), 5);
+        }
+    }
+
+    #[test]
+    fn test_snapshot_keeps_decision() {
+        let mut network = Network::from_config(
+            NetworkConfig::default()
+                .with_nodes(4)
+                .with_connection_prob(1.0)
+                .with_seed(5),
+        );
+        let positions = (0..4)
+            .map(|i| (NodeId::from_index(i), if i < 2 { 0.2 } else { 0.8 }))
+            .collect();
+        network.start_decision(Decision::new(TaskId::new(1), positions, Score::ONE));
+        for _ in 0..3 {
+            network.tick();
+        }
+
+        let json = serde_json::to_string(&network.snapshot()).unwrap();
+        let mut restored = Network::restore(serde_json::from_str(&json).unwrap());
+        for _ in 0..3 {
+            network.tick();
+            restored.tick();
+        }
+        let (ours, theirs) = (network.decision().unwrap(), restored.decision().unwrap());
+        assert_eq!(theirs.tracker.round_count, ours.tracker.round_count);
+        for i in 0..4 {
+            let id = NodeId::from_index(i);
+            assert!((ours.position(&id).unwrap() - theirs.position(&id).unwrap()).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_affirmations_raise_confidence() {
+        use symbiont_core::defense::AffirmationType;
+
+        let mut network = Network::from_config(NetworkConfig::default().with_nodes(5).with_seed(9));
+        let recipient = NodeId::from_index(0);
+        let initial = network.get_node(&recipient).unwrap().confidence;
+
+        for _ in 0..30 {
+            for i in 1..5 {
+                network.get_node_mut(&NodeId::from_index(i)).unwrap().trust = Score::new(0.9);
+                network.queue_affirmation(Affirmation::new(
+                    NodeId::from_index(i),
+                    recipient,
+                    AffirmationType::Quality,
+                    Score::new(0.95),
+                ));
+            }
+            network.tick();
+        }
+
+        let node = network.get_node(&recipient).unwrap();
+        assert_eq!(node.affirmation_count, 120);
+        assert!(node.confidence.value() > initial.value() + 0.2);
+        assert!(node.recent_affirmations.mean().unwrap() > 0.9);
+
+        // Unaffirmed peers keep their starting confidence
+        let peer = network.get_node(&NodeId::from_index(1)).unwrap();
+        assert_eq!(peer.confidence, initial);
+    }
+
+    #[test]
+    fn test_tone_signals_set_stored_tone() {
+        use symbiont_core::interaction::ToneSignals;
+
+        let mut network = Network::from_config(
+            NetworkConfig::default()
+                .with_nodes(6)
+                .with_connection_prob(1.0)
+                .with_seed(3),
+        );
+        let ids: Vec<_> = network.nodes().keys().cloned().collect();
+        for id in ids {
+            network.set_agent(
+                id,
+                Box::new(HonestAgent::new(1.0).with_tone_signals(ToneSignals::positive())),
+            );
+        }
+
+        for _ in 0..10 {
+            network.tick();
+        }
+
+        let expected = ToneSignals::positive().compute_tone();
+        for node in network.nodes().values() {
+            assert!(!node.history.is_empty());
+            for interaction in node.history.all() {
+                assert!(interaction.tone.value() > 0.0);
+                assert_eq!(interaction.tone, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mid_run_join_trajectory() {
+        let mut network = Network::from_config(NetworkConfig::default().with_nodes(3).with_seed(7));
+        for _ in 0..19 {
+            network.tick();
+        }
+
+        let newcomer = NodeId::from_index(100);
+        network.queue_event(Event::node_join(Node::new(newcomer)));
+        for _ in 0..10 {
+            network.tick();
+        }
+
+        let trajectory = network.metrics.node_trajectory(&newcomer).unwrap();
+        assert_eq!(trajectory.first().unwrap().0, 20);
+        assert_eq!(trajectory.len(), 10);
+
+        let founder = network.metrics.node_trajectory(&NodeId::from_index(0)).unwrap();
+        assert_eq!(founder.first().unwrap().0, 1);
+        assert_eq!(founder.len(), 29);
+    }
+
+    #[test]
+    fn test_graceful_leave_cleans_up_and_reroutes() {
+        let mut network = Network::from_config(
+            NetworkConfig::default()
+                .with_nodes(6)
+                .with_capability(common::analysis())
+                .with_connection_prob(1.0)
+                .with_seed(2),
+        );
+
+        let origin = network.get_node(&NodeId::from_index(0)).unwrap().clone();
+        let mut workflow = patterns::chain(origin.id, vec![common::analysis().id]);
+        execute_sequential_workflow(&mut workflow, &origin, network.nodes());
+        let assignee = workflow.steps[0].assigned_to.unwrap();
+        let task = workflow.steps[0].task.id;
+        network.add_workflow(workflow);
+
+        let reassigned = network.remove_node_gracefully(assignee);
+
+        assert!(reassigned.contains(&task));
+        assert!(network.get_node(&assignee).is_none());
+        for node in network.nodes().values() {
+            assert!(!node.connections.contains_key(&assignee));
+        }
+        let step = &network.workflows()[0].steps[0];
+        assert!(matches!(step.assigned_to, Some(id) if id != assignee));
+        assert_eq!(step.status, StepStatus::Ready);
+
+        // Restored networks keep tracking the workflow
+        let json = serde_json::to_string(&network.snapshot()).unwrap();
+        let mut restored = Network::restore(serde_json::from_str(&json).unwrap());
+        let current = restored.workflows()[0].steps[0].assigned_to.unwrap();
+        assert!(restored.remove_node_gracefully(current).contains(&task));
+        let step = &restored.workflows()[0].steps[0];
+        assert!(matches!(step.assigned_to, Some(id) if id != current));
+
+        // Leave events take the same path
+        let leaver = NodeId::from_index(5);
+        network.queue_event(Event::node_leave(leaver));
+        network.process_events();
+        assert!(network
+            .nodes()
+            .values()
+            .all(|n| !n.connections.contains_key(&leaver)));
+    }
+
+    #[test]
+    fn test_archive_expelled_nodes() {
+        let mut network = Network::from_config(
+            NetworkConfig::default()
+                .with_nodes(5)
+                .with_capability(common::analysis())
+                .with_connection_prob(1.0)
+                .with_seed(8),
+        );
+        let expelled = NodeId::from_index(3);
+        network.get_node_mut(&expelled).unwrap().expel();
+        assert_eq!(network.stats().node_count, 5);
+
+        assert_eq!(network.archive_expelled(), 1);
+        assert_eq!(network.stats().node_count, 4);
+        assert!(network.get_node(&expelled).is_none());
+        assert!(network.nodes().values().all(|n| !n.connections.contains_key(&expelled)));
+        let archived = network.archived_node(&expelled).unwrap();
+        assert_eq!(archived.status, NodeStatus::Expelled);
+        assert!(!archived.connections.is_empty(), "links kept for forensics");
+
+        // Rejoining under the burned id is refused
+        network.queue_event(Event::node_join(Node::new(expelled)));
+        network.process_events();
+        assert!(network.get_node(&expelled).is_none());
+
+        // The archive survives a snapshot
+        let restored = Network::restore(network.snapshot());
+        assert!(restored.archived_node(&expelled).is_some());
+        assert_eq!(restored.node_count(), 4);
+    }
+
+    #[test]
+    fn test_global_trust_mode() {
+        let mut network = Network::from_config(
+            NetworkConfig::default()
+                .with_nodes(8)
+                .with_capability(common::analysis())
+                .with_connection_prob(0.5)
+                .with_seed(4)
+                .with_trust_mode(TrustMode::Global { iterations: 20 }),
+        );
+        let ids: Vec<_> = network.nodes().keys().cloned().collect();
+        for id in ids {
+            network.set_agent(id, Box::new(HonestAgent::new(1.0)));
+        }
+        for _ in 0..30 {
+            network.tick();
+        }
+
+        let global = compute_global_trust(network.nodes(), 20);
+        for node in network.nodes().values() {
+            assert_eq!(node.trust, global[&node.id]);
+        }
+        assert!(network
+            .nodes()
+            .values()
+            .any(|n| n.trust != compute_trust(n)));
+    }
+
+    #[test]
+    fn test_global_trust_respects_caps() {
+        // Three capable nodes put all their weight on an expelled node and on
+        // a node under a reduced cap
+        let mut network = Network::new();
+        let expelled = NodeId::from_index(0);
+        let capped = NodeId::from_index(1);
+        let mut node = Node::new(expelled);
+        node.expel();
+        network.add_node(node);
+        let mut node = Node::new(capped);
+        node.trust_cap = Score::new(0.1);
+        network.add_node(node);
+        for i in 2..=4 {
+            let id = NodeId::from_index(i);
+            let mut node = Node::with_capabilities(id, vec![common::analysis()]);
+            node.capabilities.get_mut(&common::analysis().id).unwrap().quality = Score::new(0.9);
+            for target in [expelled, capped] {
+                let mut conn = Connection::new(target);
+                conn.w = Weight::new(1.0);
+                node.connections.insert(target, conn);
+            }
+            network.add_node(node);
+        }
+
+        let hybrid = TrustMode::Hybrid { alpha: BlendWeight::new(0.2), iterations: 50 };
+        for mode in [TrustMode::Global { iterations: 50 }, hybrid] {
+            network.trust_mode = mode;
+            network.update_trust_scores();
+            assert_eq!(network.get_node(&expelled).unwrap().trust, Score::ZERO);
+            assert!(network.get_node(&capped).unwrap().trust.value() <= 0.1);
+        }
+    }
+
+    #[test]
+    fn test_global_trust_reorders_ranking() {
+        // Every node has a full window of distinct partners and no reduced
+        // cap, so neither cap binds. Node 0 does weaker work than node 4, but
+        // three strong nodes endorse it while node 4 is endorsed by no one.
+        fn node(id: NodeId, quality: f64) -> Node {
+            let mut node = Node::with_capabilities(id, vec![common::analysis()]);
+            let state = node.capabilities.get_mut(&common::analysis().id).unwrap();
+            state.quality = Score::new(quality);
+            state.volume = 10;
+            node.history = InteractionHistory::with_max_size(4);
+            for partner in 100..104 {
+                node.history.add(Interaction::new(id, NodeId::from_index(partner)));
+            }
+            node
+        }
+
+        let mut network = Network::new();
+        let endorsed = NodeId::from_index(0);
+        let loner = NodeId::from_index(4);
+        let endorsers: Vec<_> = (1..=3).map(NodeId::from_index).collect();
+        let mut weak = node(endorsed, 0.3);
+        for &id in &endorsers {
+            weak.connections.insert(id, Connection::new(id));
+        }
+        network.add_node(weak);
+        for &id in &endorsers {
+            let mut strong = node(id, 0.9);
+            for &target in endorsers.iter().chain([&endorsed]).filter(|&&t| t != id) {
+                let mut conn = Connection::new(target);
+                conn.w = Weight::new(1.0);
+                strong.connections.insert(target, conn);
+            }
+            network.add_node(strong);
+        }
+        network.add_node(node(loner, 0.9));
+
+        let mut trust_under = |mode| {
+            network.trust_mode = mode;
+            network.update_trust_scores();
+            let trust = |id| network.get_node(&id).unwrap().trust.value();
+            (trust(endorsed), trust(loner))
+        };
+        let (local_endorsed, local_loner) = trust_under(TrustMode::Local);
+        let (global_endorsed, global_loner) = trust_under(TrustMode::Global { iterations: 50 });
+        let alpha = BlendWeight::new(0.5);
+        let (hybrid_endorsed, _) = trust_under(TrustMode::Hybrid { alpha, iterations: 50 });
+
+        for node in network.nodes().values() {
+            assert_eq!(trust_ceiling(node), Score::ONE);
+        }
+        assert!(local_endorsed < local_loner);
+        assert!(global_endorsed > global_loner);
+        assert!(global_endorsed < 1.0);
+        assert!((hybrid_endorsed - (local_endorsed + global_endorsed) / 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_vouched_join_boosts_trust() {
+        let mut network = Network::from_config(NetworkConfig::default().with_nodes(3).with_seed(7));
+        let voucher = NodeId::from_index(0);
+        network.get_node_mut(&voucher).unwrap().trust = Score::new(0.9);
+
+        let newcomer = NodeId::from_index(100);
+        network.add_vouched_node(Node::new(newcomer), voucher);
+
+        let node = network.get_node(&newcomer).unwrap();
+        assert_eq!(node.vouched_by, Some(voucher));
+        assert!(node.trust.value() > SWIFT_TRUST_BASE);
+        assert!(network.get_node(&voucher).unwrap().vouchees.contains(&newcomer));
+    }
+
+    #[test]
+    fn test_vouchee_failure_penalizes_voucher() {
+        let mut network = Network::from_config(NetworkConfig::default().with_nodes(3).with_seed(7));
+        let voucher = NodeId::from_index(0);
+        network.get_node_mut(&voucher).unwrap().trust = Score::new(0.9);
+
+        let probation_window = |network: &mut Network, vouchee: NodeId, quality: f64| {
+            for _ in 0..PROBATION_COUNT {
+                network.queue_event(Event::interaction(vouchee, voucher, Score::new(quality)));
+            }
+            network.tick();
+        };
+        let multiplier =
+            |network: &Network| network.get_node(&voucher).unwrap().vouch_cap_multiplier;
+
+        // A failed window the vouchee recovers from is not held against the voucher
+        let recovered = NodeId::from_index(100);
+        network.add_vouched_node(Node::new(recovered), voucher);
+        probation_window(&mut network, recovered, 0.1);
+        assert_eq!(network.get_node(&recovered).unwrap().probation_failures, 1);
+        assert_eq!(multiplier(&network), 1.0);
+        probation_window(&mut network, recovered, 0.9);
+        assert_eq!(network.get_node(&recovered).unwrap().status, NodeStatus::Member);
+        assert_eq!(multiplier(&network), 1.0);
+
+        // Nor is leaving voluntarily
+        let leaver = NodeId::from_index(101);
+        network.add_vouched_node(Node::new(leaver), voucher);
+        network.queue_event(Event::node_leave(leaver));
+        network.tick();
+        assert!(network.get_node(&leaver).is_none());
+        assert_eq!(multiplier(&network), 1.0);
+
+        // Expulsion after repeated failures is
+        let expelled = NodeId::from_index(102);
+        network.add_vouched_node(Node::new(expelled), voucher);
+        for _ in 0..MAX_PROBATION_FAILURES {
+            probation_window(&mut network, expelled, 0.1);
+        }
+        assert_eq!(network.get_node(&expelled).unwrap().status, NodeStatus::Expelled);
+        let voucher_node = network.get_node(&voucher).unwrap();
+        assert!((voucher_node.vouch_cap_multiplier - VOUCH_PENALTY).abs() < 1e-9);
+        assert!(voucher_node.trust_cap.value() <= VOUCH_PENALTY);
+        assert!(!voucher_node.vouchees.contains(&expelled));
+        assert!(voucher_node.vouchees.contains(&recovered));
+    }
+
+    #[test]
+    fn test_export_dot() {
+        let network = Network::from_config(
+            NetworkConfig::default()
+                .with_nodes(4)
+                .with_connection_prob(1.0)
+                .with_seed(1),
+        );
+
+        let mut buf = Vec::new();
+        network.export_dot(&mut buf).unwrap();
+        let dot = String::from_utf8(buf).unwrap();
+
+        // Fully connected 4-node graph: 6 undirected edges
+        assert_eq!(dot.matches("->").count(), 6);
+        assert!(dot.contains("trust="));
+
+        let mut buf = Vec::new();
+        network.export_graphml(&mut buf).unwrap();
+        let graphml = String::from_utf8(buf).unwrap();
+        assert_eq!(graphml.matches("<edge ").count(), 6);
+        assert_eq!(graphml.matches("<node ").count(), 4);
     }
 }
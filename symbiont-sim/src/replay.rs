@@ -0,0 +1,73 @@
+//! Replay of recorded interaction logs through the trust engine.
+
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use symbiont_core::types::{Hash, NodeId, Score};
+
+/// One row of an interaction log
+///
+/// Agent names that are 64 hex characters are used as node ids directly;
+/// any other name is hashed into one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InteractionRecord {
+    /// Agent that initiated the interaction
+    pub from: String,
+    /// Agent that served it
+    pub to: String,
+    /// Quality rating of the interaction, clamped to [0, 1]
+    pub quality: f64,
+    /// When it happened; records sharing a timestamp replay in the same tick
+    pub timestamp: u64,
+}
+
+impl InteractionRecord {
+    /// Read records from CSV with a `from,to,quality,timestamp` header
+    pub fn from_csv_reader<R: Read>(reader: R) -> csv::Result<Vec<Self>> {
+        csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_reader(reader)
+            .deserialize()
+            .collect()
+    }
+
+    /// Node id of the initiator
+    pub fn from_id(&self) -> NodeId {
+        node_id(&self.from)
+    }
+
+    /// Node id of the responder
+    pub fn to_id(&self) -> NodeId {
+        node_id(&self.to)
+    }
+
+    /// Quality as a score
+    pub fn quality_score(&self) -> Score {
+        Score::new(self.quality)
+    }
+}
+
+/// Map an agent name to a node id
+pub fn node_id(name: &str) -> NodeId {
+    NodeId::from_hex(name).unwrap_or_else(|_| NodeId::new(Hash::compute(name.as_bytes()).0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_csv_reader() {
+        let log = "from,to,quality,timestamp\nalice, bob,0.9,1\nbob,alice,1.5,2\n";
+        let records = InteractionRecord::from_csv_reader(log.as_bytes()).unwrap();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].to, "bob");
+        assert_eq!(records[1].quality_score(), Score::ONE);
+        assert_eq!(records[0].from_id(), records[1].to_id());
+
+        let hex = NodeId::from_index(7).to_hex();
+        assert_eq!(node_id(&hex), NodeId::from_index(7));
+
+        assert!(InteractionRecord::from_csv_reader("from,to\na,b\n".as_bytes()).is_err());
+    }
+}
@@ -1,10 +1,12 @@
 //! Simulation runner for executing scenarios.
 
-use crate::events::EventScheduler;
-use crate::metrics::MetricsSummary;
-use crate::network::{Network, NetworkConfig};
+use crate::events::{AgentType, EventScheduler};
+use crate::metrics::{MetricsSummary, TrustSnapshot};
+use crate::network::{Network, NetworkConfig, NetworkSnapshot};
 use crate::scenarios::Scenario;
-use std::time::Instant;
+use std::io::Write;
+use std::time::{Duration, Instant};
+use symbiont_core::params::UnknownParam;
 
 /// Configuration for simulation run
 #[derive(Debug, Clone)]
@@ -17,6 +19,32 @@ pub struct SimulationConfig {
     pub detailed_metrics: bool,
     /// Progress reporting interval (ticks)
     pub progress_interval: Option<u64>,
+    /// Stop once trust spread stops changing
+    pub convergence_stop: Option<ConvergenceStop>,
+}
+
+/// Early-stopping rule based on trust distribution spread
+#[derive(Debug, Clone, Copy)]
+pub struct ConvergenceStop {
+    /// Number of recent snapshots to compare
+    pub window: usize,
+    /// Maximum std_dev change across the window
+    pub epsilon: f64,
+}
+
+impl ConvergenceStop {
+    /// Whether the last `window` std_dev values span less than `epsilon`
+    pub fn is_converged(&self, history: &[TrustSnapshot]) -> bool {
+        if self.window < 2 || history.len() < self.window {
+            return false;
+        }
+
+        let recent = &history[history.len() - self.window..];
+        let (min, max) = recent.iter().fold((f64::MAX, f64::MIN), |(lo, hi), s| {
+            (lo.min(s.std_dev), hi.max(s.std_dev))
+        });
+        max - min < self.epsilon
+    }
 }
 
 impl Default for SimulationConfig {
@@ -26,6 +54,7 @@ impl Default for SimulationConfig {
             network_config: NetworkConfig::default(),
             detailed_metrics: true,
             progress_interval: Some(100),
+            convergence_stop: None,
         }
     }
 }
@@ -48,6 +77,12 @@ impl SimulationConfig {
         self.detailed_metrics = enabled;
         self
     }
+
+    /// Halt once the last `window` trust std_dev values vary by less than `epsilon`
+    pub fn with_convergence_stop(mut self, window: usize, epsilon: f64) -> Self {
+        self.convergence_stop = Some(ConvergenceStop { window, epsilon });
+        self
+    }
 }
 
 /// Result of a simulation run
@@ -61,10 +96,140 @@ pub struct SimulationResult {
     pub final_tick: u64,
     /// Whether completed normally
     pub completed: bool,
+    /// Whether the run halted before max_ticks, on convergence or a wall-clock budget
+    pub stopped_early: bool,
     /// Any error message
     pub error: Option<String>,
 }
 
+/// Distribution of a metric across batch runs
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SampleStats {
+    /// Number of samples
+    pub count: usize,
+    /// Sample mean
+    pub mean: f64,
+    /// Population standard deviation
+    pub std_dev: f64,
+    /// Smallest sample
+    pub min: f64,
+    /// Largest sample
+    pub max: f64,
+}
+
+impl SampleStats {
+    /// Compute statistics over a set of samples
+    pub fn from_samples(samples: &[f64]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+
+        let n = samples.len() as f64;
+        let mean = samples.iter().sum::<f64>() / n;
+        let variance = samples.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+
+        Self {
+            count: samples.len(),
+            mean,
+            std_dev: variance.sqrt(),
+            min: samples.iter().cloned().fold(f64::INFINITY, f64::min),
+            max: samples.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+        }
+    }
+}
+
+impl std::fmt::Display for SampleStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:.3} ± {:.3} (min {:.3}, max {:.3})",
+            self.mean, self.std_dev, self.min, self.max
+        )
+    }
+}
+
+/// Aggregated results of running one scenario under several seeds
+#[derive(Debug, Clone)]
+pub struct BatchResult {
+    /// Seeds, in run order
+    pub seeds: Vec<u64>,
+    /// Per-seed summaries, in run order
+    pub summaries: Vec<MetricsSummary>,
+    /// Final mean trust across runs
+    pub final_mean_trust: SampleStats,
+    /// Detection counts across runs
+    pub detection_count: SampleStats,
+    /// Trust convergence across runs
+    pub trust_convergence: SampleStats,
+}
+
+impl BatchResult {
+    /// Aggregate per-seed summaries
+    fn from_summaries(seeds: Vec<u64>, summaries: Vec<MetricsSummary>) -> Self {
+        let stats = |metric: fn(&MetricsSummary) -> f64| {
+            let samples: Vec<f64> = summaries.iter().map(metric).collect();
+            SampleStats::from_samples(&samples)
+        };
+
+        Self {
+            final_mean_trust: stats(|s| s.final_mean_trust),
+            detection_count: stats(|s| s.detection_count as f64),
+            trust_convergence: stats(|s| s.trust_convergence),
+            seeds,
+            summaries,
+        }
+    }
+}
+
+impl std::fmt::Display for BatchResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Batch Summary ({} runs):", self.summaries.len())?;
+        writeln!(f, "  Final Mean Trust: {}", self.final_mean_trust)?;
+        writeln!(f, "  Detections: {}", self.detection_count)?;
+        write!(f, "  Trust Convergence: {}", self.trust_convergence)
+    }
+}
+
+/// Batch results for each value of a swept protocol parameter
+#[derive(Debug, Clone)]
+pub struct SweepResult {
+    /// Name of the swept [`ProtocolParams`](symbiont_core::params::ProtocolParams) field
+    pub param: String,
+    /// Parameter value and the batch run under it, in sweep order
+    pub rows: Vec<(f64, BatchResult)>,
+}
+
+impl SweepResult {
+    /// Export one CSV row per parameter value
+    pub fn export_csv<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writeln!(writer, "{},final_mean_trust,detection_count", self.param)?;
+
+        for (value, batch) in &self.rows {
+            writeln!(
+                writer,
+                "{},{:.4},{:.2}",
+                value, batch.final_mean_trust.mean, batch.detection_count.mean,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// `steps` evenly spaced values from `from` to `to` inclusive
+///
+/// Values are rounded to 12 decimal places so they print cleanly.
+pub fn sweep_values(from: f64, to: f64, steps: usize) -> Vec<f64> {
+    match steps {
+        0 => Vec::new(),
+        1 => vec![from],
+        _ => (0..steps)
+            .map(|i| from + (to - from) * i as f64 / (steps - 1) as f64)
+            .map(|v| (v * 1e12).round() / 1e12)
+            .collect(),
+    }
+}
+
 /// Callback for progress updates
 pub type ProgressCallback = Box<dyn Fn(u64, u64) + Send>;
 
@@ -78,6 +243,8 @@ pub struct SimulationRunner {
     scheduler: EventScheduler,
     /// Progress callback
     progress_callback: Option<ProgressCallback>,
+    /// Set once the convergence stop has fired
+    converged: bool,
 }
 
 impl SimulationRunner {
@@ -90,6 +257,33 @@ impl SimulationRunner {
             network,
             scheduler: EventScheduler::new(),
             progress_callback: None,
+            converged: false,
+        }
+    }
+
+    /// Resume a run from a snapshot, attaching `agent` to every node
+    ///
+    /// `config.max_ticks` counts ticks past the snapshot, and a seed in
+    /// `config.network_config` replaces the snapshot's; the rest of the
+    /// network configuration is ignored.
+    pub fn from_snapshot(
+        mut config: SimulationConfig,
+        snapshot: NetworkSnapshot,
+        agent: AgentType,
+    ) -> Self {
+        config.max_ticks = config.max_ticks.saturating_add(snapshot.tick);
+        let mut network = Network::restore(snapshot);
+        if let Some(seed) = config.network_config.seed {
+            network.set_seed(seed);
+        }
+        network.attach_agents(agent);
+
+        Self {
+            config,
+            network,
+            scheduler: EventScheduler::new(),
+            progress_callback: None,
+            converged: false,
         }
     }
 
@@ -119,38 +313,76 @@ impl SimulationRunner {
         scenario.setup(&mut self.network, &mut self.scheduler);
     }
 
-    /// Run the simulation
-    pub fn run(&mut self) -> SimulationResult {
-        let start = Instant::now();
-        let error = None;
+    /// Whether no ticks remain (max_ticks reached or converged)
+    pub fn is_finished(&self) -> bool {
+        self.converged || self.network.tick >= self.config.max_ticks
+    }
 
-        while self.network.tick < self.config.max_ticks {
-            // Process scheduled events
-            let due_events = self.scheduler.due_at(self.network.tick);
-            for event in due_events {
-                self.network.queue_event(event);
-            }
+    /// Advance exactly one tick, returning whether more remain
+    ///
+    /// Does nothing once the run is finished.
+    pub fn step(&mut self) -> bool {
+        if self.is_finished() {
+            return false;
+        }
+
+        // Process scheduled events
+        let due_events = self.scheduler.due_at(self.network.tick);
+        for event in due_events {
+            self.network.queue_event(event);
+        }
 
-            // Advance simulation
-            self.network.tick();
+        // Advance simulation
+        self.network.tick();
 
-            // Progress reporting
-            if let Some(interval) = self.config.progress_interval {
-                if self.network.tick % interval == 0 {
-                    if let Some(ref callback) = self.progress_callback {
-                        callback(self.network.tick, self.config.max_ticks);
-                    }
+        // Progress reporting
+        if let Some(interval) = self.config.progress_interval {
+            if self.network.tick % interval == 0 {
+                if let Some(ref callback) = self.progress_callback {
+                    callback(self.network.tick, self.config.max_ticks);
                 }
             }
         }
 
-        let duration = start.elapsed();
+        // Pending injections could still perturb trust, so keep running
+        if let Some(stop) = self.config.convergence_stop {
+            if !self.scheduler.has_pending()
+                && stop.is_converged(&self.network.metrics.trust_history)
+            {
+                self.converged = true;
+            }
+        }
+
+        !self.is_finished()
+    }
+
+    /// Run the simulation
+    pub fn run(&mut self) -> SimulationResult {
+        let start = Instant::now();
+        while self.step() {}
+        self.result(start)
+    }
+
+    /// Step until finished or until `max_wall` of wall-clock time has passed
+    ///
+    /// The budget is checked after each tick, so at least one tick runs if
+    /// any remain. Can be called again to resume.
+    pub fn run_for(&mut self, max_wall: Duration) -> SimulationResult {
+        let start = Instant::now();
+        while self.step() && start.elapsed() < max_wall {}
+        self.result(start)
+    }
+
+    /// Summarize the run so far
+    fn result(&self, start: Instant) -> SimulationResult {
+        let error = None;
 
         SimulationResult {
             summary: self.network.metrics.summary(),
-            duration_ms: duration.as_millis() as u64,
+            duration_ms: start.elapsed().as_millis() as u64,
             final_tick: self.network.tick,
             completed: error.is_none(),
+            stopped_early: self.network.tick < self.config.max_ticks,
             error,
         }
     }
@@ -161,15 +393,72 @@ impl SimulationRunner {
         runner.apply_scenario(scenario);
         runner.run()
     }
+
+    /// Run a fresh scenario under each seed and aggregate the outcomes
+    pub fn run_batch<F>(config: SimulationConfig, scenario_factory: F, seeds: &[u64]) -> BatchResult
+    where
+        F: Fn() -> Box<dyn Scenario>,
+    {
+        let summaries = seeds
+            .iter()
+            .map(|&seed| {
+                let mut config = config.clone();
+                config.network_config.seed = Some(seed);
+                let scenario = scenario_factory();
+                Self::run_scenario(config, scenario.as_ref()).summary
+            })
+            .collect();
+
+        BatchResult::from_summaries(seeds.to_vec(), summaries)
+    }
+
+    /// Run a batch for each value of a protocol parameter
+    ///
+    /// Fails before running anything if `param` is not a parameter name.
+    pub fn run_sweep<F>(
+        config: SimulationConfig,
+        scenario_factory: F,
+        param: &str,
+        values: &[f64],
+        seeds: &[u64],
+    ) -> Result<SweepResult, UnknownParam>
+    where
+        F: Fn() -> Box<dyn Scenario>,
+    {
+        let configs = values
+            .iter()
+            .map(|&value| {
+                let mut config = config.clone();
+                config.network_config.params =
+                    config.network_config.params.with_param(param, value)?;
+                Ok((value, config))
+            })
+            .collect::<Result<Vec<_>, UnknownParam>>()?;
+
+        let rows = configs
+            .into_iter()
+            .map(|(value, config)| (value, Self::run_batch(config, &scenario_factory, seeds)))
+            .collect();
+
+        Ok(SweepResult { param: param.to_string(), rows })
+    }
 }
 
 /// Quick-run helper for simple simulations
 pub fn quick_run(node_count: usize, ticks: u64) -> SimulationResult {
+    quick_run_with(node_count, SimulationConfig::default().with_ticks(ticks), |_| {})
+}
+
+/// Honest network of `node_count` nodes, with a hook to schedule events before running
+fn quick_run_with(
+    node_count: usize,
+    config: SimulationConfig,
+    setup: impl FnOnce(&mut EventScheduler),
+) -> SimulationResult {
     use crate::agents::HonestAgent;
     use symbiont_core::capability::common;
 
-    let config = SimulationConfig::default()
-        .with_ticks(ticks)
+    let config = config
         .with_network(
             NetworkConfig::default()
                 .with_nodes(node_count)
@@ -184,6 +473,7 @@ pub fn quick_run(node_count: usize, ticks: u64) -> SimulationResult {
     for id in node_ids {
         runner.network_mut().set_agent(id, Box::new(HonestAgent::new(0.5)));
     }
+    setup(runner.scheduler_mut());
 
     runner.run()
 }
@@ -213,4 +503,198 @@ mod tests {
         assert!(result.completed);
         assert_eq!(result.final_tick, 50);
     }
+
+    #[test]
+    fn test_step_matches_run() {
+        use crate::agents::HonestAgent;
+        use symbiont_core::capability::common;
+
+        let runner = || {
+            let config = SimulationConfig::default().with_ticks(60).with_network(
+                NetworkConfig::default()
+                    .with_nodes(8)
+                    .with_capability(common::analysis())
+                    .with_connection_prob(0.4)
+                    .with_seed(21),
+            );
+            let mut runner = SimulationRunner::new(config);
+            let ids: Vec<_> = runner.network().nodes().keys().cloned().collect();
+            for id in ids {
+                runner.network_mut().set_agent(id, Box::new(HonestAgent::new(0.5)));
+            }
+            runner
+        };
+        let trust = |runner: &SimulationRunner| {
+            let mut trusts: Vec<_> = runner
+                .network()
+                .nodes()
+                .values()
+                .map(|n| (n.id, n.trust.value()))
+                .collect();
+            trusts.sort_by_key(|t| t.0);
+            trusts
+        };
+
+        let mut stepped = runner();
+        let mut steps = 0;
+        while stepped.step() {
+            steps += 1;
+        }
+        assert_eq!(steps, 59);
+        assert!(stepped.is_finished());
+        assert!(!stepped.step());
+
+        let mut ran = runner();
+        let result = ran.run();
+        assert_eq!(stepped.network().tick, result.final_tick);
+        assert_eq!(trust(&stepped), trust(&ran));
+
+        // A spent budget stops after one tick; resuming finishes the run
+        let mut budgeted = runner();
+        let partial = budgeted.run_for(Duration::ZERO);
+        assert_eq!(partial.final_tick, 1);
+        assert!(partial.stopped_early);
+        let rest = budgeted.run_for(Duration::from_secs(60));
+        assert_eq!(rest.final_tick, 60);
+        assert!(!rest.stopped_early);
+        assert_eq!(trust(&budgeted), trust(&ran));
+    }
+
+    #[test]
+    fn test_replay_from_snapshot() {
+        use crate::scenarios::TrustEmergenceScenario;
+        use symbiont_core::capability::common;
+
+        let config = SimulationConfig::default().with_ticks(40).with_network(
+            NetworkConfig::default()
+                .with_nodes(8)
+                .with_capability(common::analysis())
+                .with_seed(3),
+        );
+        let mut prior = SimulationRunner::new(config);
+        prior.apply_scenario(&TrustEmergenceScenario::new());
+        prior.run();
+        let json = serde_json::to_string(&prior.network().snapshot()).unwrap();
+
+        let honest = AgentType::Honest { interaction_rate: 0.5, base_quality: 0.8 };
+        let replay = |seed: u64| {
+            let snapshot: NetworkSnapshot = serde_json::from_str(&json).unwrap();
+            let config = SimulationConfig::default()
+                .with_ticks(30)
+                .with_network(NetworkConfig::default().with_seed(seed));
+            let mut runner = SimulationRunner::from_snapshot(config, snapshot, honest.clone());
+            let result = runner.run();
+            (result, runner)
+        };
+
+        let (result, runner) = replay(42);
+        assert!(result.completed);
+        assert_eq!(result.final_tick, 70);
+        assert_eq!(runner.network().seed(), 42);
+        assert!(runner.network().nodes().keys().all(|id| {
+            runner.network().agent_type_of(id) == Some("honest")
+        }));
+        assert_eq!(runner.network().metrics.trust_history.len(), 70);
+
+        // Same snapshot and seed, same continuation
+        let digest = runner.network().metrics.digest();
+        assert_eq!(replay(42).1.network().metrics.digest(), digest);
+        assert_ne!(replay(43).1.network().metrics.digest(), digest);
+    }
+
+    #[test]
+    fn test_run_batch() {
+        use crate::scenarios::TrustEmergenceScenario;
+        use symbiont_core::capability::common;
+
+        let config = SimulationConfig::default().with_ticks(50).with_network(
+            NetworkConfig::default()
+                .with_nodes(10)
+                .with_capability(common::analysis())
+                .with_connection_prob(0.4),
+        );
+
+        let batch = SimulationRunner::run_batch(
+            config,
+            || Box::new(TrustEmergenceScenario::new()),
+            &[1, 2, 3, 4, 5],
+        );
+
+        assert_eq!(batch.seeds, vec![1, 2, 3, 4, 5]);
+        assert_eq!(batch.final_mean_trust.count, 5);
+        assert_eq!(batch.detection_count.count, 5);
+        assert!(batch.final_mean_trust.min <= batch.final_mean_trust.mean);
+        assert!(batch.final_mean_trust.mean <= batch.final_mean_trust.max);
+        assert!(batch.to_string().starts_with("Batch Summary (5 runs):"));
+    }
+
+    #[test]
+    fn test_run_sweep() {
+        use crate::scenarios::TrustEmergenceScenario;
+        use symbiont_core::capability::common;
+
+        let config = SimulationConfig::default().with_ticks(30).with_network(
+            NetworkConfig::default()
+                .with_nodes(8)
+                .with_capability(common::analysis())
+                .with_connection_prob(0.4),
+        );
+        let values = sweep_values(0.05, 0.2, 4);
+        assert_eq!(values.len(), 4);
+        assert_eq!(values, vec![0.05, 0.1, 0.15, 0.2]);
+
+        let factory = || -> Box<dyn Scenario> { Box::new(TrustEmergenceScenario::new()) };
+        let sweep =
+            SimulationRunner::run_sweep(config.clone(), factory, "gamma", &values, &[7]).unwrap();
+        assert_eq!(sweep.rows.len(), 4);
+        assert!(sweep.rows.iter().all(|(_, batch)| batch.summaries.len() == 1));
+
+        let mut csv = Vec::new();
+        sweep.export_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines[0], "gamma,final_mean_trust,detection_count");
+        assert_eq!(lines.len(), 5);
+        assert!(lines[1].starts_with("0.05,"));
+
+        let err = SimulationRunner::run_sweep(config, factory, "beta", &values, &[7]).unwrap_err();
+        assert_eq!(err.0, "beta");
+    }
+
+    #[test]
+    fn test_sample_stats() {
+        let stats = SampleStats::from_samples(&[1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(stats.count, 4);
+        assert!((stats.mean - 2.5).abs() < 1e-12);
+        assert!((stats.std_dev - 1.25f64.sqrt()).abs() < 1e-12);
+        assert_eq!((stats.min, stats.max), (1.0, 4.0));
+    }
+
+    #[test]
+    fn test_convergence_stop() {
+        let config = SimulationConfig::default()
+            .with_ticks(1000)
+            .with_convergence_stop(20, 5e-3);
+        let result = quick_run_with(10, config, |_| {});
+
+        assert!(result.completed);
+        assert!(result.stopped_early);
+        assert!(result.final_tick < 1000);
+    }
+
+    #[test]
+    fn test_convergence_stop_waits_for_pending_events() {
+        use crate::events::Event;
+        use symbiont_core::node::Node;
+        use symbiont_core::types::NodeId;
+
+        let config = SimulationConfig::default()
+            .with_ticks(1000)
+            .with_convergence_stop(20, 5e-3);
+        let result = quick_run_with(10, config, |scheduler| {
+            scheduler.schedule(600, Event::node_join(Node::new(NodeId::from_index(600))));
+        });
+
+        assert!(result.final_tick > 600);
+    }
 }
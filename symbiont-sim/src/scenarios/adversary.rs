@@ -5,6 +5,7 @@ use crate::events::{AgentType, Event, EventScheduler};
 use crate::network::Network;
 use crate::scenarios::Scenario;
 use symbiont_core::capability::common;
+use symbiont_core::constants::SWIFT_TRUST_BASE;
 use symbiont_core::node::Node;
 use symbiont_core::types::NodeId;
 
@@ -17,6 +18,8 @@ pub enum AdversaryType {
     FreeRider,
     /// Sybil cluster (coordinated fake identities)
     Sybil,
+    /// Whitewasher (sheds bad reputation by rejoining with a fresh identity)
+    Whitewasher,
 }
 
 /// Scenario for testing adversary detection
@@ -31,6 +34,8 @@ pub struct AdversaryScenario {
     pub defection_tick: u64,
     /// Interaction rate for honest nodes
     pub honest_interaction_rate: f64,
+    /// For whitewashers: trust below which they rejoin
+    pub whitewash_floor: f64,
 }
 
 impl Default for AdversaryScenario {
@@ -41,6 +46,8 @@ impl Default for AdversaryScenario {
             adversary_count: 3,
             defection_tick: 100,
             honest_interaction_rate: 0.5,
+            // A fresh identity starts at Swift Trust, so rejoining pays off below it
+            whitewash_floor: SWIFT_TRUST_BASE,
         }
     }
 }
@@ -71,6 +78,12 @@ impl AdversaryScenario {
         self.defection_tick = tick;
         self
     }
+
+    /// Set trust floor (for whitewashers)
+    pub fn with_trust_floor(mut self, floor: f64) -> Self {
+        self.whitewash_floor = floor;
+        self
+    }
 }
 
 impl Scenario for AdversaryScenario {
@@ -138,6 +151,22 @@ impl Scenario for AdversaryScenario {
                     );
                 }
             }
+            AdversaryType::Whitewasher => {
+                for i in 0..self.adversary_count {
+                    let id = NodeId::from_index(base_id + i as u64);
+                    let mut node = Node::new(id);
+                    node.add_capability(common::analysis());
+
+                    // Schedule node join with whitewasher agent
+                    let agent_type = AgentType::Whitewasher {
+                        trust_floor: self.whitewash_floor,
+                    };
+                    scheduler.schedule(
+                        self.inject_at_tick,
+                        Event::node_join_with_agent(node, agent_type),
+                    );
+                }
+            }
         }
     }
 
@@ -146,6 +175,7 @@ impl Scenario for AdversaryScenario {
             AdversaryType::Strategic => "adversary_strategic",
             AdversaryType::FreeRider => "adversary_free_rider",
             AdversaryType::Sybil => "adversary_sybil",
+            AdversaryType::Whitewasher => "adversary_whitewasher",
         }
     }
 
@@ -154,6 +184,9 @@ impl Scenario for AdversaryScenario {
             AdversaryType::Strategic => "Inject strategic adversaries that build trust then defect",
             AdversaryType::FreeRider => "Inject free riders that take but don't contribute",
             AdversaryType::Sybil => "Inject a coordinated Sybil cluster",
+            AdversaryType::Whitewasher => {
+                "Inject whitewashers that rejoin under fresh identities to shed bad reputation"
+            }
         }
     }
 }
@@ -204,4 +237,144 @@ mod tests {
 
         assert!(result.completed);
     }
+
+    #[test]
+    fn test_whitewasher_scenario() {
+        let config = SimulationConfig::default()
+            .with_ticks(150)
+            .with_network(
+                NetworkConfig::default()
+                    .with_nodes(8)
+                    .with_capability(common::analysis())
+                    .with_seed(7),
+            );
+
+        // Spreading its poor work across many partners keeps the whitewasher's
+        // diversity high, so its trust settles just above the default floor
+        let scenario = AdversaryScenario::new(AdversaryType::Whitewasher)
+            .inject_at(0)
+            .with_count(1)
+            .with_trust_floor(0.5);
+
+        let mut runner = SimulationRunner::new(config);
+        runner.apply_scenario(&scenario);
+        let original = NodeId::from_index(8 + 1000);
+
+        let result = runner.run();
+        assert!(result.completed);
+
+        let network = runner.network();
+        // The original identity is gone but a whitewasher is still active
+        assert!(network.get_node(&original).is_none());
+        let current: Vec<_> = network
+            .nodes()
+            .keys()
+            .filter(|id| network.agent_type_of(id) == Some("whitewasher"))
+            .cloned()
+            .collect();
+        assert_eq!(current.len(), 1);
+        assert_ne!(current[0], original);
+
+        // ...and every identity it used, old and new, produced poor work
+        let honest: Vec<_> = (0..8).map(NodeId::from_index).collect();
+        let identities: Vec<_> = network
+            .metrics
+            .quality_history
+            .iter()
+            .filter(|(id, _)| !honest.contains(id))
+            .collect();
+        assert!(identities.len() >= 2);
+        assert!(identities
+            .iter()
+            .all(|(_, qualities)| qualities.iter().all(|q| q.value() < 0.5)));
+    }
+
+    #[test]
+    fn test_adversary_layered_onto_warm_start() {
+        let network = NetworkConfig::default()
+            .with_nodes(10)
+            .with_capability(common::analysis())
+            .with_seed(5);
+
+        // Converge an honest network first
+        let mut prior_run = SimulationRunner::new(
+            SimulationConfig::default().with_ticks(500).with_network(network.clone()),
+        );
+        prior_run.apply_scenario(&crate::scenarios::TrustEmergenceScenario::default());
+        prior_run.run();
+        let prior = prior_run.network().snapshot();
+
+        let config = SimulationConfig::default()
+            .with_ticks(400)
+            .with_network(network.with_prior(prior.clone()));
+        let mut runner = SimulationRunner::new(config);
+
+        // Nodes resume where they left off, on a fresh tick count
+        let warm = runner.network();
+        assert_eq!(warm.tick, 0);
+        assert_eq!(warm.node_count(), prior.nodes.len());
+        for node in &prior.nodes {
+            let resumed = warm.get_node(&node.id).unwrap();
+            assert_eq!(resumed.trust, node.trust);
+            assert_eq!(resumed.connections.len(), node.connections.len());
+        }
+
+        let scenario = AdversaryScenario::new(AdversaryType::FreeRider)
+            .inject_at(10)
+            .with_count(1);
+        runner.apply_scenario(&scenario);
+        runner.run();
+
+        let adversary = NodeId::from_index(10 + 1000);
+        let network = runner.network();
+        assert_eq!(network.node_count(), prior.nodes.len() + 1);
+        assert!(network
+            .metrics
+            .detection_events
+            .iter()
+            .any(|d| d.node_id == adversary && d.threat_type == "cheating"));
+    }
+
+    #[test]
+    fn test_warm_start_detects_faster_than_cold() {
+        let network = NetworkConfig::default()
+            .with_nodes(10)
+            .with_capability(common::analysis())
+            .with_seed(5);
+
+        let mut prior_run = SimulationRunner::new(
+            SimulationConfig::default().with_ticks(500).with_network(network.clone()),
+        );
+        prior_run.apply_scenario(&crate::scenarios::TrustEmergenceScenario::default());
+        prior_run.run();
+        let prior = prior_run.network().snapshot();
+
+        // Ticks from injecting a skimming adversary to its first detection
+        let detection_delay = |network: NetworkConfig| {
+            let mut runner = SimulationRunner::new(
+                SimulationConfig::default().with_ticks(300).with_network(network),
+            );
+            runner.apply_scenario(&crate::scenarios::TrustEmergenceScenario::default());
+            let adversary = NodeId::from_index(1000);
+            let mut node = Node::new(adversary);
+            node.add_capability(common::analysis());
+            let agent_type = AgentType::Camouflage { skim: 0.5 };
+            runner.scheduler_mut().schedule(10, Event::node_join_with_agent(node, agent_type));
+            runner.run();
+            runner
+                .network()
+                .metrics
+                .detection_events
+                .iter()
+                .filter(|d| d.node_id == adversary)
+                .map(|d| d.tick - 10)
+                .min()
+        };
+
+        // Settled peers keep mostly to their established partners, so the
+        // newcomer's history is dominated by its own lopsided exchanges
+        let cold = detection_delay(network.clone()).expect("cold start detects the adversary");
+        let warm = detection_delay(network.with_prior(prior)).expect("warm start detects it");
+        assert!(warm < cold, "warm start took {warm} ticks, cold start {cold}");
+    }
 }
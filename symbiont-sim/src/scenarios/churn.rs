@@ -0,0 +1,181 @@
+//! Churn scenario - test trust stability under continuous membership change.
+
+use crate::agents::HonestAgent;
+use crate::events::{AgentType, Event, EventScheduler};
+use crate::network::Network;
+use crate::scenarios::Scenario;
+use symbiont_core::node::Node;
+use symbiont_core::types::NodeId;
+
+/// Identity range for nodes joining through churn
+const CHURN_ID_BASE: u64 = 2_000_000;
+
+/// Scenario with nodes continuously joining and leaving
+pub struct ChurnScenario {
+    /// Expected joins per tick
+    pub join_rate: f64,
+    /// Expected departures per tick
+    pub leave_rate: f64,
+    /// Tick at which churn begins
+    pub start_tick: u64,
+    /// Interaction rate for honest nodes
+    pub honest_interaction_rate: f64,
+}
+
+impl Default for ChurnScenario {
+    fn default() -> Self {
+        Self {
+            join_rate: 0.05,
+            leave_rate: 0.05,
+            start_tick: 10,
+            honest_interaction_rate: 0.5,
+        }
+    }
+}
+
+impl ChurnScenario {
+    /// Create a new scenario
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set joins per tick
+    pub fn with_join_rate(mut self, rate: f64) -> Self {
+        self.join_rate = rate;
+        self
+    }
+
+    /// Set departures per tick
+    pub fn with_leave_rate(mut self, rate: f64) -> Self {
+        self.leave_rate = rate;
+        self
+    }
+
+    /// Set when churn begins
+    pub fn starting_at(mut self, tick: u64) -> Self {
+        self.start_tick = tick;
+        self
+    }
+}
+
+/// Ticks between events for a per-tick rate (None if the rate is zero)
+fn rate_interval(rate: f64) -> Option<u64> {
+    (rate > 0.0).then(|| (1.0 / rate).round().max(1.0) as u64)
+}
+
+impl Scenario for ChurnScenario {
+    fn setup(&self, network: &mut Network, scheduler: &mut EventScheduler) {
+        // Add honest agents to existing nodes
+        let node_ids: Vec<_> = network.nodes().keys().cloned().collect();
+        for id in node_ids {
+            let agent = HonestAgent::new(self.honest_interaction_rate);
+            network.set_agent(id, Box::new(agent));
+        }
+
+        // Newcomers offer the same capabilities as the founding members
+        let capabilities: Vec<_> = network
+            .nodes()
+            .values()
+            .next()
+            .map(|n| {
+                n.capabilities
+                    .values()
+                    .map(|s| s.capability.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        if let Some(interval) = rate_interval(self.join_rate) {
+            let interaction_rate = self.honest_interaction_rate;
+            scheduler.schedule_recurring(self.start_tick, interval, None, move |tick| {
+                let node = Node::with_capabilities(
+                    NodeId::from_index(CHURN_ID_BASE + tick),
+                    capabilities.clone(),
+                );
+                let agent_type = AgentType::Honest {
+                    interaction_rate,
+                    base_quality: 0.8,
+                };
+                Event::node_join_with_agent(node, agent_type)
+            });
+        }
+
+        if let Some(interval) = rate_interval(self.leave_rate) {
+            scheduler.schedule_recurring(self.start_tick, interval, None, |_| {
+                Event::low_trust_leave()
+            });
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "churn"
+    }
+
+    fn description(&self) -> &'static str {
+        "Nodes continuously join and leave, with low-trust members leaving first"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::NetworkConfig;
+    use symbiont_core::capability::common;
+    use symbiont_core::types::Score;
+
+    #[test]
+    fn test_churn_keeps_node_count_bounded() {
+        let mut network = Network::from_config(
+            NetworkConfig::default()
+                .with_nodes(30)
+                .with_capability(common::analysis())
+                .with_connection_prob(0.2)
+                .with_seed(11),
+        );
+        let mut scheduler = EventScheduler::new();
+        let scenario = ChurnScenario::new()
+            .with_join_rate(0.1)
+            .with_leave_rate(0.1);
+        scenario.setup(&mut network, &mut scheduler);
+
+        let mut joined = 0;
+        for _ in 0..500 {
+            for event in scheduler.due_at(network.tick) {
+                if matches!(event, Event::NodeJoin { .. }) {
+                    joined += 1;
+                }
+                network.queue_event(event);
+            }
+            network.tick();
+
+            let count = network.node_count();
+            assert!(
+                (28..=32).contains(&count),
+                "node count {count} out of bounds"
+            );
+        }
+
+        // Membership actually turned over
+        assert!(joined >= 45);
+        assert!(network
+            .nodes()
+            .keys()
+            .any(|id| *id >= NodeId::from_index(CHURN_ID_BASE)));
+    }
+
+    #[test]
+    fn test_low_trust_members_leave_first() {
+        let mut network = Network::from_config(NetworkConfig::default().with_nodes(5).with_seed(3));
+        for node in network.nodes_mut().values_mut() {
+            node.trust = Score::ONE;
+        }
+        let weak = NodeId::from_index(2);
+        network.get_node_mut(&weak).unwrap().trust = Score::new(0.1);
+
+        network.queue_event(Event::low_trust_leave());
+        network.process_events();
+
+        assert_eq!(network.node_count(), 4);
+        assert!(network.get_node(&weak).is_none());
+    }
+}
@@ -0,0 +1,151 @@
+//! Consensus scenario - deliberate a decision and fall back to agree-to-disagree.
+
+use crate::agents::HonestAgent;
+use crate::decision::Decision;
+use crate::events::EventScheduler;
+use crate::network::Network;
+use crate::scenarios::Scenario;
+use symbiont_core::types::{Score, TaskId};
+
+/// Scenario where nodes deliberate on a decision over the trust graph
+///
+/// The network starts split into two camps whose positions sit `polarization`
+/// apart. Positions drift toward trusted neighbors each tick; if the decision
+/// gets stuck at low criticality, agree-to-disagree settles it.
+pub struct ConsensusScenario {
+    /// How critical the decision is (ATD only fires below 0.8)
+    pub criticality: f64,
+    /// Fraction of the gap to the neighbor mean closed each tick
+    pub drift_rate: f64,
+    /// Distance between the two camps' starting positions, in [0, 1]
+    pub polarization: f64,
+    /// Interaction rate for agents
+    pub interaction_rate: f64,
+}
+
+impl Default for ConsensusScenario {
+    fn default() -> Self {
+        Self {
+            criticality: 0.5,
+            drift_rate: 0.1,
+            polarization: 0.6,
+            interaction_rate: 0.5,
+        }
+    }
+}
+
+impl ConsensusScenario {
+    /// Create a new scenario
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set decision criticality
+    pub fn with_criticality(mut self, criticality: f64) -> Self {
+        self.criticality = criticality;
+        self
+    }
+
+    /// Set drift rate toward neighbors
+    pub fn with_drift_rate(mut self, rate: f64) -> Self {
+        self.drift_rate = rate;
+        self
+    }
+
+    /// Set distance between the camps' starting positions
+    pub fn with_polarization(mut self, polarization: f64) -> Self {
+        self.polarization = polarization.clamp(0.0, 1.0);
+        self
+    }
+}
+
+impl Scenario for ConsensusScenario {
+    fn setup(&self, network: &mut Network, _scheduler: &mut EventScheduler) {
+        let mut node_ids: Vec<_> = network.nodes().keys().cloned().collect();
+        node_ids.sort();
+
+        // Lower half of ids start in one camp, upper half in the other
+        let half = node_ids.len() / 2;
+        let positions = node_ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| {
+                let offset = if i < half { -0.5 } else { 0.5 };
+                (*id, 0.5 + offset * self.polarization)
+            })
+            .collect();
+
+        for id in node_ids {
+            network.set_agent(id, Box::new(HonestAgent::new(self.interaction_rate)));
+        }
+
+        let decision = Decision::new(TaskId::new(0), positions, Score::new(self.criticality))
+            .with_drift_rate(self.drift_rate);
+        network.start_decision(decision);
+    }
+
+    fn name(&self) -> &'static str {
+        "consensus"
+    }
+
+    fn description(&self) -> &'static str {
+        "Deliberate a polarized decision, falling back to agree-to-disagree when stuck"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::NetworkConfig;
+    use symbiont_core::capability::common;
+    use symbiont_core::convergence::ConvergenceState;
+
+    fn run(scenario: ConsensusScenario, ticks: u64) -> Network {
+        let mut network = Network::from_config(
+            NetworkConfig::default()
+                .with_nodes(16)
+                .with_capability(common::analysis())
+                .with_connection_prob(0.3)
+                .with_seed(11),
+        );
+        let mut scheduler = EventScheduler::new();
+        scenario.setup(&mut network, &mut scheduler);
+        for _ in 0..ticks {
+            network.tick();
+        }
+        network
+    }
+
+    #[test]
+    fn test_polarized_low_criticality_invokes_atd() {
+        let scenario = ConsensusScenario::new()
+            .with_polarization(1.0)
+            .with_drift_rate(0.01)
+            .with_criticality(0.2);
+        let network = run(scenario, 30);
+
+        let decision = network.decision().unwrap();
+        assert!(decision.atd_invoked());
+        assert!(decision.dissent_count() > 0);
+
+        let summary = network.metrics.summary();
+        assert!(matches!(
+            summary.decision_state,
+            Some(ConvergenceState::Polarized | ConvergenceState::Stuck)
+        ));
+        assert_eq!(summary.dissent_count, decision.dissent_count());
+    }
+
+    #[test]
+    fn test_critical_decision_keeps_deliberating() {
+        let scenario = ConsensusScenario::new()
+            .with_polarization(1.0)
+            .with_drift_rate(0.01)
+            .with_criticality(0.9);
+        let network = run(scenario, 30);
+
+        let decision = network.decision().unwrap();
+        assert!(!decision.atd_invoked());
+        assert_eq!(network.metrics.summary().dissent_count, 0);
+    }
+}
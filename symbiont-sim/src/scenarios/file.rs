@@ -0,0 +1,261 @@
+//! File scenario - a scenario described by a JSON spec instead of code.
+
+use crate::agents::HonestAgent;
+use crate::events::{AgentType, Event, EventScheduler};
+use crate::network::{Network, NetworkConfig};
+use crate::scenarios::Scenario;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use symbiont_core::capability::{common, Capability};
+use symbiont_core::node::Node;
+use symbiont_core::types::NodeId;
+use thiserror::Error;
+
+/// Offset from the initial node count where injected identities start
+const INJECTED_ID_OFFSET: u64 = 1000;
+
+/// Failure to load a scenario spec
+#[derive(Debug, Error)]
+pub enum ScenarioError {
+    /// The spec file could not be read
+    #[error("failed to read scenario file: {0}")]
+    Io(#[from] std::io::Error),
+    /// The spec is not valid JSON for a [`ScenarioSpec`]
+    #[error("invalid scenario spec: {0}")]
+    Json(#[from] serde_json::Error),
+    /// A capability name is not one of the common capabilities
+    #[error("unknown capability: {0}")]
+    UnknownCapability(String),
+    /// The injections need more node ids than fit in a u64
+    #[error("injected node ids overflow")]
+    IdOverflow,
+}
+
+/// Nodes of one agent type joining at a tick
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Injection {
+    /// Tick the nodes join at
+    pub tick: u64,
+    /// Behavior of the joining nodes
+    pub agent: AgentType,
+    /// Number of nodes to inject
+    #[serde(default = "default_count")]
+    pub count: usize,
+}
+
+fn default_count() -> usize {
+    1
+}
+
+fn default_interaction_rate() -> f64 {
+    0.5
+}
+
+/// Serializable description of a scenario
+///
+/// Every field is optional; unset network fields keep the runner's values.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScenarioSpec {
+    /// Number of initial nodes
+    #[serde(default)]
+    pub nodes: Option<usize>,
+    /// Common capability names given to every node
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+    /// Probability of connection between any two initial nodes
+    #[serde(default)]
+    pub connection_prob: Option<f64>,
+    /// Interaction rate of the honest agents driving the initial nodes
+    #[serde(default = "default_interaction_rate")]
+    pub honest_interaction_rate: f64,
+    /// Scheduled node injections
+    #[serde(default)]
+    pub injections: Vec<Injection>,
+}
+
+/// Scenario built from a [`ScenarioSpec`]
+///
+/// Initial nodes get honest agents; each injection schedules `count` nodes
+/// of its agent type. Sybil and stealth Sybil injections with no listed
+/// cluster form a cluster of their own nodes.
+pub struct FileScenario {
+    /// The spec this scenario was built from
+    pub spec: ScenarioSpec,
+    /// Resolved capabilities from the spec
+    capabilities: Vec<Capability>,
+}
+
+impl FileScenario {
+    /// Build a scenario from a spec, resolving capability names
+    pub fn from_spec(spec: ScenarioSpec) -> Result<Self, ScenarioError> {
+        let capabilities = spec
+            .capabilities
+            .iter()
+            .map(|name| {
+                common::by_name(name).ok_or_else(|| ScenarioError::UnknownCapability(name.clone()))
+            })
+            .collect::<Result<_, _>>()?;
+
+        // Injected ids count up from past the initial nodes without wrapping
+        let counts = spec.injections.iter().map(|i| i.count as u64);
+        std::iter::once(spec.nodes.unwrap_or(0) as u64)
+            .chain(counts)
+            .try_fold(INJECTED_ID_OFFSET, u64::checked_add)
+            .ok_or(ScenarioError::IdOverflow)?;
+        Ok(Self { spec, capabilities })
+    }
+
+    /// Parse a scenario from JSON text
+    pub fn from_json(json: &str) -> Result<Self, ScenarioError> {
+        Self::from_spec(serde_json::from_str(json)?)
+    }
+
+    /// Load a scenario from a JSON file
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ScenarioError> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    /// Apply the spec's network settings on top of `base`
+    pub fn network_config(&self, mut base: NetworkConfig) -> NetworkConfig {
+        if let Some(nodes) = self.spec.nodes {
+            base.node_count = nodes;
+        }
+        if let Some(prob) = self.spec.connection_prob {
+            base.connection_probability = prob;
+        }
+        if !self.capabilities.is_empty() {
+            base.capabilities = self.capabilities.clone();
+        }
+        base
+    }
+}
+
+impl Scenario for FileScenario {
+    fn setup(&self, network: &mut Network, scheduler: &mut EventScheduler) {
+        let mut node_ids: Vec<_> = network.nodes().keys().cloned().collect();
+        node_ids.sort();
+
+        // Injected nodes offer the spec's capabilities, or the founders' if none
+        let capabilities = if self.capabilities.is_empty() {
+            node_ids
+                .first()
+                .and_then(|id| network.get_node(id))
+                .map(|n| n.capabilities.values().map(|s| s.capability.clone()).collect())
+                .unwrap_or_default()
+        } else {
+            self.capabilities.clone()
+        };
+
+        let mut next_id = (network.node_count() as u64).saturating_add(INJECTED_ID_OFFSET);
+        for id in node_ids {
+            let agent = HonestAgent::new(self.spec.honest_interaction_rate);
+            network.set_agent(id, Box::new(agent));
+        }
+
+        for injection in &self.spec.injections {
+            // Only reachable when the network outgrew the spec's node count
+            let Some(end) = next_id.checked_add(injection.count as u64) else {
+                break;
+            };
+            let ids: Vec<NodeId> = (next_id..end).map(NodeId::from_index).collect();
+            next_id = end;
+
+            let agent_type = match &injection.agent {
+                AgentType::Sybil { cluster_members } if cluster_members.is_empty() => {
+                    AgentType::Sybil { cluster_members: ids.clone() }
+                }
+                AgentType::StealthSybil { cluster_members } if cluster_members.is_empty() => {
+                    AgentType::StealthSybil { cluster_members: ids.clone() }
+                }
+                other => other.clone(),
+            };
+
+            for id in ids {
+                let node = Node::with_capabilities(id, capabilities.clone());
+                scheduler.schedule(
+                    injection.tick,
+                    Event::node_join_with_agent(node, agent_type.clone()),
+                );
+            }
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "file"
+    }
+
+    fn description(&self) -> &'static str {
+        "Scenario loaded from a JSON spec"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::{SimulationConfig, SimulationRunner};
+
+    const SPEC: &str = r#"{
+        "nodes": 6,
+        "capabilities": ["analysis", "validation"],
+        "connection_prob": 0.5,
+        "injections": [
+            { "tick": 20, "agent": { "type": "free_rider", "interaction_rate": 0.5 }, "count": 2 },
+            { "tick": 30, "agent": { "type": "sybil" }, "count": 3 }
+        ]
+    }"#;
+
+    #[test]
+    fn test_injection_fires_at_tick() {
+        let scenario = FileScenario::from_json(SPEC).unwrap();
+        let config = SimulationConfig::default()
+            .with_ticks(40)
+            .with_network(scenario.network_config(NetworkConfig::default().with_seed(8)));
+
+        let mut runner = SimulationRunner::new(config);
+        runner.apply_scenario(&scenario);
+        assert_eq!(runner.network().node_count(), 6);
+        let founder = runner.network().get_node(&NodeId::from_index(0)).unwrap();
+        assert_eq!(founder.capabilities.len(), 2);
+
+        while runner.network().tick < 20 {
+            runner.step();
+        }
+        assert_eq!(runner.network().node_count(), 6);
+
+        // Events due at tick 20 are processed during that tick
+        runner.step();
+        assert_eq!(runner.network().node_count(), 8);
+        let injected = NodeId::from_index(6 + INJECTED_ID_OFFSET);
+        assert_eq!(runner.network().agent_type_of(&injected), Some("free_rider"));
+
+        runner.run();
+        assert_eq!(runner.network().node_count(), 11);
+        let sybil = NodeId::from_index(8 + INJECTED_ID_OFFSET);
+        assert_eq!(runner.network().agent_type_of(&sybil), Some("sybil"));
+    }
+
+    #[test]
+    fn test_invalid_specs_rejected() {
+        let unknown = FileScenario::from_json(r#"{ "capabilities": ["juggling"] }"#);
+        assert!(matches!(
+            unknown,
+            Err(ScenarioError::UnknownCapability(name)) if name == "juggling"
+        ));
+
+        let bad_agent = FileScenario::from_json(
+            r#"{ "injections": [{ "tick": 1, "agent": { "type": "saboteur" } }] }"#,
+        );
+        assert!(matches!(bad_agent, Err(ScenarioError::Json(_))));
+
+        let injection = format!(
+            r#"{{ "tick": 1, "agent": {{ "type": "passive" }}, "count": {} }}"#,
+            u64::MAX
+        );
+        let overflow = FileScenario::from_json(&format!(r#"{{ "injections": [{injection}] }}"#));
+        assert!(matches!(overflow, Err(ScenarioError::IdOverflow)));
+
+        let empty = FileScenario::from_json("{}").unwrap();
+        assert_eq!(empty.spec.honest_interaction_rate, 0.5);
+        assert_eq!(empty.network_config(NetworkConfig::default()).node_count, 10);
+    }
+}
@@ -1,10 +1,18 @@
 //! Predefined simulation scenarios.
 
 pub mod adversary;
+mod churn;
+mod consensus;
+mod file;
+mod partition;
 mod trust_emergence;
 pub mod workflow;
 
 pub use adversary::{AdversaryScenario, AdversaryType};
+pub use churn::ChurnScenario;
+pub use consensus::ConsensusScenario;
+pub use file::{FileScenario, Injection, ScenarioError, ScenarioSpec};
+pub use partition::PartitionScenario;
 pub use trust_emergence::TrustEmergenceScenario;
 pub use workflow::{WorkflowScenario, WorkflowType};
 
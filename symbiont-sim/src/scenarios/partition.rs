@@ -0,0 +1,207 @@
+//! Partition scenario - split the network in two, then heal it.
+
+use crate::agents::HonestAgent;
+use crate::events::{Event, EventScheduler};
+use crate::network::Network;
+use crate::scenarios::Scenario;
+use symbiont_core::node::NodeStatus;
+use symbiont_core::types::NodeId;
+
+/// Scenario that splits the network into two halves and later reconnects them
+///
+/// The halves run honest agents of different quality, so their trust drifts
+/// apart while they are cut off and re-converges once they mix again.
+pub struct PartitionScenario {
+    /// Tick at which the network splits
+    pub partition_tick: u64,
+    /// Tick at which connectivity is restored
+    pub heal_tick: u64,
+    /// Interaction rate for agents
+    pub interaction_rate: f64,
+    /// Base quality of agents in the isolated half
+    pub side_quality: f64,
+    /// Base quality of agents in the remaining half
+    pub rest_quality: f64,
+}
+
+impl Default for PartitionScenario {
+    fn default() -> Self {
+        Self {
+            partition_tick: 100,
+            heal_tick: 300,
+            interaction_rate: 0.5,
+            side_quality: 0.9,
+            rest_quality: 0.3,
+        }
+    }
+}
+
+impl PartitionScenario {
+    /// Create a new scenario
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set when the network splits and when it heals
+    pub fn with_window(mut self, partition_tick: u64, heal_tick: u64) -> Self {
+        self.partition_tick = partition_tick;
+        self.heal_tick = heal_tick;
+        self
+    }
+
+    /// Set base quality for each half
+    pub fn with_qualities(mut self, side: f64, rest: f64) -> Self {
+        self.side_quality = side;
+        self.rest_quality = rest;
+        self
+    }
+
+    /// The isolated half: the lower half of node ids
+    pub fn side(network: &Network) -> Vec<NodeId> {
+        let mut ids: Vec<_> = network.nodes().keys().cloned().collect();
+        ids.sort();
+        ids.truncate(ids.len() / 2);
+        ids
+    }
+
+    /// Variance between the two halves' mean trust
+    ///
+    /// Var = ((μ_side - μ_rest) / 2)²
+    pub fn divergence(network: &Network) -> f64 {
+        let side = Self::side(network);
+        let (mut side_sum, mut rest_sum) = (0.0, 0.0);
+        for node in network.nodes().values() {
+            if side.contains(&node.id) {
+                side_sum += node.trust.value();
+            } else {
+                rest_sum += node.trust.value();
+            }
+        }
+
+        let rest_count = network.node_count() - side.len();
+        if side.is_empty() || rest_count == 0 {
+            return 0.0;
+        }
+        let gap = side_sum / side.len() as f64 - rest_sum / rest_count as f64;
+        (gap / 2.0).powi(2)
+    }
+}
+
+impl Scenario for PartitionScenario {
+    fn setup(&self, network: &mut Network, scheduler: &mut EventScheduler) {
+        // An established network: members are past probation
+        for node in network.nodes_mut().values_mut() {
+            node.status = NodeStatus::Member;
+        }
+
+        let side = Self::side(network);
+        let node_ids: Vec<_> = network.nodes().keys().cloned().collect();
+        for id in node_ids {
+            let quality = if side.contains(&id) {
+                self.side_quality
+            } else {
+                self.rest_quality
+            };
+            let agent = HonestAgent::new(self.interaction_rate).with_quality(quality, 0.05);
+            network.set_agent(id, Box::new(agent));
+        }
+
+        scheduler.schedule(self.partition_tick, Event::partition(side));
+        scheduler.schedule(self.heal_tick, Event::heal());
+    }
+
+    fn name(&self) -> &'static str {
+        "partition"
+    }
+
+    fn description(&self) -> &'static str {
+        "Split the network into two halves, run them independently, then heal"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::NetworkConfig;
+    use symbiont_core::capability::common;
+    use symbiont_core::types::Score;
+
+    #[test]
+    fn test_partition_severs_and_restores_cross_links() {
+        let mut network = Network::from_config(
+            NetworkConfig::default()
+                .with_nodes(10)
+                .with_connection_prob(1.0)
+                .with_seed(5),
+        );
+        let side = PartitionScenario::side(&network);
+
+        network.queue_event(Event::partition(side.clone()));
+        network.process_events();
+        assert!(network.is_partitioned());
+        for node in network.nodes().values() {
+            assert_eq!(node.connections.len(), 4, "only same-side links remain");
+        }
+
+        // Interactions across the split are dropped
+        let (a, b) = (side[0], NodeId::from_index(9));
+        network.queue_event(Event::interaction(a, b, Score::ONE));
+        network.process_events();
+        assert!(network.get_node(&a).unwrap().get_connection(&b).is_none());
+
+        network.queue_event(Event::heal());
+        network.process_events();
+        assert!(!network.is_partitioned());
+        for node in network.nodes().values() {
+            assert_eq!(node.connections.len(), 9);
+        }
+    }
+
+    #[test]
+    fn test_divergence_peaks_during_partition() {
+        let mut network = Network::from_config(
+            NetworkConfig::default()
+                .with_nodes(20)
+                .with_capability(common::analysis())
+                .with_connection_prob(0.3)
+                .with_seed(8),
+        );
+        let mut scheduler = EventScheduler::new();
+        let scenario = PartitionScenario::new().with_window(100, 300);
+        scenario.setup(&mut network, &mut scheduler);
+
+        let mut divergence = Vec::new();
+        for _ in 0..500 {
+            for event in scheduler.due_at(network.tick) {
+                network.queue_event(event);
+            }
+            network.tick();
+            divergence.push((network.tick, PartitionScenario::divergence(&network)));
+        }
+
+        let (peak_tick, _) = divergence
+            .iter()
+            .cloned()
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap();
+        assert!(
+            (100..=300).contains(&peak_tick),
+            "divergence peaked at tick {peak_tick}"
+        );
+
+        // Halves drift apart while split and re-converge after healing
+        let mean = |from: u64, to: u64| {
+            let window: Vec<f64> = divergence
+                .iter()
+                .filter(|(t, _)| (from..to).contains(t))
+                .map(|(_, d)| *d)
+                .collect();
+            window.iter().sum::<f64>() / window.len() as f64
+        };
+        let before = mean(50, 100);
+        let during = mean(250, 300);
+        let after = mean(450, 500);
+        assert!(during > before, "during {during} vs before {before}");
+        assert!(after < during, "after {after} vs during {during}");
+    }
+}
@@ -95,4 +95,23 @@ mod tests {
         // In an honest network, trust should generally increase
         assert!(result.summary.final_mean_trust > 0.3);
     }
+
+    #[test]
+    fn test_honest_network_converges_to_high_trust() {
+        let config = SimulationConfig::default()
+            .with_ticks(1000)
+            .with_network(
+                NetworkConfig::default()
+                    .with_nodes(30)
+                    .with_capability(common::analysis())
+                    .with_seed(42),
+            );
+
+        let mut runner = SimulationRunner::new(config);
+        runner.apply_scenario(&TrustEmergenceScenario::new());
+        let result = runner.run();
+
+        assert!(result.summary.final_mean_trust > 0.6);
+        assert!(runner.network().metrics.has_converged(200, 0.02));
+    }
 }
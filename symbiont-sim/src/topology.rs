@@ -0,0 +1,252 @@
+//! Initial network topology and capability assignment.
+//!
+//! Generators return undirected edges between node indices; the network
+//! wires them up and hands out capabilities. All randomness comes from the
+//! caller's RNG, so a seeded network is reproducible.
+
+use rand::Rng;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use symbiont_core::capability::Capability;
+
+/// Shape of the initial connection graph
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub enum Topology {
+    /// Erdős–Rényi: each pair linked with the connection probability
+    #[default]
+    Random,
+    /// Barabási–Albert: each new node links to `m` nodes chosen by degree
+    ScaleFree(usize),
+    /// Watts–Strogatz: a ring lattice of degree `k`, each edge rewired with
+    /// probability `beta`
+    SmallWorld(usize, f64),
+    /// Each node linked to the next, the last back to the first
+    Ring,
+}
+
+impl Topology {
+    /// Undirected edges (i, j) with i ≠ j among `n` nodes, without duplicates
+    pub fn edges<R: Rng>(
+        &self,
+        n: usize,
+        connection_probability: f64,
+        rng: &mut R,
+    ) -> Vec<(usize, usize)> {
+        match *self {
+            Topology::Random => random_edges(n, connection_probability, rng),
+            Topology::ScaleFree(m) => scale_free_edges(n, m, rng),
+            Topology::SmallWorld(k, beta) => small_world_edges(n, k, beta, rng),
+            Topology::Ring => ring_edges(n),
+        }
+    }
+}
+
+/// Which capabilities each created node receives
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub enum CapabilityAssignment {
+    /// Every node gets every capability
+    #[default]
+    AllNodes,
+    /// Node i gets capability i mod |caps|
+    RoundRobin,
+    /// Each node gets each capability independently with probability p
+    RandomSubset(f64),
+}
+
+impl CapabilityAssignment {
+    /// Capabilities for the node at `index`
+    pub fn assign<R: Rng>(
+        &self,
+        index: usize,
+        caps: &[Capability],
+        rng: &mut R,
+    ) -> Vec<Capability> {
+        match *self {
+            CapabilityAssignment::AllNodes => caps.to_vec(),
+            CapabilityAssignment::RoundRobin if caps.is_empty() => Vec::new(),
+            CapabilityAssignment::RoundRobin => vec![caps[index % caps.len()].clone()],
+            CapabilityAssignment::RandomSubset(p) => {
+                caps.iter().filter(|_| rng.gen::<f64>() < p).cloned().collect()
+            }
+        }
+    }
+}
+
+fn random_edges<R: Rng>(n: usize, p: f64, rng: &mut R) -> Vec<(usize, usize)> {
+    let mut edges = Vec::new();
+    for i in 0..n {
+        for j in (i + 1)..n {
+            if rng.gen::<f64>() < p {
+                edges.push((i, j));
+            }
+        }
+    }
+    edges
+}
+
+/// Preferential attachment from a fully connected core of m + 1 nodes
+///
+/// P(attach to j) = deg(j) / Σ deg
+fn scale_free_edges<R: Rng>(n: usize, m: usize, rng: &mut R) -> Vec<(usize, usize)> {
+    let m = m.max(1);
+    let core = (m + 1).min(n);
+    let mut edges = Vec::new();
+    for i in 0..core {
+        for j in (i + 1)..core {
+            edges.push((i, j));
+        }
+    }
+
+    // Each node appears once per incident edge, so a uniform pick is
+    // proportional to degree
+    let mut endpoints: Vec<usize> = edges.iter().flat_map(|&(i, j)| [i, j]).collect();
+    for new in core..n {
+        let mut targets: Vec<usize> = Vec::with_capacity(m);
+        while targets.len() < m {
+            let target = endpoints[rng.gen_range(0..endpoints.len())];
+            if !targets.contains(&target) {
+                targets.push(target);
+            }
+        }
+        for target in targets {
+            edges.push((target, new));
+            endpoints.extend([target, new]);
+        }
+    }
+    edges
+}
+
+/// Ring lattice linking each node to its k/2 nearest neighbors on each side,
+/// then rewiring the far end of each edge with probability beta
+fn small_world_edges<R: Rng>(n: usize, k: usize, beta: f64, rng: &mut R) -> Vec<(usize, usize)> {
+    let half = (k / 2).min(n.saturating_sub(1) / 2);
+    let mut linked = vec![BTreeSet::new(); n];
+    for i in 0..n {
+        for step in 1..=half {
+            let j = (i + step) % n;
+            linked[i].insert(j);
+            linked[j].insert(i);
+        }
+    }
+
+    for step in 1..=half {
+        for i in 0..n {
+            let j = (i + step) % n;
+            // A node linked to everyone has nowhere to rewire to
+            if !linked[i].contains(&j) || linked[i].len() + 1 >= n || rng.gen::<f64>() >= beta {
+                continue;
+            }
+            let mut target = rng.gen_range(0..n);
+            while target == i || linked[i].contains(&target) {
+                target = rng.gen_range(0..n);
+            }
+            linked[i].remove(&j);
+            linked[j].remove(&i);
+            linked[i].insert(target);
+            linked[target].insert(i);
+        }
+    }
+
+    linked
+        .iter()
+        .enumerate()
+        .flat_map(|(i, partners)| partners.range(i + 1..).map(move |&j| (i, j)))
+        .collect()
+}
+
+fn ring_edges(n: usize) -> Vec<(usize, usize)> {
+    match n {
+        0 | 1 => Vec::new(),
+        2 => vec![(0, 1)],
+        _ => (0..n).map(|i| (i.min((i + 1) % n), i.max((i + 1) % n))).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::{Network, NetworkConfig};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use symbiont_core::capability::common;
+
+    fn degrees(n: usize, edges: &[(usize, usize)]) -> Vec<usize> {
+        let mut degree = vec![0; n];
+        for &(i, j) in edges {
+            degree[i] += 1;
+            degree[j] += 1;
+        }
+        degree
+    }
+
+    #[test]
+    fn test_scale_free_degree_is_heavy_tailed() {
+        let n = 500;
+        let mut rng = StdRng::seed_from_u64(3);
+        let edges = Topology::ScaleFree(2).edges(n, 0.0, &mut rng);
+
+        // Core triangle plus two links per later node
+        assert_eq!(edges.len(), 3 + 2 * (n - 3));
+        let degree = degrees(n, &edges);
+        assert!(degree.iter().all(|&d| d >= 2));
+
+        // Hubs far above the mean, while most nodes sit below it
+        let mean = 2.0 * edges.len() as f64 / n as f64;
+        let max = *degree.iter().max().unwrap() as f64;
+        let below = degree.iter().filter(|&&d| (d as f64) < mean).count();
+        assert!(max > 5.0 * mean);
+        assert!(below > n / 2);
+
+        // A random graph of the same density has no such hubs
+        let random = Topology::Random.edges(n, mean / (n - 1) as f64, &mut rng);
+        let random_max = *degrees(n, &random).iter().max().unwrap() as f64;
+        assert!(max > 2.0 * random_max);
+    }
+
+    #[test]
+    fn test_ring_and_small_world_shapes() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let ring = Topology::Ring.edges(6, 0.0, &mut rng);
+        assert_eq!(ring.len(), 6);
+        assert!(degrees(6, &ring).iter().all(|&d| d == 2));
+
+        // Rewiring moves edges but never adds or removes them
+        let lattice = Topology::SmallWorld(4, 0.0).edges(20, 0.0, &mut rng);
+        assert!(degrees(20, &lattice).iter().all(|&d| d == 4));
+        let rewired = Topology::SmallWorld(4, 0.3).edges(20, 0.0, &mut rng);
+        assert_eq!(rewired.len(), lattice.len());
+        assert_ne!(rewired, lattice);
+        assert!(rewired.iter().all(|&(i, j)| i < j));
+
+        assert!(Topology::ScaleFree(3).edges(1, 0.0, &mut rng).is_empty());
+        assert!(Topology::Ring.edges(1, 0.0, &mut rng).is_empty());
+    }
+
+    #[test]
+    fn test_round_robin_spreads_capabilities() {
+        let caps = vec![common::analysis(), common::transformation(), common::validation()];
+        let mut config = NetworkConfig::default()
+            .with_nodes(9)
+            .with_capability_assignment(CapabilityAssignment::RoundRobin)
+            .with_topology(Topology::Ring)
+            .with_seed(1);
+        for cap in &caps {
+            config = config.with_capability(cap.clone());
+        }
+        let network = Network::from_config(config);
+
+        for node in network.nodes().values() {
+            assert_eq!(node.capabilities.len(), 1);
+            assert_eq!(node.connections.len(), 2);
+        }
+        for cap in &caps {
+            let holders = network.nodes().values().filter(|n| n.has_capability(cap.id)).count();
+            assert_eq!(holders, 3);
+        }
+
+        let mut rng = StdRng::seed_from_u64(2);
+        assert!(CapabilityAssignment::RandomSubset(0.0).assign(0, &caps, &mut rng).is_empty());
+        assert_eq!(CapabilityAssignment::RandomSubset(1.0).assign(0, &caps, &mut rng).len(), 3);
+        assert!(CapabilityAssignment::RoundRobin.assign(4, &[], &mut rng).is_empty());
+    }
+}